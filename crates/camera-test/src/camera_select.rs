@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Result};
+use nokhwa::query;
+use nokhwa::utils::{ApiBackend, CameraIndex, CameraInfo};
+use std::io::Write;
+
+/// List every camera the platform's auto-detected backend can see
+pub fn enumerate() -> Result<Vec<CameraInfo>> {
+    query(ApiBackend::Auto).map_err(|e| anyhow!("Failed to enumerate cameras: {}", e))
+}
+
+/// Resolve `--camera`'s value against `cameras`: a bare number is treated
+/// as a device index, anything else is fuzzy-matched (case-insensitive
+/// substring) against each camera's `human_name()`. With no selector at
+/// all, a single detected camera is used automatically and more than one
+/// falls back to an interactive numbered prompt.
+pub fn resolve(selector: Option<&str>, cameras: &[CameraInfo]) -> Result<CameraIndex> {
+    if cameras.is_empty() {
+        return Err(anyhow!("No cameras detected"));
+    }
+
+    if let Some(selector) = selector {
+        if let Ok(n) = selector.parse::<u32>() {
+            return cameras
+                .iter()
+                .find(|c| *c.index() == CameraIndex::Index(n))
+                .map(|c| c.index().clone())
+                .ok_or_else(|| anyhow!("No camera at index {} ({} detected)", n, cameras.len()));
+        }
+        return fuzzy_match(selector, cameras);
+    }
+
+    if cameras.len() == 1 {
+        return Ok(cameras[0].index().clone());
+    }
+
+    prompt_interactive(cameras)
+}
+
+/// Match `needle` against every camera's `human_name()` as a
+/// case-insensitive substring, succeeding only when exactly one camera matches
+fn fuzzy_match(needle: &str, cameras: &[CameraInfo]) -> Result<CameraIndex> {
+    let needle_lower = needle.to_lowercase();
+    let matches: Vec<&CameraInfo> =
+        cameras.iter().filter(|c| c.human_name().to_lowercase().contains(&needle_lower)).collect();
+
+    match matches.as_slice() {
+        [] => Err(anyhow!("No camera matching '{}' found", needle)),
+        [single] => Ok(single.index().clone()),
+        many => Err(anyhow!(
+            "'{}' matches {} cameras ({}) - be more specific",
+            needle,
+            many.len(),
+            many.iter().map(|c| c.human_name()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// List every detected camera and block on stdin for a numeric choice
+fn prompt_interactive(cameras: &[CameraInfo]) -> Result<CameraIndex> {
+    println!("Multiple cameras detected:");
+    for (i, cam) in cameras.iter().enumerate() {
+        println!("  [{}] {} - {}", i, cam.human_name(), cam.description());
+    }
+    print!("Select a camera [0-{}]: ", cameras.len() - 1);
+    std::io::stdout().flush().ok();
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let choice: usize = input.trim().parse().map_err(|_| anyhow!("'{}' is not a valid selection", input.trim()))?;
+
+    cameras
+        .get(choice)
+        .map(|c| c.index().clone())
+        .ok_or_else(|| anyhow!("Selection {} out of range (0-{})", choice, cameras.len() - 1))
+}
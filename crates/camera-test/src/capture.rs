@@ -0,0 +1,209 @@
+use anyhow::{anyhow, Result};
+use image::{ImageBuffer, Rgb};
+use nokhwa::pixel_format::RgbFormat;
+use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType};
+use nokhwa::Camera;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use crate::controls::{self, ControlCommand};
+
+pub type RgbImage = ImageBuffer<Rgb<u8>, Vec<u8>>;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+
+/// Open `index`, trying progressively more constrained formats until one
+/// works - mirrors the cascade `VideoCapture::new_with_controls` in
+/// `monegle-sender` uses for the same reason (not every backend/device
+/// supports "give me any format").
+pub fn open_camera(index: CameraIndex) -> Result<Camera> {
+    println!("Strategy 1: Trying with any available format...");
+    match Camera::new(index.clone(), RequestedFormat::new::<RgbFormat>(RequestedFormatType::None)) {
+        Ok(cam) => {
+            println!("✓ Camera opened successfully!");
+            Ok(cam)
+        }
+        Err(e) => {
+            println!("✗ Strategy 1 failed: {}", e);
+            println!("\nStrategy 2: Trying 640x480...");
+            Camera::new(
+                index,
+                RequestedFormat::new::<RgbFormat>(RequestedFormatType::Closest(
+                    nokhwa::utils::CameraFormat::new(
+                        nokhwa::utils::Resolution::new(WIDTH, HEIGHT),
+                        nokhwa::utils::FrameFormat::YUYV,
+                        30,
+                    ),
+                )),
+            )
+            .map_err(|e| anyhow!("All strategies failed. Last error: {}", e))
+        }
+    }
+}
+
+/// Shared state behind a [`FrameSlotSender`]/[`FrameSlotReceiver`] pair
+struct FrameSlotState {
+    frame: Option<RgbImage>,
+    sender_alive: bool,
+    receiver_alive: bool,
+}
+
+struct FrameSlotInner {
+    state: Mutex<FrameSlotState>,
+    condvar: Condvar,
+    dropped: AtomicU64,
+}
+
+/// Sending half of a single-slot, latest-frame-wins handoff between the
+/// capture thread and the render loop. Unlike a bounded channel, `push`
+/// never blocks: a frame the render loop hasn't consumed yet is simply
+/// overwritten, so a slow camera can never back up into growing latency,
+/// and a slow render loop always sees the most recent frame rather than
+/// working through a backlog of stale ones. Built on `std::sync::{Mutex,
+/// Condvar}` rather than `tokio::sync::Notify` (contrast `FrameSlotSender`
+/// in `monegle-sender`) since camera-test has no async runtime.
+pub struct FrameSlotSender {
+    inner: Arc<FrameSlotInner>,
+}
+
+/// Receiving half of a [`frame_slot`] pair
+pub struct FrameSlotReceiver {
+    inner: Arc<FrameSlotInner>,
+}
+
+/// Result of [`FrameSlotReceiver::recv_timeout`]
+pub enum FrameSlotRecv {
+    Frame(RgbImage),
+    /// No new frame arrived within the timeout - callers should still
+    /// service their own event loop (e.g. pump window events) before
+    /// calling again
+    Timeout,
+    /// The capture thread is gone and no frame is buffered
+    Closed,
+}
+
+/// Create a `FrameSlotSender`/`FrameSlotReceiver` pair
+pub fn frame_slot() -> (FrameSlotSender, FrameSlotReceiver) {
+    let inner = Arc::new(FrameSlotInner {
+        state: Mutex::new(FrameSlotState { frame: None, sender_alive: true, receiver_alive: true }),
+        condvar: Condvar::new(),
+        dropped: AtomicU64::new(0),
+    });
+
+    (FrameSlotSender { inner: inner.clone() }, FrameSlotReceiver { inner })
+}
+
+impl FrameSlotSender {
+    /// Overwrite the slot with the newest frame, counting the previous one
+    /// as dropped if the render loop hadn't consumed it yet. Returns
+    /// `false` once the receiver has been dropped, the signal for the
+    /// capture loop to stop.
+    pub fn push(&self, frame: RgbImage) -> bool {
+        let mut state = self.inner.state.lock().unwrap();
+
+        if !state.receiver_alive {
+            return false;
+        }
+
+        if state.frame.is_some() {
+            let dropped = self.inner.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped % 100 == 0 {
+                eprintln!("Render loop stalled, {} frames dropped so far", dropped);
+            }
+        }
+
+        state.frame = Some(frame);
+        self.inner.condvar.notify_one();
+        true
+    }
+
+    pub fn dropped_frames(&self) -> u64 {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Whether the render loop is still around to receive frames - checked
+    /// by the capture loop's error-retry path, which otherwise has no
+    /// other chance to notice the receiver going away between pushes
+    pub fn is_receiver_alive(&self) -> bool {
+        self.inner.state.lock().unwrap().receiver_alive
+    }
+}
+
+impl Drop for FrameSlotSender {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.sender_alive = false;
+        self.inner.condvar.notify_one();
+    }
+}
+
+impl FrameSlotReceiver {
+    /// Wait up to `timeout` for the next frame
+    pub fn recv_timeout(&mut self, timeout: Duration) -> FrameSlotRecv {
+        let mut state = self.inner.state.lock().unwrap();
+        loop {
+            if let Some(frame) = state.frame.take() {
+                return FrameSlotRecv::Frame(frame);
+            }
+            if !state.sender_alive {
+                return FrameSlotRecv::Closed;
+            }
+
+            let (guard, result) = self.inner.condvar.wait_timeout(state, timeout).unwrap();
+            state = guard;
+            if result.timed_out() && state.frame.is_none() {
+                return FrameSlotRecv::Timeout;
+            }
+        }
+    }
+}
+
+impl Drop for FrameSlotReceiver {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.receiver_alive = false;
+        self.inner.condvar.notify_one();
+    }
+}
+
+/// Runs on its own thread and owns the `Camera` for its whole lifetime:
+/// decodes frames at whatever pace the device delivers them and pushes
+/// each one into `tx`, so a slow camera only ever delays the *next* push,
+/// never blocks the render thread. A capture error no longer freezes the
+/// UI either - it's logged and retried after a brief sleep entirely on
+/// this thread. Also drains `commands` each iteration and applies them to
+/// the `Camera`, since this thread is the only place that holds it.
+pub fn spawn_capture_thread(
+    mut camera: Camera,
+    tx: FrameSlotSender,
+    commands: mpsc::Receiver<ControlCommand>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || loop {
+        for command in commands.try_iter() {
+            if let Err(e) = controls::apply(&mut camera, command) {
+                eprintln!("Failed to apply camera control: {}", e);
+            }
+        }
+
+        match camera.frame() {
+            Ok(frame) => match frame.decode_image::<RgbFormat>() {
+                Ok(image) => {
+                    if !tx.push(image) {
+                        break;
+                    }
+                }
+                Err(e) => eprintln!("Failed to decode frame: {}", e),
+            },
+            Err(e) => {
+                eprintln!("Failed to capture frame: {}", e);
+                std::thread::sleep(Duration::from_millis(100));
+                if !tx.is_receiver_alive() {
+                    break;
+                }
+            }
+        }
+    })
+}
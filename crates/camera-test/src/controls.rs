@@ -0,0 +1,87 @@
+use anyhow::{anyhow, Result};
+use nokhwa::utils::{ControlValueSetter, KnownCameraControl};
+use nokhwa::Camera;
+
+/// A runtime camera-control adjustment requested by the render loop's key
+/// handling, applied on the capture thread since that's where the
+/// `Camera` handle lives (see `spawn_capture_thread`)
+#[derive(Debug, Clone, Copy)]
+pub enum ControlCommand {
+    /// Step a control (e.g. `KnownCameraControl::Brightness`/`Exposure`)
+    /// by one unit in the given direction, clamped to its reported range
+    Step(KnownCameraControl, StepDirection),
+    /// Flip white balance between auto and its last manual value
+    ToggleAutoWhiteBalance,
+    /// Advance to the next compatible resolution/format/frame-rate
+    /// combination (see `format::cycle`)
+    CycleFormat,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum StepDirection {
+    Up,
+    Down,
+}
+
+/// Apply one `ControlCommand` to `camera`. `Step` clamps to the control's
+/// currently reported range rather than erroring on an out-of-bounds
+/// request, since a backend's exact valid range isn't known until queried
+/// - the command only carries a direction. Prints the control's new value
+/// like the existing stats logging, so the effect of a keypress is visible
+/// without a dedicated overlay.
+pub fn apply(camera: &mut Camera, command: ControlCommand) -> Result<()> {
+    match command {
+        ControlCommand::Step(control, direction) => {
+            let controls = camera.camera_controls().map_err(|e| anyhow!("Failed to read camera controls: {}", e))?;
+            let current = controls
+                .into_iter()
+                .find(|c| c.control() == control)
+                .ok_or_else(|| anyhow!("Camera does not support {:?}", control))?;
+
+            let step = if current.step() != 0 { current.step() } else { 1 };
+            let delta = match direction {
+                StepDirection::Up => step,
+                StepDirection::Down => -step,
+            };
+            let next = (current.value() + delta).clamp(current.minimum_value(), current.maximum_value());
+
+            camera
+                .set_camera_control(control, ControlValueSetter::Integer(next))
+                .map_err(|e| anyhow!("Failed to set {:?}: {}", control, e))?;
+            println!(
+                "{:?}: {} -> {} (range {}..={})",
+                control,
+                current.value(),
+                next,
+                current.minimum_value(),
+                current.maximum_value()
+            );
+        }
+        ControlCommand::ToggleAutoWhiteBalance => {
+            let controls = camera.camera_controls().map_err(|e| anyhow!("Failed to read camera controls: {}", e))?;
+            let current = controls
+                .into_iter()
+                .find(|c| c.control() == KnownCameraControl::WhiteBalance)
+                .ok_or_else(|| anyhow!("Camera does not support white balance control"))?;
+
+            let next_auto = !current.flag();
+            camera
+                .set_camera_control(KnownCameraControl::WhiteBalance, ControlValueSetter::Boolean(next_auto))
+                .map_err(|e| anyhow!("Failed to toggle white balance: {}", e))?;
+            println!("White balance: {}", if next_auto { "auto" } else { "manual" });
+        }
+        ControlCommand::CycleFormat => {
+            let switch = crate::format::cycle(camera)?;
+            let format = switch.format;
+            println!(
+                "Switched camera format to {}x{} {:?} @ {}fps",
+                format.resolution().width(),
+                format.resolution().height(),
+                format.format(),
+                format.frame_rate()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Result};
+use nokhwa::utils::CameraFormat;
+use nokhwa::Camera;
+
+/// The `CameraFormat` (resolution, `FrameFormat`, and frame rate together)
+/// `cycle` switched to
+pub struct FormatSwitch {
+    pub format: CameraFormat,
+}
+
+/// Stop the stream, advance `camera` to the next of its compatible formats
+/// (wrapping back to the first past the last), and resume streaming.
+///
+/// Done as stop/reconfigure/open rather than calling `set_camera_format` on
+/// a live stream, since changing format while some backends are streaming
+/// hangs or fails outright. On failure, makes a best-effort attempt to
+/// restore the format the camera was running before this call so a failed
+/// cycle doesn't leave the stream stopped.
+pub fn cycle(camera: &mut Camera) -> Result<FormatSwitch> {
+    let current = camera.camera_format();
+
+    let mut formats = camera
+        .compatible_camera_formats()
+        .map_err(|e| anyhow!("Failed to query compatible formats: {}", e))?;
+    if formats.is_empty() {
+        return Err(anyhow!("Camera reported no compatible formats"));
+    }
+    formats.dedup_by_key(|f| (f.resolution(), f.format(), f.frame_rate()));
+
+    let next_index = formats.iter().position(|&f| f == current).map(|i| (i + 1) % formats.len()).unwrap_or(0);
+    let next = formats[next_index];
+
+    camera.stop_stream().map_err(|e| anyhow!("Failed to stop stream: {}", e))?;
+
+    if let Err(e) = camera.set_camera_format(next).and_then(|_| camera.open_stream()) {
+        // Best-effort restore so a failed cycle doesn't leave the camera
+        // stopped; if this also fails there's nothing more to try here.
+        let _ = camera.set_camera_format(current);
+        let _ = camera.open_stream();
+        return Err(anyhow!("Failed to switch to {:?}: {}", next, e));
+    }
+
+    Ok(FormatSwitch { format: next })
+}
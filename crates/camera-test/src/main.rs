@@ -1,51 +1,78 @@
+mod camera_select;
+mod capture;
+mod controls;
+mod format;
+mod render;
+mod snapshot;
+
 use anyhow::{anyhow, Result};
-use minifb::{Key, Window, WindowOptions};
-use nokhwa::pixel_format::RgbFormat;
-use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType};
-use nokhwa::Camera;
+use clap::Parser;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use capture::{frame_slot, open_camera, spawn_capture_thread, FrameSlotRecv};
+use controls::{ControlCommand, StepDirection};
+use nokhwa::utils::KnownCameraControl;
+use render::{CaptureKey, ControlKey, FormatKey, KittySurface, RenderSurface, RenderTarget, SixelSurface, WindowSurface};
+use snapshot::Recorder;
 
 const WIDTH: usize = 640;
 const HEIGHT: usize = 480;
 
+/// How long the render loop waits for the next frame before servicing its
+/// own event loop instead (see `RenderSurface::poll`)
+const RENDER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Parser)]
+#[command(name = "camera-test")]
+#[command(about = "Camera preview across a minifb window or the terminal", long_about = None)]
+struct Args {
+    /// Where to draw the live feed: `window` (minifb), `kitty` (kitty
+    /// graphics protocol), `sixel`, or `auto` to detect from the terminal
+    #[arg(long, default_value = "auto")]
+    target: String,
+
+    /// Which camera to open: a device index (e.g. `1`) or a substring of
+    /// its name (e.g. `facetime`). With multiple cameras detected and no
+    /// value given, prompts interactively.
+    #[arg(long)]
+    camera: Option<String>,
+
+    /// Where snapshots and recordings (window target only) are written
+    #[arg(long, default_value = "snapshots")]
+    output_dir: PathBuf,
+}
+
+/// Map a surface-reported key press to the `ControlCommand` the capture
+/// thread understands
+fn control_command_for(key: ControlKey) -> ControlCommand {
+    match key {
+        ControlKey::BrightnessUp => ControlCommand::Step(KnownCameraControl::Brightness, StepDirection::Up),
+        ControlKey::BrightnessDown => ControlCommand::Step(KnownCameraControl::Brightness, StepDirection::Down),
+        ControlKey::ExposureUp => ControlCommand::Step(KnownCameraControl::Exposure, StepDirection::Up),
+        ControlKey::ExposureDown => ControlCommand::Step(KnownCameraControl::Exposure, StepDirection::Down),
+        ControlKey::ToggleAutoWhiteBalance => ControlCommand::ToggleAutoWhiteBalance,
+    }
+}
+
 fn main() -> Result<()> {
+    let args = Args::parse();
+    let target = RenderTarget::parse(&args.target)?;
+
     println!("=== Camera Test App ===");
-    println!("This will open your camera and display the feed in a window.");
-    println!("Press ESC to exit.\n");
+    println!("This will open your camera and display the feed ({:?}).", target);
+    println!("Press ESC to exit (window target only; Ctrl-C otherwise).");
+    println!("Window target only: +/- brightness, [/] exposure, w toggles auto white balance.");
+    println!("Window target only: p saves a snapshot, r toggles recording a PNG sequence, both under {:?}.", args.output_dir);
+    println!("Window target only: f cycles resolution/format/frame rate.\n");
+
+    let cameras = camera_select::enumerate()?;
+    let camera_index = camera_select::resolve(args.camera.as_deref(), &cameras)?;
 
-    // Try to open camera with different strategies
     println!("Attempting to open camera...");
-    
-    let camera_index = CameraIndex::Index(0);
-    
-    // Strategy 1: Any format
-    println!("Strategy 1: Trying with any available format...");
-    let mut camera = match Camera::new(
-        camera_index.clone(),
-        RequestedFormat::new::<RgbFormat>(RequestedFormatType::None),
-    ) {
-        Ok(cam) => {
-            println!("✓ Camera opened successfully!");
-            cam
-        }
-        Err(e) => {
-            println!("✗ Strategy 1 failed: {}", e);
-            
-            // Strategy 2: Try 640x480 YUYV
-            println!("\nStrategy 2: Trying 640x480...");
-            Camera::new(
-                camera_index,
-                RequestedFormat::new::<RgbFormat>(RequestedFormatType::Closest(
-                    nokhwa::utils::CameraFormat::new(
-                        nokhwa::utils::Resolution::new(WIDTH as u32, HEIGHT as u32),
-                        nokhwa::utils::FrameFormat::YUYV,
-                        30,
-                    ),
-                )),
-            ).map_err(|e| anyhow!("All strategies failed. Last error: {}", e))?
-        }
-    };
+    let mut camera = open_camera(camera_index)?;
 
-    // Open camera stream
     camera.open_stream()
         .map_err(|e| anyhow!("Failed to start camera stream: {}", e))?;
 
@@ -53,69 +80,118 @@ fn main() -> Result<()> {
     println!("\nCamera Info:");
     println!("  Name: {}", info.human_name());
     println!("  Description: {}", info.description());
-    println!("\nCamera is running! Opening window...");
+    println!("\nCamera is running!");
 
-    // Create window
-    let mut window = Window::new(
-        "Camera Test - Press ESC to exit",
-        WIDTH,
-        HEIGHT,
-        WindowOptions::default(),
-    ).map_err(|e| anyhow!("Failed to create window: {}", e))?;
+    let mut surface: Box<dyn RenderSurface> = match target {
+        RenderTarget::Window => Box::new(WindowSurface::new("Camera Test - Press ESC to exit", WIDTH, HEIGHT)?),
+        RenderTarget::Kitty => Box::new(KittySurface::new()),
+        RenderTarget::Sixel => Box::new(SixelSurface::new()),
+    };
 
-    window.limit_update_rate(Some(std::time::Duration::from_millis(33))); // ~30 FPS
+    // Capture thread owns the camera from here on; the render loop below
+    // only ever talks to it through the frame slot, so a slow camera or a
+    // slow render target can no longer block each other. Control-key
+    // presses go back the other way over `command_tx`, since the camera
+    // handle they need to act on lives on the capture thread too.
+    let (tx, mut rx) = frame_slot();
+    let (command_tx, command_rx) = mpsc::channel();
+    let capture_handle = spawn_capture_thread(camera, tx, command_rx);
 
-    println!("Window opened! You should see your camera feed.");
-    println!("Press ESC to exit.\n");
+    println!("Ready! Streaming frames...\n");
 
     let mut frame_count = 0u64;
     let start_time = std::time::Instant::now();
+    let mut last_frame: Option<capture::RgbImage> = None;
+    let mut recorder: Option<Recorder> = None;
+    let (mut frame_width, mut frame_height) = (WIDTH, HEIGHT);
 
-    // Main loop
-    while window.is_open() && !window.is_key_down(Key::Escape) {
-        // Capture frame
-        match camera.frame() {
-            Ok(frame) => {
+    loop {
+        match rx.recv_timeout(RENDER_POLL_INTERVAL) {
+            FrameSlotRecv::Frame(image) => {
                 frame_count += 1;
 
-                // Decode frame to RGB
-                match frame.decode_image::<RgbFormat>() {
-                    Ok(image) => {
-                        // Convert RGB image to u32 buffer for minifb
-                        let mut buffer: Vec<u32> = Vec::with_capacity(WIDTH * HEIGHT);
-                        
-                        for pixel in image.pixels() {
-                            let r = pixel[0] as u32;
-                            let g = pixel[1] as u32;
-                            let b = pixel[2] as u32;
-                            // Pack RGB into u32: 0RGB
-                            buffer.push((r << 16) | (g << 8) | b);
-                        }
+                let (width, height) = (image.width() as usize, image.height() as usize);
+                if (width, height) != (frame_width, frame_height) {
+                    // A format cycle landed on a different resolution -
+                    // adapt the surface before drawing the first frame at
+                    // the new size.
+                    if let Err(e) = surface.resize(width, height) {
+                        eprintln!("Failed to resize display for new format: {}", e);
+                    }
+                    (frame_width, frame_height) = (width, height);
+                }
 
-                        // Update window
-                        window.update_with_buffer(&buffer, WIDTH, HEIGHT)
-                            .map_err(|e| anyhow!("Failed to update window: {}", e))?;
+                if !surface.present(image.as_raw(), frame_width, frame_height)? {
+                    break;
+                }
 
-                        // Print stats every 30 frames
-                        if frame_count % 30 == 0 {
-                            let elapsed = start_time.elapsed().as_secs_f32();
-                            let fps = frame_count as f32 / elapsed;
-                            println!("Frames: {} | FPS: {:.1} | Time: {:.1}s", 
-                                frame_count, fps, elapsed);
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to decode frame: {}", e);
+                if let Some(rec) = recorder.as_mut() {
+                    if let Err(e) = rec.write_frame(&image) {
+                        eprintln!("Failed to write recording frame: {}", e);
                     }
                 }
+
+                if frame_count % 30 == 0 {
+                    let elapsed = start_time.elapsed().as_secs_f32();
+                    let fps = frame_count as f32 / elapsed;
+                    println!("Frames: {} | FPS: {:.1} | Time: {:.1}s", frame_count, fps, elapsed);
+                }
+
+                last_frame = Some(image);
             }
-            Err(e) => {
-                eprintln!("Failed to capture frame: {}", e);
-                std::thread::sleep(std::time::Duration::from_millis(100));
+            FrameSlotRecv::Timeout => {
+                if !surface.poll() {
+                    break;
+                }
+            }
+            FrameSlotRecv::Closed => {
+                eprintln!("Capture thread ended unexpectedly");
+                break;
+            }
+        }
+
+        for key in surface.poll_control_keys() {
+            let _ = command_tx.send(control_command_for(key));
+        }
+
+        for key in surface.poll_capture_keys() {
+            match key {
+                CaptureKey::Snapshot => match last_frame.as_ref() {
+                    Some(image) => match snapshot::snapshot_path(&args.output_dir)
+                        .and_then(|path| snapshot::save_png(image, &path).map(|_| path))
+                    {
+                        Ok(path) => println!("Saved snapshot to {}", path.display()),
+                        Err(e) => eprintln!("Failed to save snapshot: {}", e),
+                    },
+                    None => eprintln!("No frame decoded yet, can't save a snapshot"),
+                },
+                CaptureKey::ToggleRecording => match recorder.take() {
+                    Some(rec) => println!("Stopped recording ({} frames)", rec.frame_count()),
+                    None => match Recorder::start(&args.output_dir) {
+                        Ok(rec) => {
+                            println!("Started recording to {}", args.output_dir.display());
+                            recorder = Some(rec);
+                        }
+                        Err(e) => eprintln!("Failed to start recording: {}", e),
+                    },
+                },
+            }
+        }
+
+        for key in surface.poll_format_keys() {
+            match key {
+                FormatKey::CycleFormat => {
+                    let _ = command_tx.send(ControlCommand::CycleFormat);
+                }
             }
         }
     }
 
+    // Dropping the receiver tells the capture thread to stop pushing and
+    // exit on its next iteration
+    drop(rx);
+    let _ = capture_handle.join();
+
     let elapsed = start_time.elapsed().as_secs_f32();
     let avg_fps = frame_count as f32 / elapsed;
 
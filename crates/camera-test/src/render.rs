@@ -0,0 +1,502 @@
+use anyhow::{anyhow, Result};
+use std::io::Write;
+
+/// Which surface decoded frames are drawn to, selected via `--target` (or
+/// auto-detected from the terminal environment) at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderTarget {
+    /// The existing `minifb` window
+    Window,
+    /// Kitty's APC graphics protocol, for kitty (and kitty-compatible)
+    /// terminals
+    Kitty,
+    /// Sixel escape sequences, for terminals that support them (xterm,
+    /// mlterm, foot, contour, ...)
+    Sixel,
+}
+
+impl RenderTarget {
+    /// Parse the `--target` flag's value
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "window" => Ok(Self::Window),
+            "kitty" => Ok(Self::Kitty),
+            "sixel" => Ok(Self::Sixel),
+            "auto" => Ok(Self::detect()),
+            other => Err(anyhow!("Unknown --target '{}' (expected window, kitty, sixel, or auto)", other)),
+        }
+    }
+
+    /// Inspect `$TERM`/`$TERM_PROGRAM` (and kitty's own `$KITTY_WINDOW_ID`)
+    /// for a known terminal-graphics capability, falling back to the
+    /// `minifb` window when nothing matches. There's no universal runtime
+    /// capability query every terminal answers, so this is a best-effort
+    /// allowlist rather than a live protocol probe.
+    pub fn detect() -> Self {
+        if std::env::var("KITTY_WINDOW_ID").is_ok() {
+            return Self::Kitty;
+        }
+
+        let term_program = std::env::var("TERM_PROGRAM").unwrap_or_default().to_lowercase();
+        let term = std::env::var("TERM").unwrap_or_default().to_lowercase();
+
+        if term_program.contains("kitty") || term.contains("kitty") {
+            return Self::Kitty;
+        }
+
+        if term.contains("mlterm") || term.contains("foot") || term.contains("contour") || term.contains("wezterm") {
+            return Self::Sixel;
+        }
+
+        // VTE-based terminals (recent GNOME Terminal, Konsole, ...) gained
+        // sixel support without changing $TERM, but do export $VTE_VERSION
+        if term.contains("xterm") && std::env::var("VTE_VERSION").is_ok() {
+            return Self::Sixel;
+        }
+
+        Self::Window
+    }
+}
+
+/// A runtime camera-control key the main loop cares about, decoupled from
+/// any one backend's key representation - `RenderSurface::poll_control_keys`
+/// translates a surface's own input (minifb `Key`s, in practice; terminal
+/// backends have none to poll) into this set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlKey {
+    BrightnessUp,
+    BrightnessDown,
+    ExposureUp,
+    ExposureDown,
+    ToggleAutoWhiteBalance,
+}
+
+/// A snapshot/recording hotkey the main loop acts on, analogous to
+/// `ControlKey` but for file-writing actions rather than camera hardware
+/// (see `snapshot::save_png`/`snapshot::Recorder`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureKey {
+    Snapshot,
+    ToggleRecording,
+}
+
+/// A resolution/format-cycling hotkey the main loop acts on, analogous to
+/// `ControlKey` but for `format::cycle` rather than a hardware control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatKey {
+    CycleFormat,
+}
+
+/// A surface decoded RGB frames are drawn to, implemented once per
+/// `RenderTarget` so the main loop doesn't care which one it's driving.
+pub trait RenderSurface {
+    /// Draw one decoded RGB (3 bytes/pixel, row-major) frame sized
+    /// `width`x`height`. Returns `Ok(false)` once the surface wants the
+    /// main loop to exit (ESC for the window backend; terminal backends
+    /// have no keyboard polling of their own and always return `true`,
+    /// relying on the process being interrupted, e.g. Ctrl-C).
+    fn present(&mut self, rgb: &[u8], width: usize, height: usize) -> Result<bool>;
+
+    /// Service the surface's own event loop without a new frame to draw
+    /// (e.g. pump window events so ESC/close is noticed even while the
+    /// capture thread is between frames). Returns `false` to ask the main
+    /// loop to exit. Terminal backends have nothing to poll and always
+    /// return `true`.
+    fn poll(&mut self) -> bool {
+        true
+    }
+
+    /// Drain any `ControlKey`s pressed since the last call. Terminal
+    /// backends have no keyboard of their own to poll (their controls would
+    /// have to come from the process's stdin, which is reserved for
+    /// Ctrl-C), so the default is always empty.
+    fn poll_control_keys(&mut self) -> Vec<ControlKey> {
+        Vec::new()
+    }
+
+    /// Drain any `CaptureKey`s pressed since the last call. Same terminal
+    /// caveat as `poll_control_keys`: always empty outside the window
+    /// backend.
+    fn poll_capture_keys(&mut self) -> Vec<CaptureKey> {
+        Vec::new()
+    }
+
+    /// Drain any `FormatKey`s pressed since the last call. Same terminal
+    /// caveat as `poll_control_keys`: always empty outside the window
+    /// backend.
+    fn poll_format_keys(&mut self) -> Vec<FormatKey> {
+        Vec::new()
+    }
+
+    /// Adapt the surface to a new decoded frame size, e.g. after a
+    /// `format::cycle` changes the camera's resolution. Terminal backends
+    /// (kitty/sixel) re-encode at whatever size `present` is given each
+    /// call, so there's nothing to do; only the window backend has a fixed
+    /// buffer size tied to surface creation.
+    fn resize(&mut self, _width: usize, _height: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The existing `minifb` window backend
+pub struct WindowSurface {
+    window: minifb::Window,
+    title: String,
+}
+
+impl WindowSurface {
+    pub fn new(title: &str, width: usize, height: usize) -> Result<Self> {
+        let mut window = minifb::Window::new(title, width, height, minifb::WindowOptions::default())
+            .map_err(|e| anyhow!("Failed to create window: {}", e))?;
+        window.limit_update_rate(Some(std::time::Duration::from_millis(33))); // ~30 FPS
+        Ok(Self { window, title: title.to_string() })
+    }
+
+    pub fn inner(&self) -> &minifb::Window {
+        &self.window
+    }
+
+    pub fn inner_mut(&mut self) -> &mut minifb::Window {
+        &mut self.window
+    }
+}
+
+impl RenderSurface for WindowSurface {
+    fn present(&mut self, rgb: &[u8], width: usize, height: usize) -> Result<bool> {
+        if !self.window.is_open() || self.window.is_key_down(minifb::Key::Escape) {
+            return Ok(false);
+        }
+
+        let mut buffer: Vec<u32> = Vec::with_capacity(width * height);
+        for pixel in rgb.chunks_exact(3) {
+            buffer.push((pixel[0] as u32) << 16 | (pixel[1] as u32) << 8 | pixel[2] as u32);
+        }
+
+        self.window
+            .update_with_buffer(&buffer, width, height)
+            .map_err(|e| anyhow!("Failed to update window: {}", e))?;
+
+        Ok(true)
+    }
+
+    fn poll(&mut self) -> bool {
+        self.window.update();
+        self.window.is_open() && !self.window.is_key_down(minifb::Key::Escape)
+    }
+
+    fn poll_control_keys(&mut self) -> Vec<ControlKey> {
+        // `KeyRepeat::Yes` for the stepped controls so holding the key steps
+        // continuously like a typematic repeat; `No` for the toggle so one
+        // press flips it once rather than flickering while held.
+        let mut keys = Vec::new();
+        if self.window.is_key_pressed(minifb::Key::Equal, minifb::KeyRepeat::Yes) {
+            keys.push(ControlKey::BrightnessUp);
+        }
+        if self.window.is_key_pressed(minifb::Key::Minus, minifb::KeyRepeat::Yes) {
+            keys.push(ControlKey::BrightnessDown);
+        }
+        if self.window.is_key_pressed(minifb::Key::RightBracket, minifb::KeyRepeat::Yes) {
+            keys.push(ControlKey::ExposureUp);
+        }
+        if self.window.is_key_pressed(minifb::Key::LeftBracket, minifb::KeyRepeat::Yes) {
+            keys.push(ControlKey::ExposureDown);
+        }
+        if self.window.is_key_pressed(minifb::Key::W, minifb::KeyRepeat::No) {
+            keys.push(ControlKey::ToggleAutoWhiteBalance);
+        }
+        keys
+    }
+
+    fn poll_capture_keys(&mut self) -> Vec<CaptureKey> {
+        let mut keys = Vec::new();
+        if self.window.is_key_pressed(minifb::Key::P, minifb::KeyRepeat::No) {
+            keys.push(CaptureKey::Snapshot);
+        }
+        if self.window.is_key_pressed(minifb::Key::R, minifb::KeyRepeat::No) {
+            keys.push(CaptureKey::ToggleRecording);
+        }
+        keys
+    }
+
+    fn poll_format_keys(&mut self) -> Vec<FormatKey> {
+        if self.window.is_key_pressed(minifb::Key::F, minifb::KeyRepeat::No) {
+            vec![FormatKey::CycleFormat]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Recreates the `minifb` window at the new size - there's no in-place
+    /// resize in the API this backend was built against, and recreating is
+    /// rare enough (only after a format cycle) that it isn't worth pulling
+    /// in a newer one just for that.
+    fn resize(&mut self, width: usize, height: usize) -> Result<()> {
+        let mut window = minifb::Window::new(&self.title, width, height, minifb::WindowOptions::default())
+            .map_err(|e| anyhow!("Failed to resize window: {}", e))?;
+        window.limit_update_rate(Some(std::time::Duration::from_millis(33)));
+        self.window = window;
+        Ok(())
+    }
+}
+
+/// Kitty graphics protocol backend: every frame is sent as a fresh RGBA
+/// image (`f=32`) via the APC escape `\x1b_G...;<payload>\x1b\\`, chunked
+/// into <=4096-byte base64 payloads per kitty's documented limit (`m=1` on
+/// every chunk but the last).
+pub struct KittySurface {
+    stdout: std::io::Stdout,
+}
+
+impl KittySurface {
+    pub fn new() -> Self {
+        Self { stdout: std::io::stdout() }
+    }
+}
+
+impl Default for KittySurface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderSurface for KittySurface {
+    fn present(&mut self, rgb: &[u8], width: usize, height: usize) -> Result<bool> {
+        // Kitty's `f=32` expects RGBA - pad with an opaque alpha channel
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        for pixel in rgb.chunks_exact(3) {
+            rgba.extend_from_slice(pixel);
+            rgba.push(255);
+        }
+
+        let encoded = base64_encode(&rgba);
+        let mut out = self.stdout.lock();
+
+        const CHUNK: usize = 4096;
+        let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(CHUNK).collect();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            let more = if i + 1 < chunks.len() { 1 } else { 0 };
+            if i == 0 {
+                write!(out, "\x1b_Gf=32,s={},v={},a=T,m={};", width, height, more)?;
+            } else {
+                write!(out, "\x1b_Gm={};", more)?;
+            }
+            out.write_all(chunk)?;
+            write!(out, "\x1b\\")?;
+        }
+
+        out.flush()?;
+        Ok(true)
+    }
+}
+
+/// Sixel protocol backend: quantizes each frame down to a fixed palette
+/// (a 6x6x6 color cube plus a grayscale ramp, the same scheme classic
+/// sixel encoders use) and emits DECSIXEL data sized to the terminal's
+/// reported pixel geometry.
+pub struct SixelSurface {
+    stdout: std::io::Stdout,
+}
+
+impl SixelSurface {
+    pub fn new() -> Self {
+        Self { stdout: std::io::stdout() }
+    }
+
+    /// Pixel dimensions to render at: the terminal's actual pixel geometry
+    /// via `TIOCGWINSZ` when the kernel reports one, otherwise its cell
+    /// count scaled by an assumed 8x16px cell.
+    pub fn target_geometry(fallback_width: usize, fallback_height: usize) -> (usize, usize) {
+        const ASSUMED_CELL_WIDTH: usize = 8;
+        const ASSUMED_CELL_HEIGHT: usize = 16;
+
+        match query_winsize() {
+            Some(ws) if ws.ws_xpixel > 0 && ws.ws_ypixel > 0 => (ws.ws_xpixel as usize, ws.ws_ypixel as usize),
+            Some(ws) if ws.ws_col > 0 && ws.ws_row > 0 => {
+                (ws.ws_col as usize * ASSUMED_CELL_WIDTH, ws.ws_row as usize * ASSUMED_CELL_HEIGHT)
+            }
+            _ => (fallback_width, fallback_height),
+        }
+    }
+}
+
+impl Default for SixelSurface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderSurface for SixelSurface {
+    fn present(&mut self, rgb: &[u8], width: usize, height: usize) -> Result<bool> {
+        // Nearest-neighbor scale to the terminal's pixel geometry
+        let (target_width, target_height) = Self::target_geometry(width, height);
+        let mut scaled = Vec::with_capacity(target_width * target_height * 3);
+        for y in 0..target_height {
+            let sy = y * height / target_height;
+            for x in 0..target_width {
+                let offset = (sy * width + x * width / target_width) * 3;
+                scaled.extend_from_slice(&rgb[offset..offset + 3]);
+            }
+        }
+
+        let sixel = encode_sixel(&scaled, target_width, target_height);
+        let mut out = self.stdout.lock();
+        out.write_all(sixel.as_bytes())?;
+        out.flush()?;
+        Ok(true)
+    }
+}
+
+#[repr(C)]
+struct WinSize {
+    ws_row: libc::c_ushort,
+    ws_col: libc::c_ushort,
+    ws_xpixel: libc::c_ushort,
+    ws_ypixel: libc::c_ushort,
+}
+
+/// Query the controlling terminal's cell/pixel geometry via `TIOCGWINSZ`.
+/// `None` if stdout isn't a terminal or the ioctl fails (e.g. piped output).
+fn query_winsize() -> Option<WinSize> {
+    let mut ws = WinSize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+    let rc = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws as *mut WinSize) };
+    if rc == 0 {
+        Some(ws)
+    } else {
+        None
+    }
+}
+
+/// The 6x6x6 color cube (216 colors) plus a 24-step grayscale ramp every
+/// classic sixel/xterm-256color palette is built from
+fn palette() -> Vec<(u8, u8, u8)> {
+    const STEPS: [u8; 6] = [0, 51, 102, 153, 204, 255];
+    let mut colors = Vec::with_capacity(216 + 24);
+    for r in STEPS {
+        for g in STEPS {
+            for b in STEPS {
+                colors.push((r, g, b));
+            }
+        }
+    }
+    for i in 0..24 {
+        let v = (i * 255 / 23) as u8;
+        colors.push((v, v, v));
+    }
+    colors
+}
+
+/// Nearest palette entry by squared Euclidean distance
+fn nearest_color_index(palette: &[(u8, u8, u8)], pixel: (u8, u8, u8)) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &(r, g, b))| {
+            let dr = r as i32 - pixel.0 as i32;
+            let dg = g as i32 - pixel.1 as i32;
+            let db = b as i32 - pixel.2 as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Quantize `rgb` to the fixed palette and emit it as a DECSIXEL string
+/// (`\x1bP...q...\x1b\\`), six source rows per sixel band
+fn encode_sixel(rgb: &[u8], width: usize, height: usize) -> String {
+    let palette = palette();
+
+    let mut out = String::new();
+    out.push_str("\x1bPq");
+
+    for (i, &(r, g, b)) in palette.iter().enumerate() {
+        // Sixel color registers are specified in percent, not 0-255
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            i,
+            r as u32 * 100 / 255,
+            g as u32 * 100 / 255,
+            b as u32 * 100 / 255
+        ));
+    }
+
+    for band_start in (0..height).step_by(6) {
+        let band_height = (height - band_start).min(6);
+
+        // One run-length-encoded row of sixel characters per color that
+        // appears anywhere in this band
+        let mut used = std::collections::BTreeSet::new();
+        let mut indices = vec![0usize; width * band_height];
+        for y in 0..band_height {
+            for x in 0..width {
+                let offset = ((band_start + y) * width + x) * 3;
+                let pixel = (rgb[offset], rgb[offset + 1], rgb[offset + 2]);
+                let idx = nearest_color_index(&palette, pixel);
+                indices[y * width + x] = idx;
+                used.insert(idx);
+            }
+        }
+
+        for color_idx in used {
+            out.push_str(&format!("#{}", color_idx));
+            let mut x = 0;
+            while x < width {
+                let mut bits = 0u8;
+                for y in 0..band_height {
+                    if indices[y * width + x] == color_idx {
+                        bits |= 1 << y;
+                    }
+                }
+                let mut run = 1;
+                while x + run < width {
+                    let mut next_bits = 0u8;
+                    for y in 0..band_height {
+                        if indices[y * width + x + run] == color_idx {
+                            next_bits |= 1 << y;
+                        }
+                    }
+                    if next_bits != bits {
+                        break;
+                    }
+                    run += 1;
+                }
+
+                let ch = (bits + 0x3f) as char;
+                if run > 3 {
+                    out.push_str(&format!("!{}{}", run, ch));
+                } else {
+                    for _ in 0..run {
+                        out.push(ch);
+                    }
+                }
+                x += run;
+            }
+            out.push('$'); // return to start of this band
+        }
+        out.push('-'); // advance to the next band
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Minimal standalone base64 encoder (standard alphabet, `=` padding) -
+/// the only user is `KittySurface`, so a small hand-rolled pass avoids
+/// pulling in a dedicated crate for one call site.
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[((b0 & 0x03) << 4 | b1 >> 4) as usize] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[((b1 & 0x0f) << 2 | b2 >> 6) as usize] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[(b2 & 0x3f) as usize] as char } else { '=' });
+    }
+
+    out
+}
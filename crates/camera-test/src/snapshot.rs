@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::capture::RgbImage;
+
+/// Write `image` as an 8-bit RGB PNG to `path`. The capture pipeline only
+/// ever decodes frames into `RgbFormat` (see `capture::open_camera`), so
+/// there's no higher-bit-depth source to carry through yet; if a 16-bit
+/// format is added upstream this would switch to
+/// `image::codecs::png::PngEncoder` with `ColorType::Rgb16` for those
+/// frames.
+pub fn save_png(image: &RgbImage, path: &Path) -> Result<()> {
+    image.save(path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// A single timestamped snapshot path under `dir`, created if it doesn't
+/// exist yet
+pub fn snapshot_path(dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    Ok(dir.join(format!("snapshot_{}.png", timestamp)))
+}
+
+/// Writes a numbered PNG sequence into a directory at the capture rate,
+/// started and stopped by the `ToggleRecording` hotkey. Each session gets
+/// its own timestamped subdirectory so repeated recordings don't clobber
+/// each other's frame numbering.
+pub struct Recorder {
+    dir: PathBuf,
+    next_index: u64,
+}
+
+impl Recorder {
+    /// Begin a new recording session under `base_dir`
+    pub fn start(base_dir: &Path) -> Result<Self> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let dir = base_dir.join(format!("recording_{}", timestamp));
+        std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        Ok(Self { dir, next_index: 0 })
+    }
+
+    /// Write the next frame in the sequence, returning its path
+    pub fn write_frame(&mut self, image: &RgbImage) -> Result<PathBuf> {
+        let path = self.dir.join(format!("frame_{:06}.png", self.next_index));
+        save_png(image, &path)?;
+        self.next_index += 1;
+        Ok(path)
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.next_index
+    }
+}
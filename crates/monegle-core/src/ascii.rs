@@ -1,4 +1,4 @@
-use crate::{CharacterSet, ColorMode};
+use crate::{CharacterSet, ColorMode, RenderMode};
 
 /// Convert a grayscale brightness value (0-255) to an ASCII character
 pub fn brightness_to_ascii(brightness: u8, charset: CharacterSet) -> char {
@@ -7,6 +7,79 @@ pub fn brightness_to_ascii(brightness: u8, charset: CharacterSet) -> char {
     palette.chars().nth(index).unwrap_or(' ')
 }
 
+/// Look up the glyph at a palette index directly, for callers (like
+/// `dither_brightness_indices`) that already picked a bucket instead of
+/// going through `brightness_to_ascii`'s own brightness-to-index mapping
+pub fn ascii_from_index(index: usize, charset: CharacterSet) -> char {
+    charset.palette().chars().nth(index).unwrap_or(' ')
+}
+
+/// Floyd-Steinberg error-diffusion dithering over a brightness buffer,
+/// producing the palette index chosen for each pixel instead of mapping
+/// brightness straight to a glyph. Direct brightness-to-glyph mapping
+/// bands hard in low-contrast regions because a character grid only has
+/// as many brightness levels as `charset`'s palette length; diffusing each
+/// pixel's quantization error into its unprocessed neighbors spreads that
+/// rounding loss out as noise instead, the same trick GIF quantizers use
+/// when reducing to a handful of levels.
+///
+/// Walks left-to-right, top-to-bottom. For each pixel, the bucket actually
+/// chosen for its (possibly already-adjusted) brightness defines the
+/// quantization error, which is then distributed to the 4 neighbors the
+/// classic Floyd-Steinberg kernel covers - 7/16 (x+1, y), 3/16 (x-1, y+1),
+/// 5/16 (x, y+1), 1/16 (x+1, y+1) - clamping each diffused value back into
+/// `0..=255`. Neighbors that fall outside the buffer are simply skipped.
+pub fn dither_brightness_indices(brightness: &[u8], width: u32, height: u32, palette_len: usize) -> Vec<usize> {
+    let width = width as usize;
+    let height = height as usize;
+
+    let mut buffer: Vec<f32> = brightness.iter().map(|&b| b as f32).collect();
+    let mut indices = vec![0usize; buffer.len()];
+
+    let bucket_of = |value: f32| -> usize {
+        if palette_len <= 1 {
+            0
+        } else {
+            ((value.clamp(0.0, 255.0) as usize) * (palette_len - 1)) / 255
+        }
+    };
+
+    let bucket_center = |bucket: usize| -> f32 {
+        if palette_len <= 1 {
+            0.0
+        } else {
+            (bucket as f32 * 255.0) / (palette_len - 1) as f32
+        }
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let idx = y * width + x;
+            let value = buffer[idx].clamp(0.0, 255.0);
+            let bucket = bucket_of(value);
+            indices[idx] = bucket;
+
+            let error = value - bucket_center(bucket);
+
+            let mut diffuse = |dx: i32, dy: i32, weight: f32| {
+                let nx = x as i32 + dx;
+                let ny = y as i32 + dy;
+                if nx >= 0 && nx < width as i32 && ny >= 0 && ny < height as i32 {
+                    let n_idx = ny as usize * width + nx as usize;
+                    buffer[n_idx] = (buffer[n_idx] + error * weight).clamp(0.0, 255.0);
+                }
+            };
+
+            diffuse(1, 0, 7.0 / 16.0);
+            diffuse(-1, 1, 3.0 / 16.0);
+            diffuse(0, 1, 5.0 / 16.0);
+            diffuse(1, 1, 1.0 / 16.0);
+        }
+    }
+
+    indices
+}
+
 /// Convert brightness to ASCII with optional colorization
 pub fn brightness_to_ascii_colored(brightness: u8, charset: CharacterSet, color_mode: ColorMode) -> String {
     let ch = brightness_to_ascii(brightness, charset);
@@ -53,6 +126,283 @@ pub fn image_to_ascii(
     result
 }
 
+/// Convert an image buffer to ASCII art using the given `RenderMode`:
+/// plain brightness mapping, or edge-aware line glyphs over a brightness
+/// fallback (see `RenderMode::EdgeAware`)
+pub fn image_to_ascii_with_mode(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    charset: CharacterSet,
+    mode: RenderMode,
+) -> String {
+    match mode {
+        RenderMode::Brightness => image_to_ascii(pixels, width, height, charset),
+        RenderMode::EdgeAware { dog_threshold } => {
+            image_to_ascii_edge_aware(pixels, width, height, charset, dog_threshold)
+        }
+    }
+}
+
+/// Edge-aware ASCII rendering: a Difference-of-Gaussians pass (sigma 1.0
+/// vs 1.6) finds strong edges, a Sobel operator at each edge pixel gives
+/// the local gradient orientation, and that orientation picks a line glyph
+/// (`-`/`/`/`|`/`\`) instead of the usual brightness mapping. Cells below
+/// `dog_threshold` fall back to `brightness_to_ascii` so flat regions
+/// don't fill with spurious lines.
+fn image_to_ascii_edge_aware(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    charset: CharacterSet,
+    dog_threshold: u8,
+) -> String {
+    let gray = grayscale_buffer(pixels, width, height);
+    let blur_narrow = gaussian_blur(&gray, width, height, 1.0);
+    let blur_wide = gaussian_blur(&gray, width, height, 1.6);
+
+    let mut result = String::with_capacity((width * height) as usize);
+
+    for y in 0..height {
+        for x in 0..width {
+            let idx = (y * width + x) as usize;
+            let dog = (blur_narrow[idx] - blur_wide[idx]).abs();
+
+            let ch = if dog * 255.0 >= dog_threshold as f32 {
+                edge_glyph_at(&gray, width, height, x, y)
+            } else {
+                brightness_to_ascii((gray[idx] * 255.0) as u8, charset)
+            };
+
+            result.push(ch);
+        }
+
+        if y < height - 1 {
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// Quantize the Sobel gradient angle at `(x, y)` into one of 4 line
+/// glyphs: 0°→`|`, 45°→`/`, 90°→`-`, 135°→`\`
+fn edge_glyph_at(gray: &[f32], width: u32, height: u32, x: u32, y: u32) -> char {
+    let (gx, gy) = sobel_gradient(gray, width, height, x, y);
+
+    // The gradient vector (gx, gy) points across the edge (e.g. a vertical
+    // brightness step has a horizontal gradient), so an angle near 0
+    // degrees here already corresponds to a vertical edge line - no extra
+    // 90-degree rotation needed before bucketing
+    let angle = gy.atan2(gx).to_degrees();
+    let angle = ((angle + 180.0) % 180.0 + 180.0) % 180.0; // fold to [0, 180)
+
+    match angle {
+        a if !(22.5..157.5).contains(&a) => '|',
+        a if a < 67.5 => '/',
+        a if a < 112.5 => '-',
+        _ => '\\',
+    }
+}
+
+/// 3x3 Sobel gradient at `(x, y)`, sampling with border pixels clamped to
+/// the edge of the image
+fn sobel_gradient(gray: &[f32], width: u32, height: u32, x: u32, y: u32) -> (f32, f32) {
+    let sample = |dx: i32, dy: i32| -> f32 {
+        let sx = (x as i32 + dx).clamp(0, width as i32 - 1) as u32;
+        let sy = (y as i32 + dy).clamp(0, height as i32 - 1) as u32;
+        gray[(sy * width + sx) as usize]
+    };
+
+    let gx = -sample(-1, -1) + sample(1, -1)
+        - 2.0 * sample(-1, 0) + 2.0 * sample(1, 0)
+        - sample(-1, 1) + sample(1, 1);
+
+    let gy = -sample(-1, -1) - 2.0 * sample(0, -1) - sample(1, -1)
+        + sample(-1, 1) + 2.0 * sample(0, 1) + sample(1, 1);
+
+    (gx, gy)
+}
+
+/// Extract a normalized (0.0-1.0) grayscale buffer from an RGBA pixel
+/// slice
+fn grayscale_buffer(pixels: &[u8], width: u32, height: u32) -> Vec<f32> {
+    let mut gray = vec![0.0f32; (width * height) as usize];
+
+    for y in 0..height {
+        for x in 0..width {
+            let idx = ((y * width + x) * 4) as usize;
+            let out_idx = (y * width + x) as usize;
+
+            if idx + 2 < pixels.len() {
+                let brightness = rgb_to_brightness(pixels[idx], pixels[idx + 1], pixels[idx + 2]);
+                gray[out_idx] = brightness as f32 / 255.0;
+            }
+        }
+    }
+
+    gray
+}
+
+/// Separable Gaussian blur with the given sigma, clamping kernel sampling
+/// at the image borders
+fn gaussian_blur(buffer: &[f32], width: u32, height: u32, sigma: f32) -> Vec<f32> {
+    let radius = (sigma * 3.0).ceil() as i32;
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let kernel_sum: f32 = kernel.iter().sum();
+
+    // Horizontal pass
+    let mut horizontal = vec![0.0f32; buffer.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let mut acc = 0.0;
+            for (i, weight) in kernel.iter().enumerate() {
+                let offset = i as i32 - radius;
+                let sx = (x as i32 + offset).clamp(0, width as i32 - 1) as u32;
+                acc += buffer[(y * width + sx) as usize] * weight;
+            }
+            horizontal[(y * width + x) as usize] = acc / kernel_sum;
+        }
+    }
+
+    // Vertical pass
+    let mut result = vec![0.0f32; buffer.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let mut acc = 0.0;
+            for (i, weight) in kernel.iter().enumerate() {
+                let offset = i as i32 - radius;
+                let sy = (y as i32 + offset).clamp(0, height as i32 - 1) as u32;
+                acc += horizontal[(sy * width + x) as usize] * weight;
+            }
+            result[(y * width + x) as usize] = acc / kernel_sum;
+        }
+    }
+
+    result
+}
+
+/// Dot-bit lookup for packing a 2-wide by 4-tall pixel block into a
+/// Braille cell: `DOT_BITS[row][col]` is the bit set in the U+2800 offset
+/// when that sub-pixel is "on"
+const DOT_BITS: [[u8; 2]; 4] = [
+    [0x01, 0x08],
+    [0x02, 0x10],
+    [0x04, 0x20],
+    [0x40, 0x80],
+];
+
+/// Convert an image buffer to Braille-glyph ASCII art: each output cell
+/// packs a 2x4 grayscale pixel block into a single Unicode Braille dot
+/// pattern (U+2800 base), quadrupling effective vertical resolution
+/// versus one-char-per-pixel rendering. Pixels are thresholded against a
+/// flat brightness cutoff; sub-pixels that fall outside the image bounds
+/// (when width/height isn't an exact multiple of 2/4) are treated as off.
+pub fn image_to_braille(pixels: &[u8], width: u32, height: u32, threshold: u8) -> String {
+    let gray = grayscale_buffer(pixels, width, height);
+    let cell_cols = width.div_ceil(2);
+    let cell_rows = height.div_ceil(4);
+
+    let mut result = String::with_capacity((cell_cols * cell_rows) as usize);
+
+    for cell_y in 0..cell_rows {
+        for cell_x in 0..cell_cols {
+            let bits = braille_cell_bits(&gray, width, height, cell_x, cell_y, threshold);
+            let ch = char::from_u32(0x2800 + bits as u32).unwrap_or(' ');
+            result.push(ch);
+        }
+
+        if cell_y < cell_rows - 1 {
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// Like `image_to_braille`, but emits one averaged 24-bit RGB foreground
+/// color per cell (averaged over the same 2x4 sampled block), escaping
+/// each glyph with an ANSI truecolor sequence
+pub fn image_to_braille_colored(pixels: &[u8], width: u32, height: u32, threshold: u8) -> String {
+    let gray = grayscale_buffer(pixels, width, height);
+    let cell_cols = width.div_ceil(2);
+    let cell_rows = height.div_ceil(4);
+
+    let mut result = String::with_capacity((cell_cols * cell_rows * 20) as usize);
+
+    for cell_y in 0..cell_rows {
+        for cell_x in 0..cell_cols {
+            let bits = braille_cell_bits(&gray, width, height, cell_x, cell_y, threshold);
+            let ch = char::from_u32(0x2800 + bits as u32).unwrap_or(' ');
+            let (r, g, b) = average_cell_color(pixels, width, height, cell_x, cell_y);
+
+            result.push_str(&format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, ch));
+        }
+
+        if cell_y < cell_rows - 1 {
+            result.push('\n');
+        }
+    }
+
+    result
+}
+
+/// Sample the 2x4 grayscale block at cell `(cell_x, cell_y)`, threshold
+/// each sub-pixel, and OR together the matching `DOT_BITS` entries
+fn braille_cell_bits(gray: &[f32], width: u32, height: u32, cell_x: u32, cell_y: u32, threshold: u8) -> u8 {
+    let mut bits = 0u8;
+
+    for (row, row_bits) in DOT_BITS.iter().enumerate() {
+        for (col, bit) in row_bits.iter().enumerate() {
+            let x = cell_x * 2 + col as u32;
+            let y = cell_y * 4 + row as u32;
+
+            if x >= width || y >= height {
+                continue;
+            }
+
+            let brightness = (gray[(y * width + x) as usize] * 255.0) as u8;
+            if brightness >= threshold {
+                bits |= bit;
+            }
+        }
+    }
+
+    bits
+}
+
+/// Average the RGB color of in-bounds pixels over the cell's 2x4 block
+fn average_cell_color(pixels: &[u8], width: u32, height: u32, cell_x: u32, cell_y: u32) -> (u8, u8, u8) {
+    let (mut r_sum, mut g_sum, mut b_sum, mut count) = (0u32, 0u32, 0u32, 0u32);
+
+    for row in 0..4 {
+        for col in 0..2 {
+            let x = cell_x * 2 + col;
+            let y = cell_y * 4 + row;
+
+            if x >= width || y >= height {
+                continue;
+            }
+
+            let idx = ((y * width + x) * 4) as usize;
+            if idx + 2 < pixels.len() {
+                r_sum += pixels[idx] as u32;
+                g_sum += pixels[idx + 1] as u32;
+                b_sum += pixels[idx + 2] as u32;
+                count += 1;
+            }
+        }
+    }
+
+    if count == 0 {
+        (0, 0, 0)
+    } else {
+        ((r_sum / count) as u8, (g_sum / count) as u8, (b_sum / count) as u8)
+    }
+}
+
 /// Convert an image buffer to colored ASCII art
 pub fn image_to_ascii_colored(
     pixels: &[u8],
@@ -97,6 +447,56 @@ pub fn image_to_ascii_colored(
     result
 }
 
+/// Convert an image buffer to half-block color art: each output cell
+/// covers a two-pixel-tall column and renders the upper-half-block glyph
+/// `▀`, with its foreground set to the top pixel's true color and its
+/// background set to the bottom pixel's, doubling vertical color
+/// resolution versus one-glyph-per-pixel rendering. A trailing odd row
+/// renders with no background color (top pixel only). Callers on
+/// terminals without truecolor support should fall back to
+/// `image_to_ascii_colored` with `ColorMode::Rgb` instead.
+pub fn image_to_halfblock(pixels: &[u8], width: u32, height: u32) -> String {
+    let mut result = String::with_capacity((width * height.div_ceil(2) * 24) as usize);
+
+    let mut y = 0;
+    while y < height {
+        for x in 0..width {
+            let (tr, tg, tb) = pixel_rgb(pixels, width, x, y).unwrap_or((0, 0, 0));
+
+            match pixel_rgb(pixels, width, x, y + 1) {
+                Some((br, bg, bb)) => {
+                    result.push_str(&format!(
+                        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀\x1b[0m",
+                        tr, tg, tb, br, bg, bb
+                    ));
+                }
+                None => {
+                    result.push_str(&format!("\x1b[38;2;{};{};{}m▀\x1b[0m", tr, tg, tb));
+                }
+            }
+        }
+
+        if y + 2 < height {
+            result.push('\n');
+        }
+
+        y += 2;
+    }
+
+    result
+}
+
+/// Sample the RGB color at `(x, y)` from an RGBA pixel buffer, or `None`
+/// if `y` is out of bounds
+fn pixel_rgb(pixels: &[u8], width: u32, x: u32, y: u32) -> Option<(u8, u8, u8)> {
+    let idx = ((y * width + x) * 4) as usize;
+    if idx + 2 < pixels.len() {
+        Some((pixels[idx], pixels[idx + 1], pixels[idx + 2]))
+    } else {
+        None
+    }
+}
+
 /// Calculate the aspect ratio correction for ASCII characters
 /// (most terminal fonts are taller than they are wide)
 pub fn aspect_ratio_correction() -> f32 {
@@ -118,4 +518,165 @@ mod tests {
         assert_eq!(rgb_to_brightness(0, 0, 0), 0);
         assert_eq!(rgb_to_brightness(255, 255, 255), 255);
     }
+
+    /// A flat gray image has no gradients, so a high threshold should
+    /// leave every cell on the brightness fallback (no stray line glyphs)
+    #[test]
+    fn test_edge_aware_flat_image_has_no_edges() {
+        let width = 4;
+        let height = 4;
+        let pixels = vec![128u8; (width * height * 4) as usize];
+
+        let result = image_to_ascii_with_mode(
+            &pixels,
+            width,
+            height,
+            CharacterSet::Standard,
+            RenderMode::EdgeAware { dog_threshold: 10 },
+        );
+
+        let expected = brightness_to_ascii(128, CharacterSet::Standard);
+        assert!(result.chars().filter(|c| *c != '\n').all(|c| c == expected));
+    }
+
+    /// A sharp vertical edge (left half black, right half white) should be
+    /// picked up as a strong gradient and rendered with a line glyph
+    #[test]
+    fn test_edge_aware_detects_vertical_edge() {
+        let width = 8;
+        let height = 8;
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+        for y in 0..height {
+            for x in 0..width {
+                let idx = ((y * width + x) * 4) as usize;
+                let value = if x < width / 2 { 0 } else { 255 };
+                pixels[idx] = value;
+                pixels[idx + 1] = value;
+                pixels[idx + 2] = value;
+                pixels[idx + 3] = 255;
+            }
+        }
+
+        let result = image_to_ascii_with_mode(
+            &pixels,
+            width,
+            height,
+            CharacterSet::Standard,
+            RenderMode::EdgeAware { dog_threshold: 10 },
+        );
+
+        assert!(result.contains('|'));
+    }
+
+    /// An all-dark 2x4 block should produce the empty Braille glyph (no
+    /// dots set, U+2800)
+    #[test]
+    fn test_braille_all_dark_is_blank_glyph() {
+        let width = 2;
+        let height = 4;
+        let pixels = vec![0u8; (width * height * 4) as usize];
+
+        let result = image_to_braille(&pixels, width, height, 128);
+        assert_eq!(result, "\u{2800}");
+    }
+
+    /// An all-bright 2x4 block should set every dot bit (U+28FF)
+    #[test]
+    fn test_braille_all_bright_is_full_glyph() {
+        let width = 2;
+        let height = 4;
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        for chunk in pixels.chunks_mut(4) {
+            chunk[0] = 255;
+            chunk[1] = 255;
+            chunk[2] = 255;
+            chunk[3] = 255;
+        }
+
+        let result = image_to_braille(&pixels, width, height, 128);
+        assert_eq!(result, "\u{28FF}");
+    }
+
+    /// Cells past the image bounds (width/height not an exact multiple of
+    /// 2/4) should treat missing sub-pixels as off rather than panicking
+    #[test]
+    fn test_braille_handles_non_multiple_dimensions() {
+        let width = 3;
+        let height = 5;
+        let pixels = vec![255u8; (width * height * 4) as usize];
+
+        let result = image_to_braille(&pixels, width, height, 128);
+        // 2 columns of cells (ceil(3/2)), 2 rows of cells (ceil(5/4))
+        assert_eq!(result.lines().count(), 2);
+        assert_eq!(result.lines().next().unwrap().chars().count(), 2);
+    }
+
+    /// A flat-brightness buffer quantizes to the same bucket everywhere
+    /// whether or not error diffusion runs, since there's no quantization
+    /// error to spread once the first pixel lands exactly on a bucket
+    /// center
+    #[test]
+    fn test_dither_flat_buffer_is_uniform() {
+        let width = 6;
+        let height = 6;
+        let brightness = vec![0u8; (width * height) as usize];
+
+        let indices = dither_brightness_indices(&brightness, width, height, 10);
+        assert!(indices.iter().all(|&i| i == 0));
+    }
+
+    /// A flat brightness buffer that lands squarely between two palette buckets
+    /// bands under direct quantization but should pick up some of the
+    /// higher bucket once error diffusion carries the rounding loss
+    /// forward
+    #[test]
+    fn test_dither_spreads_quantization_error() {
+        let width = 8;
+        let height = 8;
+        let palette_len = 2; // only bucket 0 (0) and bucket 1 (255) exist
+        let brightness = vec![120u8; (width * height) as usize];
+
+        let indices = dither_brightness_indices(&brightness, width, height, palette_len);
+        assert!(indices.iter().any(|&i| i == 1), "some pixels should round up once error accumulates");
+        assert!(indices.iter().any(|&i| i == 0), "not every pixel should round up");
+    }
+
+    #[test]
+    fn test_dither_out_of_bounds_neighbors_are_skipped() {
+        // A 1x1 buffer has no neighbors at all - this should simply not panic
+        let indices = dither_brightness_indices(&[200], 1, 1, 10);
+        assert_eq!(indices.len(), 1);
+    }
+
+    /// Each output row should encode two pixel rows via foreground +
+    /// background truecolor escapes around a single `▀` glyph
+    #[test]
+    fn test_halfblock_encodes_two_rows_per_glyph() {
+        let width = 1;
+        let height = 2;
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        // Top pixel red, bottom pixel blue
+        pixels[0] = 255;
+        pixels[3] = 255;
+        pixels[4 + 2] = 255;
+        pixels[4 + 3] = 255;
+
+        let result = image_to_halfblock(&pixels, width, height);
+        assert_eq!(result, "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m");
+    }
+
+    /// An odd trailing row with no pixel below it should render with only
+    /// a foreground color, no background escape
+    #[test]
+    fn test_halfblock_handles_odd_trailing_row() {
+        let width = 1;
+        let height = 1;
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        pixels[1] = 255;
+        pixels[3] = 255;
+
+        let result = image_to_halfblock(&pixels, width, height);
+        assert_eq!(result, "\x1b[38;2;0;255;0m▀\x1b[0m");
+    }
 }
@@ -225,6 +225,136 @@ impl FrameEncoder for DeltaEncoder {
     }
 }
 
+/// VNC-style row/span delta: keyframes are a whole-frame RLE pass; other
+/// frames are split into rows (the ASCII grid is newline-delimited) and
+/// diffed row by row against the previous frame, keeping only the
+/// `(row, start_col, run_of_chars)` span from the first changed column to
+/// the end of each changed row, which is itself RLE-compacted before being
+/// written out. Cheaper than per-character `DeltaEncoder` whenever changes
+/// cluster on a handful of rows instead of scattering pixel by pixel.
+pub struct SpanDeltaEncoder;
+
+/// One changed row: replace everything in `prev_row` from `start_col`
+/// onward with `run`
+struct RowSpan {
+    row: u32,
+    start_col: u32,
+    run: String,
+}
+
+/// Find the longest common prefix between `prev_row` and `curr_row` and
+/// return the span of `curr_row` after it, or `None` if the rows are
+/// identical
+fn row_span(prev_row: &str, curr_row: &str) -> Option<(usize, String)> {
+    let prev_chars: Vec<char> = prev_row.chars().collect();
+    let curr_chars: Vec<char> = curr_row.chars().collect();
+
+    let prefix_len = prev_chars
+        .iter()
+        .zip(curr_chars.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    if prefix_len == prev_chars.len() && prefix_len == curr_chars.len() {
+        return None;
+    }
+
+    Some((prefix_len, curr_chars[prefix_len..].iter().collect()))
+}
+
+impl FrameEncoder for SpanDeltaEncoder {
+    fn encode(&self, current: &str, previous: Option<&str>) -> Result<Vec<u8>> {
+        let prev = match previous {
+            None => return RleEncoder.encode(current, None),
+            Some(prev) => prev,
+        };
+
+        let curr_rows: Vec<&str> = current.split('\n').collect();
+        let prev_rows: Vec<&str> = prev.split('\n').collect();
+
+        let mut spans = Vec::new();
+        for (row, &curr_row) in curr_rows.iter().enumerate() {
+            let prev_row = prev_rows.get(row).copied().unwrap_or("");
+            if let Some((start_col, run)) = row_span(prev_row, curr_row) {
+                spans.push(RowSpan {
+                    row: row as u32,
+                    start_col: start_col as u32,
+                    run,
+                });
+            }
+        }
+
+        let mut encoded = Vec::new();
+        encoded.extend_from_slice(&(curr_rows.len() as u32).to_le_bytes());
+        encoded.extend_from_slice(&(spans.len() as u32).to_le_bytes());
+
+        for span in spans {
+            let rle_run = RleEncoder.encode(&span.run, None)?;
+            encoded.extend_from_slice(&span.row.to_le_bytes());
+            encoded.extend_from_slice(&span.start_col.to_le_bytes());
+            encoded.extend_from_slice(&(rle_run.len() as u32).to_le_bytes());
+            encoded.extend_from_slice(&rle_run);
+        }
+
+        Ok(encoded)
+    }
+
+    fn decode(&self, data: &[u8], previous: Option<&str>) -> Result<String> {
+        let prev = match previous {
+            None => return RleEncoder.decode(data, None),
+            Some(prev) => prev,
+        };
+
+        if data.len() < 8 {
+            return Err(anyhow!("SpanDelta decode error: data too short"));
+        }
+
+        let mut i = 0;
+        let num_rows = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+        i += 4;
+        let num_spans = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+        i += 4;
+
+        let prev_rows: Vec<&str> = prev.split('\n').collect();
+        let mut rows: Vec<String> = (0..num_rows)
+            .map(|r| prev_rows.get(r).copied().unwrap_or("").to_string())
+            .collect();
+
+        for _ in 0..num_spans {
+            if i + 12 > data.len() {
+                return Err(anyhow!("SpanDelta decode error: incomplete span header"));
+            }
+
+            let row = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+            i += 4;
+            let start_col = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+            i += 4;
+            let run_len = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+            i += 4;
+
+            if i + run_len > data.len() {
+                return Err(anyhow!("SpanDelta decode error: incomplete span run"));
+            }
+
+            let run = RleEncoder.decode(&data[i..i + run_len], None)?;
+            i += run_len;
+
+            if let Some(target_row) = rows.get_mut(row) {
+                let mut chars: Vec<char> = target_row.chars().collect();
+                chars.truncate(start_col.min(chars.len()));
+                chars.extend(run.chars());
+                *target_row = chars.into_iter().collect();
+            }
+        }
+
+        Ok(rows.join("\n"))
+    }
+
+    fn compression_type(&self) -> CompressionType {
+        CompressionType::SpanDelta
+    }
+}
+
 /// Zlib compression
 pub struct ZlibCodec;
 
@@ -247,12 +377,84 @@ impl FrameEncoder for ZlibCodec {
     }
 }
 
+/// Brotli compression - generally better ratio than zlib on ASCII/terminal text
+pub struct BrotliCodec;
+
+impl FrameEncoder for BrotliCodec {
+    fn encode(&self, current: &str, _previous: Option<&str>) -> Result<Vec<u8>> {
+        let mut encoded = Vec::new();
+        brotli::BrotliCompress(
+            &mut current.as_bytes(),
+            &mut encoded,
+            &brotli::enc::BrotliEncoderParams::default(),
+        )
+        .map_err(|e| anyhow!("Brotli encode error: {}", e))?;
+        Ok(encoded)
+    }
+
+    fn decode(&self, data: &[u8], _previous: Option<&str>) -> Result<String> {
+        let mut decoded = Vec::new();
+        brotli::BrotliDecompress(&mut &data[..], &mut decoded)
+            .map_err(|e| anyhow!("Brotli decode error: {}", e))?;
+        String::from_utf8(decoded).map_err(|e| anyhow!("UTF-8 decode error: {}", e))
+    }
+
+    fn compression_type(&self) -> CompressionType {
+        CompressionType::Brotli
+    }
+}
+
+/// Zstd compression - fast, also better ratio than zlib on ASCII/terminal text
+pub struct ZstdCodec;
+
+impl FrameEncoder for ZstdCodec {
+    fn encode(&self, current: &str, _previous: Option<&str>) -> Result<Vec<u8>> {
+        zstd::stream::encode_all(current.as_bytes(), 0)
+            .map_err(|e| anyhow!("Zstd encode error: {}", e))
+    }
+
+    fn decode(&self, data: &[u8], _previous: Option<&str>) -> Result<String> {
+        let decoded = zstd::stream::decode_all(data)
+            .map_err(|e| anyhow!("Zstd decode error: {}", e))?;
+        String::from_utf8(decoded).map_err(|e| anyhow!("UTF-8 decode error: {}", e))
+    }
+
+    fn compression_type(&self) -> CompressionType {
+        CompressionType::Zstd
+    }
+}
+
+/// LZ4 block compression - much faster to encode than `Zlib`/`Brotli`/
+/// `Zstd`, while still beating raw on static terminal content
+pub struct Lz4Codec;
+
+impl FrameEncoder for Lz4Codec {
+    fn encode(&self, current: &str, _previous: Option<&str>) -> Result<Vec<u8>> {
+        Ok(lz4_flex::compress_prepend_size(current.as_bytes()))
+    }
+
+    fn decode(&self, data: &[u8], _previous: Option<&str>) -> Result<String> {
+        let decoded = lz4_flex::decompress_size_prepended(data)
+            .map_err(|e| anyhow!("Lz4 decode error: {}", e))?;
+        String::from_utf8(decoded).map_err(|e| anyhow!("UTF-8 decode error: {}", e))
+    }
+
+    fn compression_type(&self) -> CompressionType {
+        CompressionType::Lz4
+    }
+}
+
 /// Hybrid encoder: automatically selects best compression
 pub struct HybridEncoder {
     none: NoneEncoder,
     rle: RleEncoder,
     delta: DeltaEncoder,
+    span_delta: SpanDeltaEncoder,
     zlib: ZlibCodec,
+    brotli: BrotliCodec,
+    zstd: ZstdCodec,
+    lz4: Lz4Codec,
+    motion: crate::motion_delta::MotionEncoder,
 }
 
 impl HybridEncoder {
@@ -261,7 +463,12 @@ impl HybridEncoder {
             none: NoneEncoder,
             rle: RleEncoder,
             delta: DeltaEncoder,
+            span_delta: SpanDeltaEncoder,
             zlib: ZlibCodec,
+            brotli: BrotliCodec,
+            zstd: ZstdCodec,
+            lz4: Lz4Codec,
+            motion: crate::motion_delta::MotionEncoder,
         }
     }
 
@@ -277,7 +484,16 @@ impl HybridEncoder {
         } else {
             vec![]
         };
+        let span_delta_result = self.span_delta.encode(current, previous)?;
         let zlib_result = self.zlib.encode(current, previous)?;
+        let brotli_result = self.brotli.encode(current, previous)?;
+        let zstd_result = self.zstd.encode(current, previous)?;
+        let lz4_result = self.lz4.encode(current, previous)?;
+        let motion_result = if previous.is_some() {
+            self.motion.encode(current, previous)?
+        } else {
+            vec![]
+        };
 
         // Select the smallest
         let mut best = (CompressionType::None, none_result);
@@ -290,10 +506,30 @@ impl HybridEncoder {
             best = (CompressionType::Delta, delta_result);
         }
 
+        if span_delta_result.len() < best.1.len() {
+            best = (CompressionType::SpanDelta, span_delta_result);
+        }
+
         if zlib_result.len() < best.1.len() {
             best = (CompressionType::Zlib, zlib_result);
         }
 
+        if brotli_result.len() < best.1.len() {
+            best = (CompressionType::Brotli, brotli_result);
+        }
+
+        if zstd_result.len() < best.1.len() {
+            best = (CompressionType::Zstd, zstd_result);
+        }
+
+        if lz4_result.len() < best.1.len() {
+            best = (CompressionType::Lz4, lz4_result);
+        }
+
+        if !motion_result.is_empty() && motion_result.len() < best.1.len() {
+            best = (CompressionType::Motion, motion_result);
+        }
+
         Ok(CompressedFrame {
             compression_type: best.0,
             data: best.1,
@@ -310,16 +546,28 @@ impl Default for HybridEncoder {
 }
 
 impl FrameEncoder for HybridEncoder {
+    /// Encodes with the smallest-winning compression and prepends a one-byte
+    /// tag identifying which one was chosen, so `decode` can dispatch
+    /// correctly without external metadata. This tag is local to the
+    /// standalone `FrameEncoder` round-trip - `CompressedFrame` already
+    /// carries the compression type itself, so `encode_best` never adds it.
     fn encode(&self, current: &str, previous: Option<&str>) -> Result<Vec<u8>> {
         let frame = self.encode_best(current, previous)?;
-        Ok(frame.data)
+        let mut tagged = Vec::with_capacity(1 + frame.data.len());
+        tagged.push(frame.compression_type as u8);
+        tagged.extend_from_slice(&frame.data);
+        Ok(tagged)
     }
 
     fn decode(&self, data: &[u8], previous: Option<&str>) -> Result<String> {
-        // For hybrid decoder, we need to know the compression type
-        // This is typically stored in the CompressedFrame metadata
-        // Default to zlib for compatibility
-        self.zlib.decode(data, previous)
+        let (&tag, rest) = data
+            .split_first()
+            .ok_or_else(|| anyhow!("Hybrid decode error: empty data"))?;
+
+        let compression_type = CompressionType::from_u8(tag)
+            .ok_or_else(|| anyhow!("Hybrid decode error: unknown compression tag {}", tag))?;
+
+        get_encoder(compression_type).decode(rest, previous)
     }
 
     fn compression_type(&self) -> CompressionType {
@@ -327,13 +575,70 @@ impl FrameEncoder for HybridEncoder {
     }
 }
 
+/// Placeholder returned by `get_encoder` for `CompressionType::ZstdDict`.
+/// Dictionary-based compression carries state (the trained dictionary and
+/// its id) across frames, which doesn't fit the stateless `FrameEncoder`
+/// signature - use `ZstdDictEncoder`/`ZstdDictDecoder` directly instead.
+pub struct ZstdDictPlaceholder;
+
+impl FrameEncoder for ZstdDictPlaceholder {
+    fn encode(&self, _current: &str, _previous: Option<&str>) -> Result<Vec<u8>> {
+        Err(anyhow!(
+            "ZstdDict compression carries dictionary state - use ZstdDictEncoder, not get_encoder"
+        ))
+    }
+
+    fn decode(&self, _data: &[u8], _previous: Option<&str>) -> Result<String> {
+        Err(anyhow!(
+            "ZstdDict compression carries dictionary state - use ZstdDictDecoder, not get_encoder"
+        ))
+    }
+
+    fn compression_type(&self) -> CompressionType {
+        CompressionType::ZstdDict
+    }
+}
+
+/// Placeholder returned by `get_encoder` for `CompressionType::Lz4Stream`.
+/// Streaming LZ4 carries a ring-buffer window across frames, which doesn't
+/// fit the stateless `FrameEncoder` signature - use
+/// `Lz4StreamEncoder`/`Lz4StreamDecoder` directly instead (see
+/// `monegle-sender`'s `FrameBatcher` and `monegle-receiver`'s
+/// `FrameDecoder`).
+pub struct Lz4StreamPlaceholder;
+
+impl FrameEncoder for Lz4StreamPlaceholder {
+    fn encode(&self, _current: &str, _previous: Option<&str>) -> Result<Vec<u8>> {
+        Err(anyhow!(
+            "Lz4Stream compression carries window state - use Lz4StreamEncoder, not get_encoder"
+        ))
+    }
+
+    fn decode(&self, _data: &[u8], _previous: Option<&str>) -> Result<String> {
+        Err(anyhow!(
+            "Lz4Stream compression carries window state - use Lz4StreamDecoder, not get_encoder"
+        ))
+    }
+
+    fn compression_type(&self) -> CompressionType {
+        CompressionType::Lz4Stream
+    }
+}
+
 /// Get an encoder for the specified compression type
 pub fn get_encoder(compression_type: CompressionType) -> Box<dyn FrameEncoder> {
     match compression_type {
         CompressionType::None => Box::new(NoneEncoder),
         CompressionType::Rle => Box::new(RleEncoder),
         CompressionType::Delta => Box::new(DeltaEncoder),
+        CompressionType::SpanDelta => Box::new(SpanDeltaEncoder),
         CompressionType::Zlib => Box::new(ZlibCodec),
+        CompressionType::Brotli => Box::new(BrotliCodec),
+        CompressionType::Zstd => Box::new(ZstdCodec),
+        CompressionType::Lz4 => Box::new(Lz4Codec),
+        CompressionType::Motion => Box::new(crate::motion_delta::MotionEncoder),
+        CompressionType::ZstdDict => Box::new(ZstdDictPlaceholder),
+        CompressionType::Lz4Stream => Box::new(Lz4StreamPlaceholder),
         CompressionType::Auto => Box::new(HybridEncoder::new()),
     }
 }
@@ -344,6 +649,15 @@ pub fn decode_frame(frame: &CompressedFrame, previous: Option<&str>) -> Result<S
     encoder.decode(&frame.data, previous)
 }
 
+/// Same as `decode_frame`, but for a borrowing `CompressedFrameRef` (see
+/// `crate::zerocopy`) so a receiver parsing batches with `FrameBatch::parse_ref`
+/// can decode straight from the received buffer without first copying each
+/// frame into an owned `CompressedFrame`.
+pub fn decode_frame_ref(frame: &crate::zerocopy::CompressedFrameRef<'_>, previous: Option<&str>) -> Result<String> {
+    let encoder = get_encoder(frame.compression_type);
+    encoder.decode(frame.data, previous)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -368,6 +682,37 @@ mod tests {
         assert_eq!(frame2, decoded);
     }
 
+    #[test]
+    fn test_span_delta_keyframe_roundtrip() {
+        let encoder = SpanDeltaEncoder;
+        let input = "aaaa\nbbbb\ncccc";
+        let encoded = encoder.encode(input, None).unwrap();
+        let decoded = encoder.decode(&encoded, None).unwrap();
+        assert_eq!(input, decoded);
+    }
+
+    #[test]
+    fn test_span_delta_encoding() {
+        let encoder = SpanDeltaEncoder;
+        let frame1 = "aaaa\nbbbb\ncccc";
+        let frame2 = "aaXa\nbbbb\nccXX";
+
+        let encoded = encoder.encode(frame2, Some(frame1)).unwrap();
+        let decoded = encoder.decode(&encoded, Some(frame1)).unwrap();
+        assert_eq!(frame2, decoded);
+    }
+
+    #[test]
+    fn test_span_delta_grows_row_count() {
+        let encoder = SpanDeltaEncoder;
+        let frame1 = "aaaa\nbbbb";
+        let frame2 = "aaaa\nbbbb\ncccc";
+
+        let encoded = encoder.encode(frame2, Some(frame1)).unwrap();
+        let decoded = encoder.decode(&encoded, Some(frame1)).unwrap();
+        assert_eq!(frame2, decoded);
+    }
+
     #[test]
     fn test_zlib_encoding() {
         let encoder = ZlibCodec;
@@ -376,4 +721,42 @@ mod tests {
         let decoded = encoder.decode(&encoded, None).unwrap();
         assert_eq!(input, decoded);
     }
+
+    #[test]
+    fn test_brotli_encoding() {
+        let encoder = BrotliCodec;
+        let input = "The quick brown fox jumps over the lazy dog";
+        let encoded = encoder.encode(input, None).unwrap();
+        let decoded = encoder.decode(&encoded, None).unwrap();
+        assert_eq!(input, decoded);
+    }
+
+    #[test]
+    fn test_zstd_encoding() {
+        let encoder = ZstdCodec;
+        let input = "The quick brown fox jumps over the lazy dog";
+        let encoded = encoder.encode(input, None).unwrap();
+        let decoded = encoder.decode(&encoded, None).unwrap();
+        assert_eq!(input, decoded);
+    }
+
+    #[test]
+    fn test_lz4_encoding() {
+        let encoder = Lz4Codec;
+        let input = "The quick brown fox jumps over the lazy dog";
+        let encoded = encoder.encode(input, None).unwrap();
+        let decoded = encoder.decode(&encoded, None).unwrap();
+        assert_eq!(input, decoded);
+    }
+
+    #[test]
+    fn test_hybrid_self_describing_roundtrip() {
+        // Previously hardcoded zlib on decode, which broke whenever
+        // encode_best picked a different winner
+        let encoder = HybridEncoder::new();
+        let input = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let encoded = encoder.encode(input, None).unwrap();
+        let decoded = encoder.decode(&encoded, None).unwrap();
+        assert_eq!(input, decoded);
+    }
 }
@@ -66,6 +66,52 @@ pub struct SenderConfig {
     /// Target address for transactions
     #[serde(default = "default_target_address")]
     pub target_address: String,
+
+    /// Manual camera controls (exposure, gain, white balance, brightness)
+    /// to pin before `open_stream()`, overriding the camera's auto
+    /// defaults for low-light scenes
+    #[serde(default)]
+    pub camera_controls: CameraControlsConfig,
+
+    /// How many frames the capture-to-encode handoff buffers before
+    /// overwriting the oldest (see `FrameSlotSender` in monegle-sender).
+    /// 1 minimizes latency for a live stream; a higher value smooths over
+    /// brief encoder stalls at the cost of staler frames, suiting
+    /// recording better
+    #[serde(default = "default_capture_slot_depth")]
+    pub capture_slot_depth: usize,
+
+    /// Apply Floyd-Steinberg error-diffusion dithering to brightness
+    /// before glyph selection, trading per-pixel independence for smoother
+    /// gradients at small character grids (see `AsciiConverter::convert`)
+    #[serde(default)]
+    pub dither: bool,
+}
+
+/// Manual camera controls to pin before `open_stream()`. Any field left
+/// `None` leaves that control on the camera's own default (usually auto)
+/// setting; `auto_exposure`/`auto_white_balance` let auto mode be
+/// requested explicitly rather than just left alone.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CameraControlsConfig {
+    /// Exposure time, in the camera driver's own units
+    pub exposure: Option<i64>,
+
+    /// Explicitly force auto-exposure on/off, independent of `exposure`
+    pub auto_exposure: Option<bool>,
+
+    /// Sensor gain
+    pub gain: Option<i64>,
+
+    /// White balance color temperature
+    pub white_balance: Option<i64>,
+
+    /// Explicitly force auto white balance on/off, independent of
+    /// `white_balance`
+    pub auto_white_balance: Option<bool>,
+
+    /// Brightness offset
+    pub brightness: Option<i64>,
 }
 
 /// Receiver configuration
@@ -89,6 +135,12 @@ pub struct ReceiverConfig {
     /// Polling interval in milliseconds (if not using WebSocket)
     #[serde(default = "default_polling_interval")]
     pub polling_interval: u64,
+
+    /// Also subscribe to the pending-transaction pubsub feed for lower
+    /// latency than waiting on confirmed blocks (WebSocket mode only; the
+    /// confirmed-block scanner keeps running regardless as a backstop)
+    #[serde(default = "default_true")]
+    pub pending_tx_subscription: bool,
 }
 
 fn default_max_batch_size() -> usize {
@@ -99,6 +151,10 @@ fn default_keyframe_interval() -> u64 {
     30 // Every 30 frames
 }
 
+fn default_capture_slot_depth() -> usize {
+    1 // Realtime: always work on the freshest frame
+}
+
 fn default_target_address() -> String {
     "0x0000000000000000000000000000000000000001".to_string()
 }
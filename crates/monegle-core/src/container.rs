@@ -0,0 +1,330 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::{FrameBatch, StreamMetadata};
+
+/// Box fourcc identifying the header box: a single instance at the start
+/// of the file carrying the stream's `StreamMetadata`.
+const MHDR: [u8; 4] = *b"MHDR";
+
+/// Box fourcc identifying a batch box: one per recorded `FrameBatch`, in
+/// the order they were written.
+const MDAT: [u8; 4] = *b"MDAT";
+
+/// Box fourcc identifying the trailing keyframe index box.
+const SIDX: [u8; 4] = *b"SIDX";
+
+/// `(frame_number, byte offset of the MDAT box carrying that frame)` for
+/// every keyframe written, in ascending `frame_number` order - lets
+/// `StreamReader::seek_to_frame` jump straight to the nearest preceding
+/// keyframe instead of scanning the whole file.
+type KeyframeIndex = Vec<(u64, u64)>;
+
+/// Write a length-prefixed box: a placeholder 4-byte size, the 4-byte
+/// fourcc, then `content`, with the size backfilled once `content`'s
+/// length is known - the same two-pass pattern MP4 muxers use for boxes
+/// whose payload isn't known up front, kept here even though `content` is
+/// already fully built so every box on disk looks the same regardless of
+/// how it was produced. Returns the byte offset the box started at, for
+/// callers (like the keyframe index) that need to point back at it.
+fn write_box<W: Write + Seek>(writer: &mut W, fourcc: &[u8; 4], content: &[u8]) -> Result<u64> {
+    let box_start = writer.stream_position()?;
+
+    writer.write_all(&0u32.to_le_bytes())?;
+    writer.write_all(fourcc)?;
+    writer.write_all(content)?;
+
+    let box_end = writer.stream_position()?;
+    let size = (box_end - box_start) as u32;
+    writer.seek(SeekFrom::Start(box_start))?;
+    writer.write_all(&size.to_le_bytes())?;
+    writer.seek(SeekFrom::Start(box_end))?;
+
+    Ok(box_start)
+}
+
+/// Read a box header (size + fourcc) at the reader's current position,
+/// returning the total box size including the 8-byte header (callers
+/// subtract 8 to get the size of the `content` that follows).
+fn read_box_header<R: Read>(reader: &mut R) -> Result<(u32, [u8; 4])> {
+    let mut size_buf = [0u8; 4];
+    reader.read_exact(&mut size_buf)?;
+    let size = u32::from_le_bytes(size_buf);
+
+    let mut fourcc = [0u8; 4];
+    reader.read_exact(&mut fourcc)?;
+
+    Ok((size, fourcc))
+}
+
+/// Writes a stream of `FrameBatch`es to a seekable box-structured
+/// container file: an `MHDR` box with the stream's `StreamMetadata`,
+/// followed by one `MDAT` box per batch (each carrying its own CRC32C over
+/// the encoded batch, on top of `FrameBatch`'s internal `crc`, so a
+/// truncated or corrupted box is caught before `decode_from_bytes` ever
+/// runs), and a trailing `SIDX` box indexing every keyframe's byte offset.
+/// A companion `StreamReader` parses that index to support `seek_to_frame`.
+pub struct StreamRecorder {
+    writer: BufWriter<File>,
+    keyframe_index: KeyframeIndex,
+}
+
+impl StreamRecorder {
+    /// Create `path`, writing the `MHDR` header box immediately.
+    pub fn create(path: impl AsRef<Path>, metadata: &StreamMetadata) -> Result<Self> {
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+
+        let header = bincode::serialize(metadata)
+            .map_err(|e| anyhow!("Failed to encode stream header: {}", e))?;
+        write_box(&mut writer, &MHDR, &header)?;
+
+        Ok(Self {
+            writer,
+            keyframe_index: Vec::new(),
+        })
+    }
+
+    /// Append `batch` as an `MDAT` box, recording the box's offset against
+    /// every keyframe frame it carries.
+    pub fn write_batch(&mut self, batch: &FrameBatch) -> Result<()> {
+        let encoded = batch.encode_to_bytes()?;
+        let box_crc = crc32c::crc32c(&encoded);
+
+        let mut content = Vec::with_capacity(4 + encoded.len());
+        content.extend_from_slice(&box_crc.to_le_bytes());
+        content.extend_from_slice(&encoded);
+
+        let box_start = write_box(&mut self.writer, &MDAT, &content)?;
+
+        for frame in &batch.frames {
+            if frame.is_keyframe {
+                self.keyframe_index.push((frame.frame_number, box_start));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the trailing `SIDX` index box, followed by an 8-byte
+    /// little-endian footer carrying that box's own byte offset so
+    /// `StreamReader::open` can find it from the end of the file without
+    /// scanning every `MDAT` box first. Consumes `self` since no more
+    /// batches can be appended after the index is written.
+    pub fn finalize(mut self) -> Result<()> {
+        let index = bincode::serialize(&self.keyframe_index)
+            .map_err(|e| anyhow!("Failed to encode keyframe index: {}", e))?;
+        let box_start = write_box(&mut self.writer, &SIDX, &index)?;
+        self.writer.write_all(&box_start.to_le_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads a container file written by `StreamRecorder`, supporting
+/// sequential replay from the start or `seek_to_frame` via the `SIDX`
+/// keyframe index.
+pub struct StreamReader {
+    file: File,
+    pub metadata: StreamMetadata,
+    keyframe_index: KeyframeIndex,
+    first_batch_offset: u64,
+    sidx_offset: u64,
+}
+
+impl StreamReader {
+    /// Open `path`, reading the `MHDR` header box and the trailing `SIDX`
+    /// keyframe index up front.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let mut file = File::open(path)?;
+
+        let (size, fourcc) = read_box_header(&mut file)?;
+        if fourcc != MHDR {
+            return Err(anyhow!("expected MHDR box at start of file, found {:?}", fourcc));
+        }
+        let mut header = vec![0u8; size as usize - 8];
+        file.read_exact(&mut header)?;
+        let metadata: StreamMetadata = bincode::deserialize(&header)
+            .map_err(|e| anyhow!("Failed to decode stream header: {}", e))?;
+
+        let first_batch_offset = file.stream_position()?;
+
+        let file_len = file.seek(SeekFrom::End(0))?;
+        // At minimum there's an (empty) SIDX box's 8-byte header plus the
+        // 8-byte footer pointing at it - anything short of that can't hold
+        // a real SIDX box, regardless of how many MDAT boxes preceded it.
+        if file_len < first_batch_offset + 8 + 8 {
+            return Err(anyhow!("container file missing SIDX footer"));
+        }
+        file.seek(SeekFrom::Start(file_len - 8))?;
+        let mut footer = [0u8; 8];
+        file.read_exact(&mut footer)?;
+        let sidx_offset = u64::from_le_bytes(footer);
+
+        file.seek(SeekFrom::Start(sidx_offset))?;
+        let (size, fourcc) = read_box_header(&mut file)?;
+        if fourcc != SIDX {
+            return Err(anyhow!("expected SIDX box at footer offset, found {:?}", fourcc));
+        }
+        let mut index_bytes = vec![0u8; size as usize - 8];
+        file.read_exact(&mut index_bytes)?;
+        let mut keyframe_index: KeyframeIndex = bincode::deserialize(&index_bytes)
+            .map_err(|e| anyhow!("Failed to decode keyframe index: {}", e))?;
+        keyframe_index.sort_unstable_by_key(|&(frame_number, _)| frame_number);
+
+        file.seek(SeekFrom::Start(first_batch_offset))?;
+
+        Ok(Self {
+            file,
+            metadata,
+            keyframe_index,
+            first_batch_offset,
+            sidx_offset,
+        })
+    }
+
+    /// Reposition the read cursor at the `MDAT` box of the nearest
+    /// keyframe at or before `frame_number`. Callers then replay forward
+    /// with `read_next_batch` until the target frame is reached.
+    pub fn seek_to_frame(&mut self, frame_number: u64) -> Result<()> {
+        let offset = self
+            .keyframe_index
+            .iter()
+            .rev()
+            .find(|&&(keyframe_number, _)| keyframe_number <= frame_number)
+            .map(|&(_, offset)| offset)
+            .ok_or_else(|| anyhow!("no keyframe at or before frame {}", frame_number))?;
+
+        self.file.seek(SeekFrom::Start(offset))?;
+        Ok(())
+    }
+
+    /// Reposition the read cursor at the first `MDAT` box, for replaying
+    /// the whole stream from the start.
+    pub fn rewind(&mut self) -> Result<()> {
+        self.file.seek(SeekFrom::Start(self.first_batch_offset))?;
+        Ok(())
+    }
+
+    /// Read and decode the next `MDAT` box, verifying its CRC32C first.
+    /// Returns `None` once the cursor reaches the trailing `SIDX` box.
+    pub fn read_next_batch(&mut self) -> Result<Option<FrameBatch>> {
+        if self.file.stream_position()? >= self.sidx_offset {
+            return Ok(None);
+        }
+
+        let (size, fourcc) = read_box_header(&mut self.file)?;
+        if fourcc != MDAT {
+            return Err(anyhow!("expected MDAT box, found {:?}", fourcc));
+        }
+
+        let mut content = vec![0u8; size as usize - 8];
+        self.file.read_exact(&mut content)?;
+
+        let stored_crc = u32::from_le_bytes(content[..4].try_into().unwrap());
+        let encoded = &content[4..];
+        let actual_crc = crc32c::crc32c(encoded);
+        if actual_crc != stored_crc {
+            return Err(anyhow!(
+                "MDAT box CRC mismatch: expected {:#x}, got {:#x}",
+                stored_crc,
+                actual_crc
+            ));
+        }
+
+        Ok(Some(FrameBatch::decode_from_bytes(encoded)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharacterSet, ColorMode, CompressedFrame, CompressionType};
+
+    fn sample_metadata() -> StreamMetadata {
+        StreamMetadata {
+            fps: 15,
+            width: 80,
+            height: 24,
+            compression_type: CompressionType::Rle,
+            character_set: CharacterSet::Standard,
+            color_mode: ColorMode::None,
+            frames_per_batch: 2,
+            keyframe_interval: 2,
+        }
+    }
+
+    fn sample_batch(sequence: u64, base_frame_number: u64) -> FrameBatch {
+        let frames = vec![
+            CompressedFrame {
+                compression_type: CompressionType::Rle,
+                data: vec![1, 2, 3],
+                frame_number: base_frame_number,
+                is_keyframe: true,
+            },
+            CompressedFrame {
+                compression_type: CompressionType::Rle,
+                data: vec![4, 5],
+                frame_number: base_frame_number + 1,
+                is_keyframe: false,
+            },
+        ];
+
+        let mut batch = FrameBatch {
+            stream_id: [3u8; 32],
+            sequence,
+            metadata: sample_metadata(),
+            frames,
+            base_frame_number,
+            base_timestamp: 1_700_000_000_000 + sequence,
+            crc: 0,
+        };
+        batch.crc = batch.compute_crc().unwrap();
+        batch
+    }
+
+    #[test]
+    fn test_container_roundtrip_and_seek() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("monegle-container-test-{}.bin", std::process::id()));
+
+        let mut recorder = StreamRecorder::create(&path, &sample_metadata()).unwrap();
+        for i in 0..5u64 {
+            recorder.write_batch(&sample_batch(i, i * 2)).unwrap();
+        }
+        recorder.finalize().unwrap();
+
+        let mut reader = StreamReader::open(&path).unwrap();
+        assert_eq!(reader.metadata, sample_metadata());
+
+        let mut replayed = Vec::new();
+        while let Some(batch) = reader.read_next_batch().unwrap() {
+            replayed.push(batch);
+        }
+        assert_eq!(replayed.len(), 5);
+        assert_eq!(replayed[3].base_frame_number, 6);
+
+        reader.seek_to_frame(7).unwrap();
+        let batch = reader.read_next_batch().unwrap().unwrap();
+        assert_eq!(batch.base_frame_number, 6);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_seek_to_frame_before_first_keyframe_errors() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("monegle-container-test-empty-{}.bin", std::process::id()));
+
+        let mut recorder = StreamRecorder::create(&path, &sample_metadata()).unwrap();
+        recorder.write_batch(&sample_batch(0, 10)).unwrap();
+        recorder.finalize().unwrap();
+
+        let mut reader = StreamReader::open(&path).unwrap();
+        assert!(reader.seek_to_frame(5).is_err());
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,247 @@
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Sparkline},
+    Terminal,
+};
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+/// How many ticks of buffer-depth history the sparkline keeps
+const DEPTH_HISTORY_LEN: usize = 60;
+
+/// How many recent FPS samples the rolling quantile estimator keeps. A
+/// small bounded reservoir rather than the full history, since only the
+/// distribution's shape is needed for p50/p95/p99, not exact ordering over
+/// the whole run
+const FPS_RESERVOIR_LEN: usize = 200;
+
+/// Incremental p50/p95/p99 estimator over a bounded reservoir of recent
+/// samples. Trades exact quantiles for O(1) memory; the sort only runs over
+/// the small, bounded reservoir when a quantile is actually read
+#[derive(Debug, Default)]
+struct RollingQuantiles {
+    samples: VecDeque<f32>,
+}
+
+impl RollingQuantiles {
+    fn push(&mut self, value: f32) {
+        if self.samples.len() >= FPS_RESERVOIR_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    fn quantile(&self, q: f32) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+
+        let mut sorted: Vec<f32> = self.samples.iter().copied().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let idx = (((sorted.len() - 1) as f32) * q).floor() as usize;
+        sorted[idx]
+    }
+
+    fn p50(&self) -> f32 {
+        self.quantile(0.50)
+    }
+
+    fn p95(&self) -> f32 {
+        self.quantile(0.95)
+    }
+
+    fn p99(&self) -> f32 {
+        self.quantile(0.99)
+    }
+}
+
+/// Counters the dashboard displays as-is, without any smoothing. Sourced
+/// from `BufferStats` on the receiver's playback path; the sender's
+/// counter test mode has no buffer to underrun or jump backwards in, so it
+/// just reports zero
+#[derive(Debug, Clone, Default)]
+pub struct DashboardCounters {
+    pub underruns: usize,
+    pub backward_jumps: usize,
+}
+
+/// One tick's worth of telemetry fed into the dashboard. Fields that don't
+/// apply to a given caller (e.g. `sequence_range` in the sender's counter
+/// test mode, which has no buffer) are left at their empty default rather
+/// than omitted, so the widget layer stays shared
+#[derive(Debug, Clone)]
+pub struct DashboardTick {
+    pub depth: usize,
+    pub current_fps: f32,
+    pub target_fps: f32,
+    pub adaptive_fps: f32,
+    pub sequence_range: Option<(u64, u64)>,
+    pub counters: DashboardCounters,
+}
+
+/// Crossterm/ratatui dashboard rendering buffer/playback telemetry in the
+/// alternate screen: a sparkline of buffer depth over the last
+/// `DEPTH_HISTORY_LEN` ticks, current/target/adaptive FPS, a rolling FPS
+/// histogram (p50/p95/p99), sequence range, and underrun/backward-jump
+/// counters. Intended to replace ad hoc `print!("\x1B[2J\x1B[H")` clearing
+/// wherever a mode wants live telemetry instead of (or alongside) raw log
+/// lines.
+pub struct Dashboard {
+    terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    title: String,
+    depth_history: VecDeque<u64>,
+    fps_quantiles: RollingQuantiles,
+}
+
+impl Dashboard {
+    /// Enable raw mode and enter the alternate screen, matching
+    /// `TerminalDisplay`'s ratatui setup
+    pub fn enter(title: impl Into<String>) -> Result<Self> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+        terminal.clear()?;
+
+        Ok(Self {
+            terminal,
+            title: title.into(),
+            depth_history: VecDeque::with_capacity(DEPTH_HISTORY_LEN),
+            fps_quantiles: RollingQuantiles::default(),
+        })
+    }
+
+    /// Record this tick's telemetry and redraw
+    pub fn render(&mut self, tick: &DashboardTick) -> Result<()> {
+        if self.depth_history.len() >= DEPTH_HISTORY_LEN {
+            self.depth_history.pop_front();
+        }
+        self.depth_history.push_back(tick.depth as u64);
+        self.fps_quantiles.push(tick.current_fps);
+
+        let title = self.title.clone();
+        let depth_data: Vec<u64> = self.depth_history.iter().copied().collect();
+        let (p50, p95, p99) = (
+            self.fps_quantiles.p50(),
+            self.fps_quantiles.p95(),
+            self.fps_quantiles.p99(),
+        );
+        let seq_range = match tick.sequence_range {
+            Some((lo, hi)) => format!("{}-{}", lo, hi),
+            None => "n/a".to_string(),
+        };
+        let tick = tick.clone();
+
+        self.terminal.draw(|f| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Length(8),
+                    Constraint::Length(3),
+                    Constraint::Length(3),
+                ])
+                .split(f.size());
+
+            let header = Paragraph::new(Line::from(format!(
+                "{}  |  Press 'q' to quit",
+                title
+            )))
+            .style(Style::default().fg(Color::Cyan))
+            .block(Block::default().borders(Borders::ALL));
+            f.render_widget(header, chunks[0]);
+
+            let sparkline = Sparkline::default()
+                .block(Block::default().borders(Borders::ALL).title("Buffer depth (frames)"))
+                .data(&depth_data)
+                .style(Style::default().fg(Color::Green));
+            f.render_widget(sparkline, chunks[1]);
+
+            let fps_widget = Paragraph::new(vec![
+                Line::from(format!(
+                    "FPS current: {:.1} / target: {:.1} / adaptive: {:.1}",
+                    tick.current_fps, tick.target_fps, tick.adaptive_fps
+                )),
+                Line::from(format!("p50: {:.1}  p95: {:.1}  p99: {:.1}", p50, p95, p99)),
+            ])
+            .block(Block::default().borders(Borders::ALL).title("FPS"));
+            f.render_widget(fps_widget, chunks[2]);
+
+            let counters = Paragraph::new(Line::from(format!(
+                "Sequence range: {}  |  Underruns: {}  |  Backward jumps: {}",
+                seq_range, tick.counters.underruns, tick.counters.backward_jumps
+            )))
+            .block(Block::default().borders(Borders::ALL).title("Counters"));
+            f.render_widget(counters, chunks[3]);
+        })?;
+
+        Ok(())
+    }
+
+    /// Non-blocking check for a quit keypress ('q' or Esc), matching
+    /// `TerminalDisplay`'s ratatui loop convention
+    pub fn should_quit(&self) -> Result<bool> {
+        if event::poll(Duration::from_millis(0))? {
+            if let Event::Key(key) = event::read()? {
+                return Ok(key.code == KeyCode::Char('q') || key.code == KeyCode::Esc);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Restore the terminal. Callers should invoke this once on the way
+    /// out rather than relying on `Drop`, matching how `TerminalDisplay`
+    /// tears itself down after its loop exits.
+    pub fn leave(mut self) -> Result<()> {
+        disable_raw_mode()?;
+        execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
+        self.terminal.show_cursor()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantiles_on_empty_reservoir_are_zero() {
+        let q = RollingQuantiles::default();
+        assert_eq!(q.p50(), 0.0);
+        assert_eq!(q.p99(), 0.0);
+    }
+
+    #[test]
+    fn test_quantiles_of_uniform_samples() {
+        let mut q = RollingQuantiles::default();
+        for v in 1..=100 {
+            q.push(v as f32);
+        }
+        assert_eq!(q.p50(), 50.0);
+        assert_eq!(q.p99(), 99.0);
+    }
+
+    #[test]
+    fn test_reservoir_evicts_oldest_samples() {
+        let mut q = RollingQuantiles::default();
+        for _ in 0..FPS_RESERVOIR_LEN {
+            q.push(1.0);
+        }
+        // Push enough high values to fully displace the low ones
+        for _ in 0..FPS_RESERVOIR_LEN {
+            q.push(100.0);
+        }
+        assert_eq!(q.p50(), 100.0);
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+use crate::CompressionType;
+
+/// Minimum number of training frames buffered before a dictionary is
+/// trained. Below this the zstd trainer has too little data to produce
+/// anything useful, so encoding falls back to plain (dictionary-less) zstd.
+const MIN_TRAINING_SAMPLES: usize = 64;
+
+/// Target size of the trained dictionary, in bytes
+const DICTIONARY_SIZE: usize = 16 * 1024;
+
+/// Zstd compression level used both for training and for dictionary-backed
+/// compression/decompression
+const ZSTD_LEVEL: i32 = 3;
+
+/// Safety cap on a decompressed frame's size, since the bulk decompressor
+/// needs a capacity hint up front
+const MAX_DECOMPRESSED_FRAME_BYTES: usize = 8 * 1024 * 1024;
+
+/// A dictionary id of zero means "no dictionary" - frames tagged with it
+/// were compressed with plain zstd (training not yet complete)
+const NO_DICTIONARY: u32 = 0;
+
+/// A single dictionary-compressed frame, plus the out-of-band state the
+/// receiver needs to decode it.
+pub struct DictFrame {
+    pub data: Vec<u8>,
+    pub compression_type: CompressionType,
+
+    /// Which dictionary (if any, see `NO_DICTIONARY`) this frame was
+    /// compressed against
+    pub dictionary_id: u32,
+
+    /// Set only on the keyframe that introduces a freshly trained
+    /// dictionary. The receiver must build a matching `Decompressor` from
+    /// these bytes before it can decode any later frame tagged with
+    /// `dictionary_id`.
+    pub is_keyframe: bool,
+    pub dictionary: Option<Vec<u8>>,
+}
+
+/// Sender-side half of dictionary-based cross-frame Zstd compression.
+///
+/// Terminal streams repeat the same box-drawing glyphs, ANSI escapes, and
+/// layout across frames far more than they differ frame-to-frame - exactly
+/// what a trained dictionary captures, and what makes it beat `DeltaEncoder`
+/// without `DeltaEncoder`'s fragility across scrolls/clears (a dictionary
+/// frame is still a complete, independently decodable frame).
+///
+/// Buffers the first `MIN_TRAINING_SAMPLES` frames as training data, trains
+/// a shared dictionary from them, then compresses every later frame against
+/// it. Streams shorter than the training threshold never leave plain-zstd
+/// fallback.
+pub struct ZstdDictEncoder {
+    training_samples: Vec<Vec<u8>>,
+    dictionary_id: u32,
+    dictionary_bytes: Option<Vec<u8>>,
+    dictionary_shipped: bool,
+}
+
+impl ZstdDictEncoder {
+    pub fn new() -> Self {
+        Self {
+            training_samples: Vec::new(),
+            dictionary_id: NO_DICTIONARY,
+            dictionary_bytes: None,
+            dictionary_shipped: false,
+        }
+    }
+
+    /// Encode the next frame of the stream
+    pub fn encode(&mut self, current: &str) -> Result<DictFrame> {
+        if self.dictionary_bytes.is_none() {
+            self.training_samples.push(current.as_bytes().to_vec());
+
+            if self.training_samples.len() < MIN_TRAINING_SAMPLES {
+                return self.encode_plain(current);
+            }
+
+            self.train_dictionary()?;
+        }
+
+        self.encode_with_dictionary(current)
+    }
+
+    fn train_dictionary(&mut self) -> Result<()> {
+        let dict_bytes = zstd::dict::from_samples(&self.training_samples, DICTIONARY_SIZE)
+            .map_err(|e| anyhow!("Zstd dictionary training failed: {}", e))?;
+
+        self.dictionary_id = self.dictionary_id.wrapping_add(1).max(1);
+        self.dictionary_bytes = Some(dict_bytes);
+        self.dictionary_shipped = false;
+        self.training_samples.clear();
+
+        Ok(())
+    }
+
+    fn encode_plain(&self, current: &str) -> Result<DictFrame> {
+        let data = zstd::stream::encode_all(current.as_bytes(), ZSTD_LEVEL)
+            .map_err(|e| anyhow!("Zstd encode error: {}", e))?;
+
+        Ok(DictFrame {
+            data,
+            compression_type: CompressionType::ZstdDict,
+            dictionary_id: NO_DICTIONARY,
+            is_keyframe: false,
+            dictionary: None,
+        })
+    }
+
+    fn encode_with_dictionary(&mut self, current: &str) -> Result<DictFrame> {
+        let dict_bytes = self.dictionary_bytes.as_ref().expect("dictionary trained");
+
+        let mut compressor = zstd::bulk::Compressor::with_dictionary(ZSTD_LEVEL, dict_bytes)
+            .map_err(|e| anyhow!("Zstd dictionary compressor init failed: {}", e))?;
+
+        let data = compressor
+            .compress(current.as_bytes())
+            .map_err(|e| anyhow!("Zstd dictionary encode error: {}", e))?;
+
+        // Ship the dictionary exactly once, on the keyframe that starts using it
+        let dictionary = if self.dictionary_shipped {
+            None
+        } else {
+            self.dictionary_shipped = true;
+            Some(dict_bytes.clone())
+        };
+
+        Ok(DictFrame {
+            data,
+            compression_type: CompressionType::ZstdDict,
+            dictionary_id: self.dictionary_id,
+            is_keyframe: dictionary.is_some(),
+            dictionary,
+        })
+    }
+}
+
+impl Default for ZstdDictEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Receiver-side half of dictionary-based cross-frame Zstd compression.
+///
+/// Learns dictionaries from the keyframes that ship them and keeps them
+/// around by id so later frames compressed against the same dictionary can
+/// be decoded.
+pub struct ZstdDictDecoder {
+    dictionaries: HashMap<u32, Vec<u8>>,
+}
+
+impl ZstdDictDecoder {
+    pub fn new() -> Self {
+        Self {
+            dictionaries: HashMap::new(),
+        }
+    }
+
+    pub fn decode(&mut self, frame: &DictFrame) -> Result<String> {
+        if let Some(dict_bytes) = &frame.dictionary {
+            self.dictionaries.insert(frame.dictionary_id, dict_bytes.clone());
+        }
+
+        let decoded = if frame.dictionary_id == NO_DICTIONARY {
+            zstd::stream::decode_all(&frame.data[..]).map_err(|e| anyhow!("Zstd decode error: {}", e))?
+        } else {
+            let dict_bytes = self.dictionaries.get(&frame.dictionary_id).ok_or_else(|| {
+                anyhow!(
+                    "Received frame for dictionary {} before its keyframe",
+                    frame.dictionary_id
+                )
+            })?;
+
+            let mut decompressor = zstd::bulk::Decompressor::with_dictionary(dict_bytes)
+                .map_err(|e| anyhow!("Zstd dictionary decompressor init failed: {}", e))?;
+
+            decompressor
+                .decompress(&frame.data, MAX_DECOMPRESSED_FRAME_BYTES)
+                .map_err(|e| anyhow!("Zstd dictionary decode error: {}", e))?
+        };
+
+        String::from_utf8(decoded).map_err(|e| anyhow!("UTF-8 decode error: {}", e))
+    }
+}
+
+impl Default for ZstdDictDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terminal_like_frame(n: usize) -> String {
+        format!("\x1b[2J\x1b[H+--------+\n| frame {:02} |\n+--------+\n", n % 100)
+    }
+
+    #[test]
+    fn test_falls_back_to_plain_zstd_before_training_threshold() {
+        let mut encoder = ZstdDictEncoder::new();
+        let mut decoder = ZstdDictDecoder::new();
+
+        let frame = terminal_like_frame(0);
+        let encoded = encoder.encode(&frame).unwrap();
+        assert_eq!(encoded.dictionary_id, NO_DICTIONARY);
+        assert!(encoded.dictionary.is_none());
+
+        let decoded = decoder.decode(&encoded).unwrap();
+        assert_eq!(decoded, frame);
+    }
+
+    #[test]
+    fn test_trains_dictionary_and_ships_it_once() {
+        let mut encoder = ZstdDictEncoder::new();
+        let mut decoder = ZstdDictDecoder::new();
+
+        let mut keyframes_seen = 0;
+        for i in 0..(MIN_TRAINING_SAMPLES + 10) {
+            let frame = terminal_like_frame(i);
+            let encoded = encoder.encode(&frame).unwrap();
+
+            if encoded.is_keyframe {
+                keyframes_seen += 1;
+                assert!(encoded.dictionary.is_some());
+            }
+
+            let decoded = decoder.decode(&encoded).unwrap();
+            assert_eq!(decoded, frame);
+        }
+
+        assert_eq!(keyframes_seen, 1, "dictionary should ship exactly once");
+    }
+
+    #[test]
+    fn test_decoding_unknown_dictionary_id_fails() {
+        let mut decoder = ZstdDictDecoder::new();
+        let bogus = DictFrame {
+            data: vec![0, 1, 2, 3],
+            compression_type: CompressionType::ZstdDict,
+            dictionary_id: 99,
+            is_keyframe: false,
+            dictionary: None,
+        };
+
+        assert!(decoder.decode(&bogus).is_err());
+    }
+}
@@ -1,6 +1,10 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 
+/// Largest palette `encode_palette` will quantize down to - capped by the
+/// single index byte stored per character
+pub const MAX_PALETTE_SIZE: usize = 256;
+
 /// Efficient RGB frame encoding: stores characters and colors separately
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EfficientRgbFrame {
@@ -182,6 +186,159 @@ impl EfficientRgbFrame {
         })
     }
 
+    /// Encode with median-cut palette quantization instead of per-character
+    /// RGB: `max_colors` (clamped to `MAX_PALETTE_SIZE`) becomes the `K`
+    /// palette entries, each character maps to its nearest bucket's index,
+    /// and the index stream is RLE-compressed the same way `encode_compressed`
+    /// RLEs raw colors. Real camera frames rarely repeat an exact RGB
+    /// triplet but cluster tightly in color space, so this trades the RLE's
+    /// "identical neighbor" win for one that holds even when every
+    /// character's *exact* color differs - 1 index byte/char plus a tiny
+    /// palette versus 3 raw RGB bytes/char.
+    ///
+    /// Serialized as: width (2), height (2), char length (4) + chars,
+    /// palette size as `K - 1` (1 byte, so `K` itself ranges 1..=256),
+    /// `K * 3` palette bytes, then length-prefixed RLE-compressed indices.
+    pub fn encode_palette(&self, max_colors: usize) -> Result<Vec<u8>> {
+        let colors: Vec<(u8, u8, u8)> = self
+            .colors
+            .chunks_exact(3)
+            .map(|c| (c[0], c[1], c[2]))
+            .collect();
+
+        let (palette, indices) = median_cut_quantize(&colors, max_colors);
+        if palette.is_empty() || palette.len() > MAX_PALETTE_SIZE {
+            return Err(anyhow!("Palette size {} out of range", palette.len()));
+        }
+
+        let mut encoded = Vec::new();
+
+        encoded.extend_from_slice(&self.width.to_le_bytes());
+        encoded.extend_from_slice(&self.height.to_le_bytes());
+
+        let char_bytes = self.chars.as_bytes();
+        encoded.extend_from_slice(&(char_bytes.len() as u32).to_le_bytes());
+        encoded.extend_from_slice(char_bytes);
+
+        encoded.push((palette.len() - 1) as u8);
+        for (r, g, b) in &palette {
+            encoded.push(*r);
+            encoded.push(*g);
+            encoded.push(*b);
+        }
+
+        let index_rle = Self::rle_encode_indices(&indices);
+        encoded.extend_from_slice(&(index_rle.len() as u32).to_le_bytes());
+        encoded.extend_from_slice(&index_rle);
+
+        Ok(encoded)
+    }
+
+    /// Decode a frame produced by `encode_palette`
+    pub fn decode_palette(data: &[u8]) -> Result<Self> {
+        let mut pos = 0;
+
+        if data.len() < 4 {
+            return Err(anyhow!("Invalid data: too short"));
+        }
+        let width = u16::from_le_bytes([data[pos], data[pos + 1]]);
+        pos += 2;
+        let height = u16::from_le_bytes([data[pos], data[pos + 1]]);
+        pos += 2;
+
+        if data.len() < pos + 4 {
+            return Err(anyhow!("Invalid data: no char length"));
+        }
+        let char_len = u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        pos += 4;
+
+        if data.len() < pos + char_len {
+            return Err(anyhow!("Invalid data: incomplete chars"));
+        }
+        let chars = String::from_utf8(data[pos..pos + char_len].to_vec())?;
+        pos += char_len;
+
+        if data.len() < pos + 1 {
+            return Err(anyhow!("Invalid data: no palette size"));
+        }
+        let palette_size = data[pos] as usize + 1;
+        pos += 1;
+
+        let palette_bytes = palette_size * 3;
+        if data.len() < pos + palette_bytes {
+            return Err(anyhow!("Invalid data: incomplete palette"));
+        }
+        let mut palette = Vec::with_capacity(palette_size);
+        for i in 0..palette_size {
+            let base = pos + i * 3;
+            palette.push((data[base], data[base + 1], data[base + 2]));
+        }
+        pos += palette_bytes;
+
+        if data.len() < pos + 4 {
+            return Err(anyhow!("Invalid data: no index length"));
+        }
+        let index_len = u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        pos += 4;
+
+        if data.len() < pos + index_len {
+            return Err(anyhow!("Invalid data: incomplete indices"));
+        }
+        let indices = Self::rle_decode_indices(&data[pos..pos + index_len])?;
+
+        let mut colors = Vec::with_capacity(indices.len() * 3);
+        for idx in indices {
+            let (r, g, b) = *palette
+                .get(idx as usize)
+                .ok_or_else(|| anyhow!("Palette index {} out of range", idx))?;
+            colors.push(r);
+            colors.push(g);
+            colors.push(b);
+        }
+
+        Ok(Self {
+            chars,
+            colors,
+            width,
+            height,
+        })
+    }
+
+    /// RLE encode a palette index stream (1 byte/char instead of 3)
+    fn rle_encode_indices(indices: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        let mut i = 0;
+
+        while i < indices.len() {
+            let value = indices[i];
+            let mut count = 1usize;
+            while i + count < indices.len() && indices[i + count] == value && count < 255 {
+                count += 1;
+            }
+
+            encoded.push(count as u8);
+            encoded.push(value);
+            i += count;
+        }
+
+        encoded
+    }
+
+    /// RLE decode a palette index stream
+    fn rle_decode_indices(data: &[u8]) -> Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        let mut i = 0;
+
+        while i + 1 < data.len() {
+            let count = data[i] as usize;
+            let value = data[i + 1];
+            decoded.extend(std::iter::repeat(value).take(count));
+            i += 2;
+        }
+
+        Ok(decoded)
+    }
+
     /// RLE encode color data (RGB triplets)
     fn rle_encode_colors(colors: &[u8]) -> Vec<u8> {
         if colors.len() < 3 {
@@ -242,6 +399,103 @@ impl EfficientRgbFrame {
     }
 }
 
+/// Median-cut color quantization: starts with one bucket spanning every
+/// color, repeatedly splits the bucket with the widest range along any of
+/// R/G/B at that channel's median, and stops once there are `max_colors`
+/// buckets (clamped to `MAX_PALETTE_SIZE`) or no bucket can be split any
+/// further (fewer unique colors than requested). Returns each bucket's
+/// average RGB as the palette, plus one palette index per input color.
+pub fn median_cut_quantize(colors: &[(u8, u8, u8)], max_colors: usize) -> (Vec<(u8, u8, u8)>, Vec<u8>) {
+    let max_colors = max_colors.clamp(1, MAX_PALETTE_SIZE);
+
+    if colors.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut buckets: Vec<Vec<usize>> = vec![(0..colors.len()).collect()];
+
+    while buckets.len() < max_colors {
+        // Widest-range splittable bucket (>= 2 colors, non-zero range on
+        // at least one channel); buckets below that are already as
+        // uniform as they can get
+        let splittable = buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| bucket.len() >= 2)
+            .map(|(i, bucket)| (i, widest_channel(bucket, colors)))
+            .filter(|(_, (_, range))| *range > 0)
+            .max_by_key(|(_, (_, range))| *range);
+
+        let Some((bucket_index, (channel, _))) = splittable else {
+            break;
+        };
+
+        let mut bucket = buckets.remove(bucket_index);
+        bucket.sort_by_key(|&idx| channel_value(colors[idx], channel));
+        let mid = bucket.len() / 2;
+        let high = bucket.split_off(mid);
+        buckets.push(bucket);
+        buckets.push(high);
+    }
+
+    let mut palette = Vec::with_capacity(buckets.len());
+    let mut indices = vec![0u8; colors.len()];
+
+    for (bucket_index, bucket) in buckets.iter().enumerate() {
+        let (mut r_sum, mut g_sum, mut b_sum) = (0u32, 0u32, 0u32);
+        for &idx in bucket {
+            let (r, g, b) = colors[idx];
+            r_sum += r as u32;
+            g_sum += g as u32;
+            b_sum += b as u32;
+        }
+
+        let n = bucket.len() as u32;
+        palette.push(((r_sum / n) as u8, (g_sum / n) as u8, (b_sum / n) as u8));
+
+        for &idx in bucket {
+            indices[idx] = bucket_index as u8;
+        }
+    }
+
+    (palette, indices)
+}
+
+/// The channel (0=R, 1=G, 2=B) with the largest max-min range across
+/// `bucket`, plus that range
+fn widest_channel(bucket: &[usize], colors: &[(u8, u8, u8)]) -> (u8, u32) {
+    let (mut r_min, mut r_max) = (u8::MAX, 0u8);
+    let (mut g_min, mut g_max) = (u8::MAX, 0u8);
+    let (mut b_min, mut b_max) = (u8::MAX, 0u8);
+
+    for &idx in bucket {
+        let (r, g, b) = colors[idx];
+        r_min = r_min.min(r);
+        r_max = r_max.max(r);
+        g_min = g_min.min(g);
+        g_max = g_max.max(g);
+        b_min = b_min.min(b);
+        b_max = b_max.max(b);
+    }
+
+    [
+        (0u8, (r_max - r_min) as u32),
+        (1u8, (g_max - g_min) as u32),
+        (2u8, (b_max - b_min) as u32),
+    ]
+    .into_iter()
+    .max_by_key(|(_, range)| *range)
+    .expect("fixed 3-element array always has a max")
+}
+
+fn channel_value(color: (u8, u8, u8), channel: u8) -> u8 {
+    match channel {
+        0 => color.0,
+        1 => color.1,
+        _ => color.2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,4 +517,51 @@ mod tests {
         assert_eq!(colors, decoded);
         assert!(encoded.len() < colors.len()); // Should compress
     }
+
+    #[test]
+    fn test_median_cut_respects_max_colors() {
+        let colors: Vec<(u8, u8, u8)> = (0..=255u8).map(|v| (v, 255 - v, v / 2)).collect();
+        let (palette, indices) = median_cut_quantize(&colors, 16);
+
+        assert!(palette.len() <= 16);
+        assert_eq!(indices.len(), colors.len());
+        assert!(indices.iter().all(|&idx| (idx as usize) < palette.len()));
+    }
+
+    #[test]
+    fn test_median_cut_stops_when_fewer_unique_colors_than_k() {
+        let colors = vec![(10, 20, 30); 50];
+        let (palette, _) = median_cut_quantize(&colors, 8);
+
+        // A single unique color can't be split at all
+        assert_eq!(palette.len(), 1);
+    }
+
+    #[test]
+    fn test_palette_roundtrip() {
+        let frame = EfficientRgbFrame {
+            chars: "ABCD".repeat(10),
+            colors: (0..40)
+                .flat_map(|i| {
+                    let v = (i * 6) as u8;
+                    [v, 255 - v, v / 2]
+                })
+                .collect(),
+            width: 8,
+            height: 5,
+        };
+
+        let encoded = frame.encode_palette(16).unwrap();
+        let decoded = EfficientRgbFrame::decode_palette(&encoded).unwrap();
+
+        assert_eq!(decoded.chars, frame.chars);
+        assert_eq!(decoded.width, frame.width);
+        assert_eq!(decoded.height, frame.height);
+        assert_eq!(decoded.colors.len(), frame.colors.len());
+
+        // This turns 3 bytes/char into ~1 byte/char plus a small palette:
+        // everything after the header and chars section is the color data
+        let color_section_len = encoded.len() - (8 + frame.chars.len());
+        assert!(color_section_len < frame.colors.len());
+    }
 }
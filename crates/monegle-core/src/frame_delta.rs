@@ -0,0 +1,150 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Store a full keyframe every this many frames, so the chain never has to
+/// replay more than this many edits to reconstruct a frame and a single
+/// dropped batch can't corrupt everything after it
+pub const DEFAULT_KEYFRAME_INTERVAL: usize = 30;
+
+/// Zstd compression level used for the wrapped delta stream
+const ZSTD_LEVEL: i32 = 3;
+
+/// A single edit that reconstructs a frame from its predecessor: replace
+/// everything from `offset` onward with `replacement`.
+///
+/// Built from nothing but slice comparisons (longest-common-prefix), so this
+/// type and [`diff_frames`]/[`apply_edits`] have no dependency on `std`
+/// beyond `alloc`'s `Vec` - the same core can run on a constrained sender
+/// that only has `no_std` + `alloc`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameEdit {
+    pub offset: usize,
+    pub replacement: Vec<u8>,
+}
+
+/// A single frame in a delta-encoded stream: either a full keyframe, or a
+/// set of edits against the frame immediately before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum FrameRecord {
+    Keyframe(Vec<u8>),
+    Delta(Vec<FrameEdit>),
+}
+
+/// Diff `current` against `previous` as a list of edits. Only ever produces
+/// zero edits (identical frames) or one edit (the longest common prefix
+/// followed by everything that changed) - a list rather than a single
+/// `FrameEdit` so future callers can append more granular edits without
+/// changing the wire format.
+pub fn diff_frames(previous: &[u8], current: &[u8]) -> Vec<FrameEdit> {
+    let prefix_len = previous
+        .iter()
+        .zip(current.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    if prefix_len == previous.len() && prefix_len == current.len() {
+        return Vec::new();
+    }
+
+    vec![FrameEdit {
+        offset: prefix_len,
+        replacement: current[prefix_len..].to_vec(),
+    }]
+}
+
+/// Reconstruct a frame by replaying `edits` against `previous`
+pub fn apply_edits(previous: &[u8], edits: &[FrameEdit]) -> Vec<u8> {
+    let mut result = previous.to_vec();
+
+    for edit in edits {
+        result.truncate(edit.offset);
+        result.extend_from_slice(&edit.replacement);
+    }
+
+    result
+}
+
+/// Delta-encode a sequence of frames (storing a full keyframe every
+/// `keyframe_interval` frames, and edits against the previous frame
+/// otherwise) and zstd-compress the resulting stream.
+pub fn encode_batch_frames(frames: &[String], keyframe_interval: usize) -> Result<Vec<u8>> {
+    let keyframe_interval = keyframe_interval.max(1);
+    let mut records = Vec::with_capacity(frames.len());
+    let mut previous: Option<&[u8]> = None;
+
+    for (i, frame) in frames.iter().enumerate() {
+        let bytes = frame.as_bytes();
+
+        let record = match previous {
+            Some(prev) if i % keyframe_interval != 0 => FrameRecord::Delta(diff_frames(prev, bytes)),
+            _ => FrameRecord::Keyframe(bytes.to_vec()),
+        };
+
+        records.push(record);
+        previous = Some(bytes);
+    }
+
+    let serialized = bincode::serialize(&records)
+        .map_err(|e| anyhow!("Failed to serialize delta-encoded batch: {}", e))?;
+
+    zstd::stream::encode_all(&serialized[..], ZSTD_LEVEL)
+        .map_err(|e| anyhow!("Failed to zstd-compress delta-encoded batch: {}", e))
+}
+
+/// Inverse of [`encode_batch_frames`]: decompress and replay deltas to
+/// recover the original frames in order
+pub fn decode_batch_frames(compressed: &[u8]) -> Result<Vec<String>> {
+    let serialized =
+        zstd::stream::decode_all(compressed).map_err(|e| anyhow!("Failed to zstd-decompress batch: {}", e))?;
+
+    let records: Vec<FrameRecord> =
+        bincode::deserialize(&serialized).map_err(|e| anyhow!("Failed to deserialize delta-encoded batch: {}", e))?;
+
+    let mut frames = Vec::with_capacity(records.len());
+    let mut previous: Option<Vec<u8>> = None;
+
+    for record in records {
+        let bytes = match record {
+            FrameRecord::Keyframe(bytes) => bytes,
+            FrameRecord::Delta(edits) => {
+                let prev = previous
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("Delta frame with no preceding keyframe"))?;
+                apply_edits(prev, &edits)
+            }
+        };
+
+        let frame = String::from_utf8(bytes.clone()).map_err(|e| anyhow!("UTF-8 decode error: {}", e))?;
+        frames.push(frame);
+        previous = Some(bytes);
+    }
+
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_identical_frames_is_empty() {
+        let edits = diff_frames(b"hello world", b"hello world");
+        assert!(edits.is_empty());
+    }
+
+    #[test]
+    fn test_diff_and_apply_roundtrip() {
+        let previous = b"frame 01: ....";
+        let current = b"frame 02: ####";
+        let edits = diff_frames(previous, current);
+        assert_eq!(apply_edits(previous, &edits), current);
+    }
+
+    #[test]
+    fn test_encode_decode_batch_roundtrip() {
+        let frames: Vec<String> = (0..10).map(|i| format!("frame {:03}: {}", i, "x".repeat(i))).collect();
+        let compressed = encode_batch_frames(&frames, 4).unwrap();
+        let decoded = decode_batch_frames(&compressed).unwrap();
+        assert_eq!(decoded, frames);
+    }
+}
@@ -0,0 +1,323 @@
+use std::fmt::Write as _;
+
+/// A single character cell in a `TerminalGrid`: the glyph plus its optional
+/// truecolor foreground, matching the `\x1b[38;2;r;g;bm ... \x1b[0m` runs
+/// `ColorMode::Rgb` emits (see `types::ColorMode::colorize`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Option<(u8, u8, u8)>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self { ch: ' ', color: None }
+    }
+}
+
+/// A `width x height` snapshot of a rendered ASCII frame, cell by cell.
+/// Parsing a frame into a grid and diffing it against the previous one is
+/// what lets the batcher submit only the cells that actually changed
+/// instead of the whole screen every frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalGrid {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+}
+
+impl TerminalGrid {
+    /// An all-blank grid, used as the "previous" state before any keyframe
+    /// has been seen
+    pub fn blank(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// The cell at `(row, col)`, or `None` if out of bounds
+    pub fn cell(&self, row: usize, col: usize) -> Option<Cell> {
+        self.index(row, col).map(|i| self.cells[i])
+    }
+
+    fn index(&self, row: usize, col: usize) -> Option<usize> {
+        if row < self.height as usize && col < self.width as usize {
+            Some(row * self.width as usize + col)
+        } else {
+            None
+        }
+    }
+
+    /// Parse a full ASCII frame (as produced by `AsciiConverter`/
+    /// `generate_counter_frame`) into a cell grid, by scanning
+    /// `\x1b[38;2;r;g;bm` truecolor runs terminated by `\x1b[0m` and
+    /// treating `\n` as a row break. Any other escape sequence is skipped
+    /// without consuming a cell.
+    pub fn parse(frame: &str, width: u16, height: u16) -> Self {
+        let mut grid = Self::blank(width, height);
+        let mut row = 0usize;
+        let mut col = 0usize;
+        let mut color: Option<(u8, u8, u8)> = None;
+
+        let chars: Vec<char> = frame.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            if c == '\n' {
+                row += 1;
+                col = 0;
+                i += 1;
+                continue;
+            }
+
+            if c == '\x1b' && chars.get(i + 1) == Some(&'[') {
+                if let Some((rgb, consumed)) = parse_rgb_escape(&chars[i..]) {
+                    color = rgb;
+                    i += consumed;
+                    continue;
+                }
+
+                if let Some(consumed) = parse_reset_escape(&chars[i..]) {
+                    color = None;
+                    i += consumed;
+                    continue;
+                }
+
+                // Unrecognized escape: skip just the introducer so we don't
+                // spend it as a visible cell
+                i += 1;
+                continue;
+            }
+
+            if let Some(idx) = grid.index(row, col) {
+                grid.cells[idx] = Cell { ch: c, color };
+            }
+            col += 1;
+            i += 1;
+        }
+
+        grid
+    }
+
+    /// Render the grid back into the same ANSI text `parse` reads, one row
+    /// per line, colored cells wrapped in truecolor escapes
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(self.cells.len() * 2);
+
+        for row in 0..self.height as usize {
+            for col in 0..self.width as usize {
+                let cell = self.cells[row * self.width as usize + col];
+                match cell.color {
+                    Some((r, g, b)) => {
+                        let _ = write!(out, "\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, cell.ch);
+                    }
+                    None => out.push(cell.ch),
+                }
+            }
+            if row + 1 < self.height as usize {
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+
+    /// Diff this grid against `previous`, returning cursor-positioned
+    /// escape sequences for only the cells that changed: `\x1b[{row};{col}H`
+    /// (1-indexed, matching terminal cursor addressing) followed by the
+    /// cell's styled character. Empty if nothing changed. Grids of
+    /// mismatched dimensions are treated as entirely changed.
+    pub fn diff(&self, previous: &TerminalGrid) -> String {
+        let mut out = String::new();
+        let same_dims = self.width == previous.width && self.height == previous.height;
+
+        for row in 0..self.height as usize {
+            for col in 0..self.width as usize {
+                let idx = row * self.width as usize + col;
+                let cell = self.cells[idx];
+                let unchanged = same_dims && previous.cells.get(idx) == Some(&cell);
+
+                if unchanged {
+                    continue;
+                }
+
+                let _ = write!(out, "\x1b[{};{}H", row + 1, col + 1);
+                match cell.color {
+                    Some((r, g, b)) => {
+                        let _ = write!(out, "\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, cell.ch);
+                    }
+                    None => out.push(cell.ch),
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Apply a diff produced by `diff` onto this grid in place, moving a
+    /// virtual cursor on each `\x1b[{row};{col}H` and writing the styled
+    /// character that follows it
+    pub fn apply_diff(&mut self, diff: &str) {
+        let chars: Vec<char> = diff.chars().collect();
+        let mut i = 0;
+        let mut cursor: Option<(usize, usize)> = None;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            if c == '\x1b' && chars.get(i + 1) == Some(&'[') {
+                if let Some((row, col, consumed)) = parse_cursor_escape(&chars[i..]) {
+                    cursor = Some((row, col));
+                    i += consumed;
+                    continue;
+                }
+
+                if let Some((rgb, consumed)) = parse_rgb_escape(&chars[i..]) {
+                    if let Some((row, col)) = cursor {
+                        if let (Some(idx), Some(&ch)) = (self.index(row, col), chars.get(i + consumed)) {
+                            self.cells[idx] = Cell { ch, color: rgb };
+                            cursor = Some((row, col + 1));
+                        }
+                        i += consumed + 1;
+                        continue;
+                    }
+                }
+
+                if let Some(consumed) = parse_reset_escape(&chars[i..]) {
+                    i += consumed;
+                    continue;
+                }
+
+                i += 1;
+                continue;
+            }
+
+            if let Some((row, col)) = cursor {
+                if let Some(idx) = self.index(row, col) {
+                    self.cells[idx] = Cell { ch: c, color: None };
+                }
+                cursor = Some((row, col + 1));
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Parse a `\x1b[38;2;r;g;bm` truecolor-foreground escape at the start of
+/// `chars`, returning the color and how many `char`s it consumed
+fn parse_rgb_escape(chars: &[char]) -> Option<(Option<(u8, u8, u8)>, usize)> {
+    let rest: String = chars.iter().take_while(|&&c| c != 'm').collect();
+    let prefix = "\x1b[38;2;";
+    if !rest.starts_with(prefix) {
+        return None;
+    }
+
+    let body = &rest[prefix.len()..];
+    let parts: Vec<&str> = body.split(';').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let r = parts[0].parse::<u8>().ok()?;
+    let g = parts[1].parse::<u8>().ok()?;
+    let b = parts[2].parse::<u8>().ok()?;
+
+    // +1 for the closing 'm'
+    Some((Some((r, g, b)), rest.chars().count() + 1))
+}
+
+/// Parse a `\x1b[0m` style reset, returning how many `char`s it consumed
+fn parse_reset_escape(chars: &[char]) -> Option<usize> {
+    let reset: Vec<char> = "\x1b[0m".chars().collect();
+    if chars.len() >= reset.len() && chars[..reset.len()] == reset[..] {
+        Some(reset.len())
+    } else {
+        None
+    }
+}
+
+/// Parse a `\x1b[{row};{col}H` cursor-position escape, returning the
+/// 0-indexed row/col and how many `char`s it consumed
+fn parse_cursor_escape(chars: &[char]) -> Option<(usize, usize, usize)> {
+    if chars.first() != Some(&'\x1b') || chars.get(1) != Some(&'[') {
+        return None;
+    }
+
+    let body: String = chars[2..].iter().take_while(|&&c| c != 'H').collect();
+    if body.len() + 3 > chars.len() {
+        // No closing 'H' found within bounds
+        return None;
+    }
+
+    let parts: Vec<&str> = body.split(';').collect();
+    if parts.len() != 2 {
+        return None;
+    }
+
+    let row = parts[0].parse::<usize>().ok()?.checked_sub(1)?;
+    let col = parts[1].parse::<usize>().ok()?.checked_sub(1)?;
+
+    // 2 for "\x1b[", body, 1 for the closing 'H'
+    Some((row, col, 2 + body.chars().count() + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_render_roundtrip_monochrome() {
+        let frame = "ab\ncd";
+        let grid = TerminalGrid::parse(frame, 2, 2);
+        assert_eq!(grid.render(), frame);
+    }
+
+    #[test]
+    fn test_parse_and_render_roundtrip_colored() {
+        let frame = "\x1b[38;2;10;20;30mX\x1b[0m \n  ";
+        let grid = TerminalGrid::parse(frame, 2, 2);
+        assert_eq!(grid.render(), frame);
+    }
+
+    #[test]
+    fn test_diff_of_identical_grids_is_empty() {
+        let grid = TerminalGrid::parse("ab\ncd", 2, 2);
+        assert!(grid.diff(&grid).is_empty());
+    }
+
+    #[test]
+    fn test_diff_and_apply_roundtrip() {
+        let previous = TerminalGrid::parse("ab\ncd", 2, 2);
+        let current = TerminalGrid::parse("ab\nXd", 2, 2);
+
+        let diff = current.diff(&previous);
+        assert!(!diff.is_empty());
+
+        let mut reconstructed = previous.clone();
+        reconstructed.apply_diff(&diff);
+        assert_eq!(reconstructed, current);
+    }
+
+    #[test]
+    fn test_diff_against_blank_is_full_keyframe_equivalent() {
+        let blank = TerminalGrid::blank(2, 2);
+        let current = TerminalGrid::parse("\x1b[38;2;1;2;3mX\x1b[0my\nzw", 2, 2);
+
+        let diff = current.diff(&blank);
+        let mut reconstructed = blank;
+        reconstructed.apply_diff(&diff);
+        assert_eq!(reconstructed, current);
+    }
+}
@@ -3,9 +3,31 @@ pub mod codec;
 pub mod ascii;
 pub mod config;
 pub mod synthetic;
+pub mod transport;
+pub mod dict_codec;
+pub mod frame_delta;
+pub mod dashboard;
+pub mod grid;
+pub mod efficient_rgb;
+pub mod motion_delta;
+pub mod zerocopy;
+pub mod lz4_stream;
+pub mod varint;
+pub mod container;
 
 pub use types::*;
 pub use codec::*;
 pub use ascii::*;
 pub use config::*;
 pub use synthetic::*;
+pub use transport::*;
+pub use dict_codec::*;
+pub use frame_delta::*;
+pub use dashboard::*;
+pub use grid::*;
+pub use efficient_rgb::*;
+pub use motion_delta::*;
+pub use zerocopy::*;
+pub use lz4_stream::*;
+pub use varint::*;
+pub use container::*;
@@ -0,0 +1,214 @@
+use anyhow::{anyhow, Result};
+use std::os::raw::c_char;
+
+/// Safety cap on a decompressed frame's size, mirroring
+/// `crate::dict_codec::MAX_DECOMPRESSED_FRAME_BYTES` - the streaming decoder
+/// needs a capacity hint up front too, just carried per-frame instead of
+/// fixed.
+const MAX_DECOMPRESSED_FRAME_BYTES: usize = 8 * 1024 * 1024;
+
+/// `acceleration` passed to `LZ4_compress_fast_continue`; 1 is the library's
+/// own default trade-off between ratio and speed
+const LZ4_ACCELERATION: i32 = 1;
+
+/// Length of the `uncompressed_len` prefix each encoded frame carries, in
+/// the same "prepend the size" spirit as `lz4_flex::compress_prepend_size`
+/// (see `crate::codec::Lz4Codec`) - the streaming decoder needs the
+/// original length up front to size its output buffer before calling
+/// `LZ4_decompress_safe_continue`.
+const LEN_PREFIX_BYTES: usize = 4;
+
+/// Sender-side half of streaming LZ4 compression with a carried
+/// inter-frame dictionary - the lz4_sys `LZ4_compress_fast_continue`
+/// pattern, Kafka/Fluvio-style but block-at-a-time instead of trained up
+/// front (see `crate::dict_codec::ZstdDictEncoder` for that variant).
+///
+/// Every frame is compressed against LZ4's own ring-buffer window of
+/// everything emitted so far on this stream, so the keyframe that starts a
+/// fresh window doubles as the dictionary primer for the deltas that
+/// follow - no separate dictionary ever needs to be trained or shipped.
+/// `FrameBatcher` owns one of these per stream and calls `reset` at every
+/// keyframe boundary so a receiver resyncing after a gap is never asked to
+/// decode against window contents it never saw.
+pub struct Lz4StreamEncoder {
+    stream: *mut lz4_sys::LZ4Stream,
+}
+
+// The raw `LZ4Stream*` is only ever touched through `&mut self`, so it's
+// safe to move the encoder (and the pointer with it) across threads.
+unsafe impl Send for Lz4StreamEncoder {}
+
+impl Lz4StreamEncoder {
+    pub fn new() -> Self {
+        Self {
+            stream: unsafe { lz4_sys::LZ4_createStream() },
+        }
+    }
+
+    /// Drop the carried window and start fresh - called on every keyframe
+    /// boundary (`frame_counter % keyframe_interval == 0`)
+    pub fn reset(&mut self) {
+        unsafe { lz4_sys::LZ4_resetStream(self.stream) };
+    }
+
+    /// Compress `current` against the window accumulated so far, then
+    /// extend that window with `current`'s bytes. Returns the
+    /// `uncompressed_len` prefix followed by the compressed bytes.
+    pub fn encode(&mut self, current: &str) -> Result<Vec<u8>> {
+        let src = current.as_bytes();
+        let bound = unsafe { lz4_sys::LZ4_compressBound(src.len() as i32) };
+        let mut compressed = vec![0u8; bound.max(16) as usize];
+
+        let written = unsafe {
+            lz4_sys::LZ4_compress_fast_continue(
+                self.stream,
+                src.as_ptr() as *const c_char,
+                compressed.as_mut_ptr() as *mut c_char,
+                src.len() as i32,
+                compressed.len() as i32,
+                LZ4_ACCELERATION,
+            )
+        };
+
+        if written <= 0 {
+            return Err(anyhow!("LZ4 stream compress failed"));
+        }
+        compressed.truncate(written as usize);
+
+        let mut out = Vec::with_capacity(LEN_PREFIX_BYTES + compressed.len());
+        out.extend_from_slice(&(src.len() as u32).to_le_bytes());
+        out.extend_from_slice(&compressed);
+        Ok(out)
+    }
+}
+
+impl Default for Lz4StreamEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Lz4StreamEncoder {
+    fn drop(&mut self) {
+        unsafe { lz4_sys::LZ4_freeStream(self.stream) };
+    }
+}
+
+/// Receiver-side half of streaming LZ4 decompression
+/// (`LZ4_decompress_safe_continue`). Mirrors the encoder's window one
+/// frame at a time, so frames must be replayed strictly in order; `reset`
+/// must be called at the same keyframe boundaries as the encoder's or the
+/// mirrored window desyncs and decoding fails.
+pub struct Lz4StreamDecoder {
+    stream: *mut lz4_sys::LZ4StreamDecode,
+}
+
+unsafe impl Send for Lz4StreamDecoder {}
+
+impl Lz4StreamDecoder {
+    pub fn new() -> Self {
+        Self {
+            stream: unsafe { lz4_sys::LZ4_createStreamDecode() },
+        }
+    }
+
+    /// Clear the mirrored window - called on every keyframe boundary
+    pub fn reset(&mut self) {
+        unsafe { lz4_sys::LZ4_setStreamDecode(self.stream, std::ptr::null(), 0) };
+    }
+
+    /// Decode a frame produced by `Lz4StreamEncoder::encode`: reads the
+    /// `uncompressed_len` prefix to size the output buffer, then
+    /// decompresses against the mirrored window.
+    pub fn decode(&mut self, data: &[u8]) -> Result<String> {
+        if data.len() < LEN_PREFIX_BYTES {
+            return Err(anyhow!("Lz4Stream decode error: data too short"));
+        }
+
+        let uncompressed_len =
+            u32::from_le_bytes(data[..LEN_PREFIX_BYTES].try_into().unwrap()) as usize;
+        if uncompressed_len > MAX_DECOMPRESSED_FRAME_BYTES {
+            return Err(anyhow!(
+                "Lz4Stream frame claims {} bytes, exceeding the {} byte safety cap",
+                uncompressed_len,
+                MAX_DECOMPRESSED_FRAME_BYTES
+            ));
+        }
+
+        let src = &data[LEN_PREFIX_BYTES..];
+        let mut dst = vec![0u8; uncompressed_len];
+
+        let written = unsafe {
+            lz4_sys::LZ4_decompress_safe_continue(
+                self.stream,
+                src.as_ptr() as *const c_char,
+                dst.as_mut_ptr() as *mut c_char,
+                src.len() as i32,
+                dst.len() as i32,
+            )
+        };
+
+        if written < 0 || written as usize != dst.len() {
+            return Err(anyhow!("LZ4 stream decompress failed"));
+        }
+
+        String::from_utf8(dst).map_err(|e| anyhow!("UTF-8 decode error: {}", e))
+    }
+}
+
+impl Default for Lz4StreamDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Lz4StreamDecoder {
+    fn drop(&mut self) {
+        unsafe { lz4_sys::LZ4_freeStreamDecode(self.stream) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terminal_like_frame(n: usize) -> String {
+        format!("\x1b[2J\x1b[H+--------+\n| frame {:02} |\n+--------+\n", n % 100)
+    }
+
+    #[test]
+    fn test_stream_roundtrip() {
+        let mut encoder = Lz4StreamEncoder::new();
+        let mut decoder = Lz4StreamDecoder::new();
+
+        for i in 0..20 {
+            let frame = terminal_like_frame(i);
+            let encoded = encoder.encode(&frame).unwrap();
+            let decoded = decoder.decode(&encoded).unwrap();
+            assert_eq!(decoded, frame);
+        }
+    }
+
+    #[test]
+    fn test_reset_restarts_the_window_on_both_sides() {
+        let mut encoder = Lz4StreamEncoder::new();
+        let mut decoder = Lz4StreamDecoder::new();
+
+        let keyframe = terminal_like_frame(0);
+        let encoded = encoder.encode(&keyframe).unwrap();
+        assert_eq!(decoder.decode(&encoded).unwrap(), keyframe);
+
+        encoder.reset();
+        decoder.reset();
+
+        let next_keyframe = terminal_like_frame(1);
+        let encoded = encoder.encode(&next_keyframe).unwrap();
+        assert_eq!(decoder.decode(&encoded).unwrap(), next_keyframe);
+    }
+
+    #[test]
+    fn test_short_data_is_rejected() {
+        let mut decoder = Lz4StreamDecoder::new();
+        assert!(decoder.decode(&[0, 1]).is_err());
+    }
+}
@@ -0,0 +1,552 @@
+use anyhow::{anyhow, Result};
+
+use crate::{CompressionType, FrameEncoder, RleEncoder};
+
+/// Block edge length (in character cells). 8x8 balances motion-search cost
+/// against how finely a pan or small camera shake can be tracked.
+pub const BLOCK_SIZE: usize = 8;
+
+/// How far (in cells, each direction) the motion search looks in the
+/// previous frame for the offset that best matches a block
+pub const SEARCH_RADIUS: i32 = 4;
+
+/// A block only keeps its best motion vector if the shifted block's
+/// Hamming distance is under this fraction of the block's cell count;
+/// otherwise a residual list would cost more than just storing the block
+/// verbatim (intra), so it falls back to that instead
+const MOTION_THRESHOLD_FRACTION: f32 = 0.5;
+
+/// One block's encoding: either copied verbatim (no shift in the previous
+/// frame beat the threshold) or reconstructed by copying a `(dx, dy)`-
+/// shifted region of the previous frame and patching the cells that still
+/// differ after the shift
+#[derive(Debug, Clone)]
+enum BlockRecord {
+    Intra { chars: Vec<char> },
+    Motion {
+        dx: i32,
+        dy: i32,
+        /// `(local offset within the block, resulting char)` for cells
+        /// that differ from the shifted previous block
+        residuals: Vec<(u16, char)>,
+    },
+}
+
+/// A motion-compensated encoding of one frame against its predecessor:
+/// the `width x height` character grid divided into fixed `BLOCK_SIZE`
+/// blocks, each independently motion-estimated. Kept as a struct (rather
+/// than just encoded bytes) so a color stream's `EfficientRgbFrame` plane
+/// can reuse the exact same per-block decisions instead of re-running
+/// motion search on color data.
+#[derive(Debug, Clone)]
+pub struct MotionFrame {
+    width: usize,
+    height: usize,
+    blocks_wide: usize,
+    blocks_high: usize,
+    blocks: Vec<BlockRecord>,
+}
+
+/// Split a frame's text into a rectangular `width x height` char grid,
+/// padding short rows with spaces so every row has equal length - motion
+/// search operates on a dense grid, not ragged rows
+fn to_grid(text: &str, width: usize, height: usize) -> Vec<char> {
+    let rows: Vec<&str> = text.split('\n').collect();
+    let mut grid = vec![' '; width * height];
+
+    for (row, &row_text) in rows.iter().take(height).enumerate() {
+        for (col, ch) in row_text.chars().take(width).enumerate() {
+            grid[row * width + col] = ch;
+        }
+    }
+
+    grid
+}
+
+fn from_grid(grid: &[char], width: usize, height: usize) -> String {
+    (0..height)
+        .map(|row| grid[row * width..row * width + width].iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Frame dimensions inferred from the text itself: height is the row
+/// count, width is the longest row (shorter rows are treated as
+/// space-padded)
+fn infer_dimensions(text: &str) -> (usize, usize) {
+    let rows: Vec<&str> = text.split('\n').collect();
+    let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+    (width, rows.len())
+}
+
+impl MotionFrame {
+    /// Motion-estimate `current` against `previous`, both already split
+    /// into equal-sized `width x height` grids
+    fn estimate(current: &[char], previous: &[char], width: usize, height: usize) -> Self {
+        let blocks_wide = width.div_ceil(BLOCK_SIZE);
+        let blocks_high = height.div_ceil(BLOCK_SIZE);
+        let mut blocks = Vec::with_capacity(blocks_wide * blocks_high);
+
+        for block_row in 0..blocks_high {
+            for block_col in 0..blocks_wide {
+                let row0 = block_row * BLOCK_SIZE;
+                let col0 = block_col * BLOCK_SIZE;
+                let block_h = BLOCK_SIZE.min(height - row0);
+                let block_w = BLOCK_SIZE.min(width - col0);
+
+                blocks.push(Self::estimate_block(
+                    current, previous, width, height, row0, col0, block_w, block_h,
+                ));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            blocks_wide,
+            blocks_high,
+            blocks,
+        }
+    }
+
+    /// Search `±SEARCH_RADIUS` cells for the offset that minimizes the
+    /// Hamming distance against `current`'s block, preferring `(0, 0)` on
+    /// ties; shifted cells that fall outside the frame count as mismatches
+    #[allow(clippy::too_many_arguments)]
+    fn estimate_block(
+        current: &[char],
+        previous: &[char],
+        width: usize,
+        height: usize,
+        row0: usize,
+        col0: usize,
+        block_w: usize,
+        block_h: usize,
+    ) -> BlockRecord {
+        let block_cells = block_w * block_h;
+
+        let distance_at = |dy: i32, dx: i32| -> usize {
+            let mut mismatches = 0;
+            for r in 0..block_h {
+                for c in 0..block_w {
+                    let cur = current[(row0 + r) * width + (col0 + c)];
+                    let src_row = row0 as i32 + r as i32 + dy;
+                    let src_col = col0 as i32 + c as i32 + dx;
+                    let prev = if src_row >= 0
+                        && (src_row as usize) < height
+                        && src_col >= 0
+                        && (src_col as usize) < width
+                    {
+                        previous[src_row as usize * width + src_col as usize]
+                    } else {
+                        // Out of bounds counts as a mismatch - the shift
+                        // can't actually source this cell from `previous`
+                        '\0'
+                    };
+                    if cur != prev {
+                        mismatches += 1;
+                    }
+                }
+            }
+            mismatches
+        };
+
+        let mut best_dx = 0;
+        let mut best_dy = 0;
+        let mut best_distance = distance_at(0, 0);
+
+        for dy in -SEARCH_RADIUS..=SEARCH_RADIUS {
+            for dx in -SEARCH_RADIUS..=SEARCH_RADIUS {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let distance = distance_at(dy, dx);
+                if distance < best_distance {
+                    best_distance = distance;
+                    best_dx = dx;
+                    best_dy = dy;
+                }
+            }
+        }
+
+        let threshold = (block_cells as f32 * MOTION_THRESHOLD_FRACTION) as usize;
+        if best_distance > threshold {
+            let chars = (0..block_h)
+                .flat_map(|r| (0..block_w).map(move |c| (r, c)))
+                .map(|(r, c)| current[(row0 + r) * width + (col0 + c)])
+                .collect();
+            return BlockRecord::Intra { chars };
+        }
+
+        let mut residuals = Vec::with_capacity(best_distance);
+        for r in 0..block_h {
+            for c in 0..block_w {
+                let cur = current[(row0 + r) * width + (col0 + c)];
+                let src_row = row0 as i32 + r as i32 + best_dy;
+                let src_col = col0 as i32 + c as i32 + best_dx;
+                let prev = if src_row >= 0
+                    && (src_row as usize) < height
+                    && src_col >= 0
+                    && (src_col as usize) < width
+                {
+                    previous[src_row as usize * width + src_col as usize]
+                } else {
+                    '\0'
+                };
+                if cur != prev {
+                    residuals.push(((r * block_w + c) as u16, cur));
+                }
+            }
+        }
+
+        BlockRecord::Motion {
+            dx: best_dx,
+            dy: best_dy,
+            residuals,
+        }
+    }
+
+    /// Rebuild the `width x height` char grid from `previous` plus this
+    /// frame's block records
+    fn reconstruct(&self, previous: &[char]) -> Vec<char> {
+        let mut grid = vec![' '; self.width * self.height];
+
+        for (block_index, block) in self.blocks.iter().enumerate() {
+            let block_row = block_index / self.blocks_wide;
+            let block_col = block_index % self.blocks_wide;
+            let row0 = block_row * BLOCK_SIZE;
+            let col0 = block_col * BLOCK_SIZE;
+            let block_h = BLOCK_SIZE.min(self.height - row0);
+            let block_w = BLOCK_SIZE.min(self.width - col0);
+
+            match block {
+                BlockRecord::Intra { chars } => {
+                    for r in 0..block_h {
+                        for c in 0..block_w {
+                            grid[(row0 + r) * self.width + (col0 + c)] = chars[r * block_w + c];
+                        }
+                    }
+                }
+                BlockRecord::Motion { dx, dy, residuals } => {
+                    for r in 0..block_h {
+                        for c in 0..block_w {
+                            let src_row = row0 as i32 + r as i32 + dy;
+                            let src_col = col0 as i32 + c as i32 + dx;
+                            let shifted = if src_row >= 0
+                                && (src_row as usize) < self.height
+                                && src_col >= 0
+                                && (src_col as usize) < self.width
+                            {
+                                previous[src_row as usize * self.width + src_col as usize]
+                            } else {
+                                ' '
+                            };
+                            grid[(row0 + r) * self.width + (col0 + c)] = shifted;
+                        }
+                    }
+                    for &(local_offset, ch) in residuals {
+                        let r = local_offset as usize / block_w;
+                        let c = local_offset as usize % block_w;
+                        grid[(row0 + r) * self.width + (col0 + c)] = ch;
+                    }
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Serialize to the wire format `decode`/`Self::deserialize` expect:
+    /// width, height (u32 each), block count (u32), then per block a tag
+    /// byte (0 = intra, 1 = motion) followed by either the block's chars
+    /// (RLE-compressed) or `dx`, `dy` (i8 each) plus a residual list.
+    fn serialize(&self) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        encoded.extend_from_slice(&(self.width as u32).to_le_bytes());
+        encoded.extend_from_slice(&(self.height as u32).to_le_bytes());
+        encoded.extend_from_slice(&(self.blocks.len() as u32).to_le_bytes());
+
+        for block in &self.blocks {
+            match block {
+                BlockRecord::Intra { chars } => {
+                    encoded.push(0);
+                    let text: String = chars.iter().collect();
+                    let rle = RleEncoder.encode(&text, None).unwrap_or_default();
+                    encoded.extend_from_slice(&(rle.len() as u32).to_le_bytes());
+                    encoded.extend_from_slice(&rle);
+                }
+                BlockRecord::Motion { dx, dy, residuals } => {
+                    encoded.push(1);
+                    encoded.push(*dx as i8 as u8);
+                    encoded.push(*dy as i8 as u8);
+                    encoded.extend_from_slice(&(residuals.len() as u16).to_le_bytes());
+                    for &(offset, ch) in residuals {
+                        encoded.extend_from_slice(&offset.to_le_bytes());
+                        let mut buf = [0u8; 4];
+                        let bytes = ch.encode_utf8(&mut buf);
+                        encoded.push(bytes.len() as u8);
+                        encoded.extend_from_slice(bytes.as_bytes());
+                    }
+                }
+            }
+        }
+
+        encoded
+    }
+
+    /// Deserialize `data` produced by `serialize`
+    fn deserialize(data: &[u8]) -> Result<Self> {
+        if data.len() < 12 {
+            return Err(anyhow!("Motion decode error: header too short"));
+        }
+
+        let mut i = 0;
+        let width = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+        i += 4;
+        let height = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+        i += 4;
+        let block_count = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+        i += 4;
+
+        let blocks_wide = width.div_ceil(BLOCK_SIZE);
+        let blocks_high = height.div_ceil(BLOCK_SIZE);
+        let mut blocks = Vec::with_capacity(block_count);
+
+        for block_index in 0..block_count {
+            let block_row = block_index / blocks_wide.max(1);
+            let block_col = block_index % blocks_wide.max(1);
+            let row0 = block_row * BLOCK_SIZE;
+            let col0 = block_col * BLOCK_SIZE;
+            let block_h = BLOCK_SIZE.min(height.saturating_sub(row0));
+            let block_w = BLOCK_SIZE.min(width.saturating_sub(col0));
+
+            let tag = *data.get(i).ok_or_else(|| anyhow!("Motion decode error: missing block tag"))?;
+            i += 1;
+
+            match tag {
+                0 => {
+                    if data.len() < i + 4 {
+                        return Err(anyhow!("Motion decode error: missing intra block length"));
+                    }
+                    let len = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+                    i += 4;
+                    if data.len() < i + len {
+                        return Err(anyhow!("Motion decode error: incomplete intra block"));
+                    }
+                    let text = RleEncoder.decode(&data[i..i + len], None)?;
+                    i += len;
+                    let mut chars: Vec<char> = text.chars().collect();
+                    chars.resize(block_w * block_h, ' ');
+                    blocks.push(BlockRecord::Intra { chars });
+                }
+                1 => {
+                    if data.len() < i + 4 {
+                        return Err(anyhow!("Motion decode error: missing motion header"));
+                    }
+                    let dx = data[i] as i8 as i32;
+                    let dy = data[i + 1] as i8 as i32;
+                    let residual_count = u16::from_le_bytes([data[i + 2], data[i + 3]]) as usize;
+                    i += 4;
+
+                    let mut residuals = Vec::with_capacity(residual_count);
+                    for _ in 0..residual_count {
+                        if data.len() < i + 3 {
+                            return Err(anyhow!("Motion decode error: incomplete residual"));
+                        }
+                        let offset = u16::from_le_bytes([data[i], data[i + 1]]);
+                        let char_len = data[i + 2] as usize;
+                        i += 3;
+                        if data.len() < i + char_len {
+                            return Err(anyhow!("Motion decode error: incomplete residual char"));
+                        }
+                        let ch = std::str::from_utf8(&data[i..i + char_len])?
+                            .chars()
+                            .next()
+                            .ok_or_else(|| anyhow!("Motion decode error: empty residual char"))?;
+                        i += char_len;
+                        residuals.push((offset, ch));
+                    }
+
+                    blocks.push(BlockRecord::Motion { dx, dy, residuals });
+                }
+                other => return Err(anyhow!("Motion decode error: unknown block tag {}", other)),
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            blocks_wide,
+            blocks_high,
+            blocks,
+        })
+    }
+
+    /// Apply this frame's block motion vectors/residual positions to a
+    /// color plane (one RGB triplet per character cell, row-major, same
+    /// `width x height` as the char grid): each block is reconstructed by
+    /// shifting `previous_colors` the same `(dx, dy)` used for the
+    /// characters, then patched with `current_colors` at exactly the
+    /// residual cells the character pass already identified (an intra
+    /// block copies `current_colors` verbatim). This reuses the motion
+    /// search already done on the character grid instead of running a
+    /// second search purely on color data.
+    pub fn apply_to_color_plane(&self, current_colors: &[u8], previous_colors: &[u8]) -> Vec<u8> {
+        let mut colors = vec![0u8; self.width * self.height * 3];
+
+        for (block_index, block) in self.blocks.iter().enumerate() {
+            let block_row = block_index / self.blocks_wide;
+            let block_col = block_index % self.blocks_wide;
+            let row0 = block_row * BLOCK_SIZE;
+            let col0 = block_col * BLOCK_SIZE;
+            let block_h = BLOCK_SIZE.min(self.height - row0);
+            let block_w = BLOCK_SIZE.min(self.width - col0);
+
+            let cell_idx = |r: usize, c: usize| (row0 + r) * self.width + (col0 + c);
+
+            match block {
+                BlockRecord::Intra { .. } => {
+                    for r in 0..block_h {
+                        for c in 0..block_w {
+                            let idx = cell_idx(r, c) * 3;
+                            colors[idx..idx + 3].copy_from_slice(&current_colors[idx..idx + 3]);
+                        }
+                    }
+                }
+                BlockRecord::Motion { dx, dy, residuals } => {
+                    for r in 0..block_h {
+                        for c in 0..block_w {
+                            let src_row = row0 as i32 + r as i32 + dy;
+                            let src_col = col0 as i32 + c as i32 + dx;
+                            let idx = cell_idx(r, c) * 3;
+                            if src_row >= 0
+                                && (src_row as usize) < self.height
+                                && src_col >= 0
+                                && (src_col as usize) < self.width
+                            {
+                                let src_idx = (src_row as usize * self.width + src_col as usize) * 3;
+                                colors[idx..idx + 3].copy_from_slice(&previous_colors[src_idx..src_idx + 3]);
+                            }
+                        }
+                    }
+                    for &(local_offset, _) in residuals {
+                        let r = local_offset as usize / block_w;
+                        let c = local_offset as usize % block_w;
+                        let idx = cell_idx(r, c) * 3;
+                        colors[idx..idx + 3].copy_from_slice(&current_colors[idx..idx + 3]);
+                    }
+                }
+            }
+        }
+
+        colors
+    }
+}
+
+/// Motion-compensated inter-frame coder: divides the frame into
+/// `BLOCK_SIZE` blocks and encodes each as either a motion vector plus a
+/// small residual, or verbatim if no shift in the previous frame beats
+/// `MOTION_THRESHOLD_FRACTION`. Keyframes (no `previous`) fall back to
+/// plain RLE, same as `SpanDeltaEncoder`. Panning or small camera shake -
+/// which a position-for-position delta sees as an almost-entirely-changed
+/// frame - instead costs one motion vector per block plus a handful of
+/// residual cells.
+pub struct MotionEncoder;
+
+impl FrameEncoder for MotionEncoder {
+    fn encode(&self, current: &str, previous: Option<&str>) -> Result<Vec<u8>> {
+        let Some(previous) = previous else {
+            return RleEncoder.encode(current, None);
+        };
+
+        let (width, height) = infer_dimensions(current);
+        let current_grid = to_grid(current, width, height);
+        let previous_grid = to_grid(previous, width, height);
+
+        let motion = MotionFrame::estimate(&current_grid, &previous_grid, width, height);
+        Ok(motion.serialize())
+    }
+
+    fn decode(&self, data: &[u8], previous: Option<&str>) -> Result<String> {
+        let Some(previous) = previous else {
+            return RleEncoder.decode(data, None);
+        };
+
+        let motion = MotionFrame::deserialize(data)?;
+        let previous_grid = to_grid(previous, motion.width, motion.height);
+        let current_grid = motion.reconstruct(&previous_grid);
+
+        Ok(from_grid(&current_grid, motion.width, motion.height))
+    }
+
+    fn compression_type(&self) -> CompressionType {
+        CompressionType::Motion
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_motion_roundtrip_on_panned_frame() {
+        let previous = "AAAABBBB\nAAAABBBB\nCCCCDDDD\nCCCCDDDD";
+        // Shifted one column to the right, with the vacated column filled in
+        let current = "XAAAABBB\nXAAAABBB\nXCCCCDDD\nXCCCCDDD";
+
+        let encoder = MotionEncoder;
+        let encoded = encoder.encode(current, Some(previous)).unwrap();
+        let decoded = encoder.decode(&encoded, Some(previous)).unwrap();
+
+        assert_eq!(decoded, current);
+    }
+
+    #[test]
+    fn test_motion_roundtrip_with_no_previous_is_keyframe() {
+        let encoder = MotionEncoder;
+        let current = "hello world";
+        let encoded = encoder.encode(current, None).unwrap();
+        let decoded = encoder.decode(&encoded, None).unwrap();
+        assert_eq!(decoded, current);
+    }
+
+    #[test]
+    fn test_motion_roundtrip_on_unrelated_frames_falls_back_to_intra() {
+        let previous = "aaaaaaaa\naaaaaaaa";
+        let current = "zzzzzzzz\nzzzzzzzz";
+
+        let encoder = MotionEncoder;
+        let encoded = encoder.encode(current, Some(previous)).unwrap();
+        let decoded = encoder.decode(&encoded, Some(previous)).unwrap();
+
+        assert_eq!(decoded, current);
+    }
+
+    #[test]
+    fn test_apply_to_color_plane_follows_character_motion() {
+        let previous = "AB\nCD";
+        let current = "XA\nXC"; // shifted one column right
+
+        let (width, height) = infer_dimensions(current);
+        let current_grid = to_grid(current, width, height);
+        let previous_grid = to_grid(previous, width, height);
+        let motion = MotionFrame::estimate(&current_grid, &previous_grid, width, height);
+
+        // Previous colors: A=red, B=green, C=blue, D=yellow
+        let previous_colors = vec![
+            255, 0, 0, 0, 255, 0, // A, B
+            0, 0, 255, 255, 255, 0, // C, D
+        ];
+        // Current colors: X=black (new), A keeps red, C keeps blue
+        let current_colors = vec![
+            0, 0, 0, 255, 0, 0, // X, A
+            0, 0, 0, 0, 0, 255, // X, C
+        ];
+
+        let colors = motion.apply_to_color_plane(&current_colors, &previous_colors);
+        assert_eq!(colors.len(), current_colors.len());
+        // A and C carry their previous colors one column right; the new X
+        // cells are residuals and take the current black
+        assert_eq!(colors, current_colors);
+    }
+}
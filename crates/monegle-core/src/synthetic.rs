@@ -72,15 +72,19 @@ impl SyntheticFrameGenerator {
             character_set: CharacterSet::Standard,
             color_mode: ColorMode::None,
             frames_per_batch: count as u8,
+            keyframe_interval: crate::frame_delta::DEFAULT_KEYFRAME_INTERVAL as u32,
         };
 
-        FrameBatch {
+        let mut batch = FrameBatch {
             stream_id,
             sequence,
             metadata,
             frames,
             timestamp,
-        }
+            crc: 0,
+        };
+        batch.crc = batch.compute_crc().expect("synthetic batch is always serializable");
+        batch
     }
 
     /// Generate a batch with mostly static content (high compression ratio)
@@ -117,15 +121,19 @@ impl SyntheticFrameGenerator {
             character_set: CharacterSet::Standard,
             color_mode: ColorMode::None,
             frames_per_batch: count as u8,
+            keyframe_interval: crate::frame_delta::DEFAULT_KEYFRAME_INTERVAL as u32,
         };
 
-        FrameBatch {
+        let mut batch = FrameBatch {
             stream_id,
             sequence,
             metadata,
             frames,
             timestamp,
-        }
+            crc: 0,
+        };
+        batch.crc = batch.compute_crc().expect("synthetic batch is always serializable");
+        batch
     }
 }
 
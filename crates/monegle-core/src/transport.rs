@@ -0,0 +1,166 @@
+use anyhow::{anyhow, Result};
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::FrameBatch;
+
+/// Magic bytes identifying a monegle frame-batch wire frame
+const MAGIC: [u8; 4] = *b"MNGL";
+
+/// Wire protocol version for the length-delimited frame header
+const PROTOCOL_VERSION: u8 = 1;
+
+/// Fixed header: magic (4) + version (1) + payload length (4)
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+
+/// `tokio_util` codec that frames `FrameBatch`es for any `AsyncRead`/`AsyncWrite`
+/// transport (a raw TCP socket, a WebSocket, a file, ...), independent of the
+/// blockchain calldata path.
+///
+/// Each frame on the wire is `[magic: 4][version: 1][len: u32 LE][payload]`,
+/// where the payload is `FrameBatch::encode_to_bytes`.
+/// The length prefix makes partial/truncated reads safe: `decode` returns
+/// `Ok(None)` until a full frame has arrived and simply waits for more bytes.
+#[derive(Debug, Default)]
+pub struct FrameBatchCodec {
+    /// Header parsed from the current frame, kept across `decode` calls
+    /// while we wait for the rest of the payload to arrive
+    header: Option<u32>,
+}
+
+impl FrameBatchCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Encoder<FrameBatch> for FrameBatchCodec {
+    type Error = anyhow::Error;
+
+    fn encode(&mut self, batch: FrameBatch, dst: &mut BytesMut) -> Result<()> {
+        let payload = batch.encode_to_bytes()?;
+        let payload_len: u32 = payload
+            .len()
+            .try_into()
+            .map_err(|_| anyhow!("frame batch too large to frame: {} bytes", payload.len()))?;
+
+        dst.reserve(HEADER_LEN + payload.len());
+        dst.put_slice(&MAGIC);
+        dst.put_u8(PROTOCOL_VERSION);
+        dst.put_u32_le(payload_len);
+        dst.put_slice(&payload);
+
+        Ok(())
+    }
+}
+
+impl Decoder for FrameBatchCodec {
+    type Item = FrameBatch;
+    type Error = anyhow::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<FrameBatch>> {
+        let payload_len = match self.header {
+            Some(len) => len,
+            None => {
+                if src.len() < HEADER_LEN {
+                    src.reserve(HEADER_LEN - src.len());
+                    return Ok(None);
+                }
+
+                if src[..MAGIC.len()] != MAGIC {
+                    return Err(anyhow!("bad frame batch magic bytes"));
+                }
+
+                let version = src[MAGIC.len()];
+                if version != PROTOCOL_VERSION {
+                    return Err(anyhow!("unsupported frame batch protocol version: {}", version));
+                }
+
+                let len_offset = MAGIC.len() + 1;
+                let len = u32::from_le_bytes(src[len_offset..HEADER_LEN].try_into().unwrap());
+
+                src.advance(HEADER_LEN);
+                self.header = Some(len);
+                len
+            }
+        };
+
+        let payload_len = payload_len as usize;
+        if src.len() < payload_len {
+            src.reserve(payload_len - src.len());
+            return Ok(None);
+        }
+
+        let payload = src.split_to(payload_len);
+        self.header = None;
+
+        let batch = FrameBatch::decode_from_bytes(&payload)
+            .map_err(|e| anyhow!("failed to decode framed batch: {}", e))?;
+
+        Ok(Some(batch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharacterSet, ColorMode, CompressionType, StreamMetadata};
+
+    fn sample_batch() -> FrameBatch {
+        FrameBatch {
+            stream_id: [7u8; 32],
+            sequence: 42,
+            metadata: StreamMetadata {
+                fps: 30,
+                width: 80,
+                height: 24,
+                compression_type: CompressionType::None,
+                character_set: CharacterSet::Standard,
+                color_mode: ColorMode::None,
+                frames_per_batch: 1,
+                keyframe_interval: crate::frame_delta::DEFAULT_KEYFRAME_INTERVAL as u32,
+            },
+            frames: vec![],
+            base_frame_number: 0,
+            base_timestamp: 1_700_000_000_000,
+            crc: 0,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut codec = FrameBatchCodec::new();
+        let batch = sample_batch();
+
+        let mut buf = BytesMut::new();
+        codec.encode(batch.clone(), &mut buf).unwrap();
+
+        let decoded = codec.decode(&mut buf).unwrap().expect("full frame available");
+        assert_eq!(decoded.sequence, batch.sequence);
+        assert_eq!(decoded.stream_id, batch.stream_id);
+    }
+
+    #[test]
+    fn test_partial_read_waits_for_more_bytes() {
+        let mut codec = FrameBatchCodec::new();
+        let mut buf = BytesMut::new();
+        codec.encode(sample_batch(), &mut buf).unwrap();
+
+        let mut trickle = buf.split_to(buf.len() - 1);
+        assert!(codec.decode(&mut trickle).unwrap().is_none());
+
+        trickle.unsplit(buf);
+        assert!(codec.decode(&mut trickle).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_bad_magic_is_rejected() {
+        let mut codec = FrameBatchCodec::new();
+        let mut buf = BytesMut::new();
+        buf.put_slice(b"XXXX");
+        buf.put_u8(PROTOCOL_VERSION);
+        buf.put_u32_le(0);
+
+        assert!(codec.decode(&mut buf).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::varint::{decode_varint, encode_varint, zigzag_decode, zigzag_encode};
+
 /// Unique identifier for a stream (derived from sender address or custom ID)
 pub type StreamId = [u8; 32];
 
@@ -21,25 +23,155 @@ pub struct FrameBatch {
     /// Compressed frames in this batch
     pub frames: Vec<CompressedFrame>,
 
-    /// Unix timestamp (milliseconds)
-    pub timestamp: u64,
+    /// `frame_number` of the first frame in `frames` (0 if the batch is
+    /// empty), against which `encode_to_bytes` stores every frame's
+    /// `frame_number` as a zigzag+varint delta instead of a fixed 8 bytes -
+    /// the same base-offset trick Kafka record batches use for sequential
+    /// offsets.
+    pub base_frame_number: u64,
+
+    /// Unix timestamp (milliseconds) the batch was finalized at
+    pub base_timestamp: u64,
+
+    /// CRC32C (Castagnoli) over the bincode-serialized `(metadata, frames)`
+    /// region, set by `FrameBatcher::finalize_batch` and checked by
+    /// `verify` - mirrors the per-record-batch checksum in the Kafka/
+    /// Fluvio wire format, so a single corrupted byte on the wire is
+    /// caught and the batch dropped before its frames are decoded, rather
+    /// than silently producing garbage output.
+    pub crc: u32,
 }
 
 impl FrameBatch {
-    /// Encode the batch to bytes for blockchain storage
+    /// Encode the batch to bytes for blockchain storage. The fixed fields
+    /// (everything but `frames`) are bincode-serialized as a header, same
+    /// as before; each frame's `frame_number` is then stored as a
+    /// zigzag+varint delta from `base_frame_number` rather than a raw
+    /// `u64`, since a batch of dozens of sequential frames would otherwise
+    /// pay 8+ bytes per frame to say "one more than the last one" (see
+    /// `crate::varint`).
     pub fn encode_to_bytes(&self) -> anyhow::Result<Vec<u8>> {
-        bincode::serialize(self).map_err(|e| anyhow::anyhow!("Failed to encode batch: {}", e))
+        let header = (
+            &self.stream_id,
+            self.sequence,
+            &self.metadata,
+            self.base_frame_number,
+            self.base_timestamp,
+            self.crc,
+            self.frames.len() as u32,
+        );
+        let mut out = bincode::serialize(&header)
+            .map_err(|e| anyhow::anyhow!("Failed to encode batch header: {}", e))?;
+
+        for frame in &self.frames {
+            out.push(frame.compression_type as u8);
+            out.push(frame.is_keyframe as u8);
+
+            let delta = frame.frame_number as i64 - self.base_frame_number as i64;
+            encode_varint(zigzag_encode(delta), &mut out);
+            encode_varint(frame.data.len() as u64, &mut out);
+            out.extend_from_slice(&frame.data);
+        }
+
+        Ok(out)
     }
 
-    /// Decode a batch from bytes
+    /// Decode a batch from bytes, reversing `encode_to_bytes`: the header
+    /// is read with `bincode::deserialize_from` (which stops at the header's
+    /// own end), then each frame's varint delta is added back onto
+    /// `base_frame_number` to recover its absolute `frame_number`.
     pub fn decode_from_bytes(data: &[u8]) -> anyhow::Result<Self> {
-        bincode::deserialize(data).map_err(|e| anyhow::anyhow!("Failed to decode batch: {}", e))
+        let mut cursor = std::io::Cursor::new(data);
+        let (stream_id, sequence, metadata, base_frame_number, base_timestamp, crc, frame_count): (
+            StreamId,
+            SequenceNumber,
+            StreamMetadata,
+            u64,
+            u64,
+            u32,
+            u32,
+        ) = bincode::deserialize_from(&mut cursor)
+            .map_err(|e| anyhow::anyhow!("Failed to decode batch header: {}", e))?;
+
+        let rest = &data[cursor.position() as usize..];
+        let mut offset = 0usize;
+        let mut frames = Vec::with_capacity(frame_count as usize);
+
+        for _ in 0..frame_count {
+            let compression_byte = *rest
+                .get(offset)
+                .ok_or_else(|| anyhow::anyhow!("truncated frame batch: missing compression type"))?;
+            let compression_type = CompressionType::from_u8(compression_byte)
+                .ok_or_else(|| anyhow::anyhow!("unknown compression type byte: {}", compression_byte))?;
+            offset += 1;
+
+            let is_keyframe = *rest
+                .get(offset)
+                .ok_or_else(|| anyhow::anyhow!("truncated frame batch: missing keyframe flag"))?
+                != 0;
+            offset += 1;
+
+            let (zigzag_delta, consumed) = decode_varint(&rest[offset..])?;
+            offset += consumed;
+            let frame_number = (base_frame_number as i64 + zigzag_decode(zigzag_delta)) as u64;
+
+            let (len, consumed) = decode_varint(&rest[offset..])?;
+            offset += consumed;
+            let len = len as usize;
+
+            let data = rest
+                .get(offset..offset + len)
+                .ok_or_else(|| anyhow::anyhow!("truncated frame batch: missing frame data"))?
+                .to_vec();
+            offset += len;
+
+            frames.push(CompressedFrame {
+                compression_type,
+                data,
+                frame_number,
+                is_keyframe,
+            });
+        }
+
+        Ok(FrameBatch {
+            stream_id,
+            sequence,
+            metadata,
+            frames,
+            base_frame_number,
+            base_timestamp,
+            crc,
+        })
     }
 
     /// Calculate total size in bytes
     pub fn size_bytes(&self) -> usize {
         self.encode_to_bytes().map(|v| v.len()).unwrap_or(0)
     }
+
+    /// Compute the CRC32C over this batch's `metadata` + `frames` region.
+    /// Covers exactly those two fields (not `stream_id`/`sequence`/
+    /// `base_frame_number`/`base_timestamp`/`crc` itself) so a receiver
+    /// validating a partially buffered batch only needs the bytes that
+    /// follow the fixed header.
+    pub fn compute_crc(&self) -> anyhow::Result<u32> {
+        let region = bincode::serialize(&(&self.metadata, &self.frames))
+            .map_err(|e| anyhow::anyhow!("Failed to serialize batch for CRC: {}", e))?;
+        Ok(crc32c::crc32c(&region))
+    }
+
+    /// Recompute the CRC32C and compare it against the stored `crc`,
+    /// returning an error describing the mismatch if the batch is corrupt
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let expected = self.compute_crc()?;
+        if expected != self.crc {
+            return Err(anyhow::anyhow!(
+                "Frame batch {} CRC mismatch: expected {:#x}, got {:#x}",
+                self.sequence, expected, self.crc
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// A single compressed ASCII frame
@@ -75,6 +207,44 @@ pub enum CompressionType {
 
     /// Automatic selection based on content
     Auto = 4,
+
+    /// Brotli compression (better ratio than zlib on ASCII/terminal text)
+    Brotli = 5,
+
+    /// Zstd compression (fast, also better ratio than zlib)
+    Zstd = 6,
+
+    /// Zstd compressed against a dictionary trained on earlier frames of
+    /// the same stream (see `ZstdDictEncoder`/`ZstdDictDecoder`). Unlike the
+    /// other variants this carries state across frames, so it is not driven
+    /// through the stateless `FrameEncoder` trait.
+    ZstdDict = 7,
+
+    /// VNC-style row/span delta: keyframes are RLE-compressed whole
+    /// frames, other frames carry only the `(row, start_col, run_of_chars)`
+    /// spans that changed since the previous frame, with the run itself
+    /// RLE-compacted (see `SpanDeltaEncoder`)
+    SpanDelta = 8,
+
+    /// LZ4 block compression: much faster to encode than `Zlib`/`Brotli`/
+    /// `Zstd`, while still beating raw on static terminal content
+    Lz4 = 9,
+
+    /// Block-based motion compensation: each frame is divided into fixed
+    /// blocks, and every block is stored as either a motion vector against
+    /// the previous frame plus a small residual, or verbatim if no shift
+    /// matches well enough (see `MotionEncoder`). Panning or small camera
+    /// shake, which `Delta`/`SpanDelta` see as an almost-entirely-changed
+    /// frame, costs one vector per block instead.
+    Motion = 10,
+
+    /// Streaming LZ4 with a carried inter-frame dictionary: the keyframe
+    /// primes LZ4's own ring-buffer window and every later frame is
+    /// compressed against it via `LZ4_compress_fast_continue`, so only the
+    /// real changes emit literals. Unlike `Lz4` this carries state across
+    /// frames (see `Lz4StreamEncoder`/`Lz4StreamDecoder`), so it is not
+    /// driven through the stateless `FrameEncoder` trait.
+    Lz4Stream = 11,
 }
 
 impl CompressionType {
@@ -85,6 +255,13 @@ impl CompressionType {
             2 => Some(Self::Delta),
             3 => Some(Self::Zlib),
             4 => Some(Self::Auto),
+            5 => Some(Self::Brotli),
+            6 => Some(Self::Zstd),
+            7 => Some(Self::ZstdDict),
+            8 => Some(Self::SpanDelta),
+            9 => Some(Self::Lz4),
+            10 => Some(Self::Motion),
+            11 => Some(Self::Lz4Stream),
             _ => None,
         }
     }
@@ -113,6 +290,17 @@ pub struct StreamMetadata {
 
     /// Frames per batch
     pub frames_per_batch: u8,
+
+    /// How often a full keyframe is sent among `SpanDelta`-compressed
+    /// frames (every Nth frame). Carried so a receiver joining mid-stream
+    /// or resyncing after a sequence gap knows how long it may have to
+    /// wait for the next keyframe - see `FrameDecoder`.
+    #[serde(default = "default_metadata_keyframe_interval")]
+    pub keyframe_interval: u32,
+}
+
+fn default_metadata_keyframe_interval() -> u32 {
+    crate::frame_delta::DEFAULT_KEYFRAME_INTERVAL as u32
 }
 
 /// ASCII character sets for different quality levels
@@ -129,10 +317,19 @@ pub enum CharacterSet {
 
     /// Detailed: Enhanced quality with Unicode symbols (45 characters, recommended)
     Detailed,
+
+    /// Braille: packs each cell from a 2x4 pixel block into a single
+    /// Unicode Braille dot pattern (U+2800+) via `image_to_braille`
+    /// instead of a brightness palette - quadruples effective vertical
+    /// resolution versus one-char-per-pixel rendering
+    Braille,
 }
 
 impl CharacterSet {
-    /// Get the character palette for this set
+    /// Get the character palette for this set. `Braille` has no brightness
+    /// palette of its own - it's rendered by `image_to_braille` instead -
+    /// so this returns a two-glyph placeholder (blank and full Braille
+    /// cell) for callers that still expect a palette string.
     pub fn palette(&self) -> &'static str {
         match self {
             Self::Standard => " .:-=+*#٪@",
@@ -141,10 +338,29 @@ impl CharacterSet {
             // Carefully selected characters with good visual weight progression
             // Includes some Unicode for better shading
             Self::Detailed => " .ﺁ٧'`,;:ﻗ┤ﻷ^\"~-_+<>=*ﺃ«!?/|\\()[]IiltrfjcvxnyuXYUJCLQ0OZmwqdbkhao#MW&8٪B@$",
+            Self::Braille => "⠀⣿",
         }
     }
 }
 
+/// Rendering strategy for `image_to_ascii`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RenderMode {
+    /// Every cell maps straight from brightness to the `CharacterSet` palette
+    Brightness,
+
+    /// Difference-of-Gaussians edge detection overlays `|`/`-`/`/`/`\` line
+    /// glyphs at strong gradients, oriented by the local Sobel gradient
+    /// angle; cells below `dog_threshold` fall back to `Brightness`
+    EdgeAware { dog_threshold: u8 },
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        Self::Brightness
+    }
+}
+
 /// Color modes for terminal output
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColorMode {
@@ -162,6 +378,30 @@ pub enum ColorMode {
 
     /// Full RGB color (truecolor terminals)
     Rgb,
+
+    /// Truecolor captured the same as `Rgb`, but transported as a
+    /// median-cut-quantized palette plus one index byte per character
+    /// (see `EfficientRgbFrame::encode_palette`) instead of 3 raw RGB
+    /// bytes - a large size win on real camera frames where a frame's
+    /// colors cluster tightly even though few cells are byte-identical
+    Palette,
+
+    /// Truecolor captured the same as `Rgb`, but the frame's colors are
+    /// first median-cut quantized down to 256 buckets (see
+    /// `crate::efficient_rgb::median_cut_quantize`), then each bucket's
+    /// averaged RGB is mapped to the nearest fixed ANSI-256 palette entry
+    /// and emitted as the compact `\x1b[38;5;{code}m` escape instead of
+    /// 24-bit truecolor - much shorter, and honored by terminals and
+    /// recording tools that don't support truecolor
+    Ansi256,
+
+    /// Same median-cut quantization as `Ansi256`, down to 16 buckets, with
+    /// each bucket's averaged RGB mapped to the nearest of the 16 basic
+    /// ANSI colors and emitted as the basic `\x1b[3{n};1m` foreground
+    /// escapes (n = the matched color mod 8, with the bold attribute
+    /// selecting the bright half of the 16) - the most broadly compatible
+    /// fallback
+    Ansi16,
 }
 
 impl ColorMode {
@@ -210,11 +450,19 @@ impl ColorMode {
                 };
                 format!("\x1b[38;5;{}m{}\x1b[0m", color_code, ch)
             }
-            Self::Rgb => {
+            Self::Rgb | Self::Palette => {
                 // Truecolor: use brightness for all RGB components equally (grayscale)
                 // Or could map to actual colors from the image
                 format!("\x1b[38;2;{};{};{}m{}\x1b[0m", brightness, brightness, brightness, ch)
             }
+            Self::Ansi256 | Self::Ansi16 => {
+                // Median-cut quantization needs the whole frame's colors to
+                // build its palette, so there's nothing meaningful to do
+                // with a single brightness value here - real quantized
+                // output comes from `AsciiConverter::convert`, which calls
+                // `crate::efficient_rgb::median_cut_quantize` directly
+                format!("\x1b[38;2;{};{};{}m{}\x1b[0m", brightness, brightness, brightness, ch)
+            }
         }
     }
 }
@@ -0,0 +1,85 @@
+/// LEB128 varint + zigzag helpers, used by [`crate::FrameBatch::encode_to_bytes`]
+/// to store per-frame `frame_number` as a small delta from the batch's base
+/// rather than a fixed 8 bytes - the same base-relative trick Kafka/Fluvio
+/// record batches use for offsets.
+
+/// Map a signed delta onto an unsigned value so small negative and positive
+/// deltas both encode to few bytes (`0 -> 0, -1 -> 1, 1 -> 2, -2 -> 3, ...`)
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Inverse of [`zigzag_encode`]
+pub fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Append `value` to `out` as an unsigned LEB128 varint: 7 bits per byte,
+/// low-to-high, with the high bit set on every byte but the last
+pub fn encode_varint(value: u64, out: &mut Vec<u8>) {
+    let mut value = value;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Read an unsigned LEB128 varint from the front of `data`, returning the
+/// decoded value and the number of bytes consumed
+pub fn decode_varint(data: &[u8]) -> anyhow::Result<(u64, usize)> {
+    let mut value: u64 = 0;
+    let mut shift = 0u32;
+
+    for (i, &byte) in data.iter().enumerate() {
+        if shift >= 64 {
+            return Err(anyhow::anyhow!("varint too long"));
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+        shift += 7;
+    }
+
+    Err(anyhow::anyhow!("truncated varint"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint_roundtrip_small_and_large() {
+        for value in [0u64, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut out = Vec::new();
+            encode_varint(value, &mut out);
+            let (decoded, consumed) = decode_varint(&out).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(consumed, out.len());
+        }
+    }
+
+    #[test]
+    fn test_varint_small_values_are_single_byte() {
+        let mut out = Vec::new();
+        encode_varint(42, &mut out);
+        assert_eq!(out.len(), 1);
+    }
+
+    #[test]
+    fn test_zigzag_roundtrip() {
+        for value in [0i64, 1, -1, 2, -2, i64::MAX, i64::MIN] {
+            assert_eq!(zigzag_decode(zigzag_encode(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_decode_varint_rejects_truncated_input() {
+        assert!(decode_varint(&[0x80, 0x80]).is_err());
+    }
+}
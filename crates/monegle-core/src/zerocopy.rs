@@ -0,0 +1,345 @@
+use anyhow::{anyhow, Result};
+
+use crate::{
+    CharacterSet, ColorMode, CompressedFrame, CompressionType, FrameBatch, SequenceNumber,
+    StreamId, StreamMetadata,
+};
+
+/// Magic bytes identifying the fixed-header zero-copy layout, distinct from
+/// `FrameBatchCodec`'s bincode-framed wire format in `transport.rs` - this is
+/// an alternative *encoding* of a `FrameBatch`, not a transport framing.
+const MAGIC: [u8; 4] = *b"MNGZ";
+
+/// Wire format version for the zero-copy layout
+const VERSION: u8 = 1;
+
+/// Fixed header length: magic(4) + version(1) + stream_id(32) + sequence(8)
+/// + timestamp(8) + crc(4) + fps(1) + width(2) + height(2)
+/// + compression_type(1) + character_set(1) + color_mode(1)
+/// + frames_per_batch(1) + keyframe_interval(4) + frame_count(4)
+const HEADER_LEN: usize = 4 + 1 + 32 + 8 + 8 + 4 + 1 + 2 + 2 + 1 + 1 + 1 + 1 + 4 + 4;
+
+/// Per-frame record header: compression_type(1) + is_keyframe(1)
+/// + frame_number(8) + data_len(4)
+const FRAME_HEADER_LEN: usize = 1 + 1 + 8 + 4;
+
+impl CharacterSet {
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Standard => 0,
+            Self::Dense => 1,
+            Self::Blocks => 2,
+            Self::Detailed => 3,
+            Self::Braille => 4,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Standard),
+            1 => Some(Self::Dense),
+            2 => Some(Self::Blocks),
+            3 => Some(Self::Detailed),
+            4 => Some(Self::Braille),
+            _ => None,
+        }
+    }
+}
+
+impl ColorMode {
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Purple => 1,
+            Self::Blue => 2,
+            Self::Green => 3,
+            Self::Rgb => 4,
+            Self::Palette => 5,
+            Self::Ansi256 => 6,
+            Self::Ansi16 => 7,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Purple),
+            2 => Some(Self::Blue),
+            3 => Some(Self::Green),
+            4 => Some(Self::Rgb),
+            5 => Some(Self::Palette),
+            6 => Some(Self::Ansi256),
+            7 => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+}
+
+/// Borrowing view of a `CompressedFrame`: `data` is a slice into the buffer
+/// `FrameBatch::parse_ref` was called with, not an owned `Vec<u8>`
+#[derive(Debug, Clone, Copy)]
+pub struct CompressedFrameRef<'a> {
+    pub compression_type: CompressionType,
+    pub data: &'a [u8],
+    pub frame_number: u64,
+    pub is_keyframe: bool,
+}
+
+/// Borrowing view of a `FrameBatch`, produced by `FrameBatch::parse_ref`
+/// without copying any frame payload. `metadata` is small and `Copy`-like,
+/// so it is cloned rather than borrowed; only the (potentially large) frame
+/// payloads need to avoid allocation on the hot decode path.
+#[derive(Debug, Clone)]
+pub struct FrameBatchRef<'a> {
+    pub stream_id: StreamId,
+    pub sequence: SequenceNumber,
+    pub metadata: StreamMetadata,
+    pub frames: Vec<CompressedFrameRef<'a>>,
+    pub base_timestamp: u64,
+    pub crc: u32,
+}
+
+impl FrameBatch {
+    /// Encode to the fixed-header zero-copy layout: a little-endian header
+    /// (stream id, sequence, timestamp, metadata fields, frame count)
+    /// followed by length-prefixed frame payloads. Unlike
+    /// `encode_to_bytes`, every field is fixed-width or length-prefixed so
+    /// `parse_ref` can hand back slices into the input buffer instead of
+    /// allocating.
+    pub fn encode_to_bytes_ref(&self) -> Result<Vec<u8>> {
+        let frame_count: u32 = self
+            .frames
+            .len()
+            .try_into()
+            .map_err(|_| anyhow!("too many frames in batch: {}", self.frames.len()))?;
+
+        let mut out = Vec::with_capacity(HEADER_LEN + self.frames.iter().map(|f| FRAME_HEADER_LEN + f.data.len()).sum::<usize>());
+
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&self.stream_id);
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+        out.extend_from_slice(&self.base_timestamp.to_le_bytes());
+        out.extend_from_slice(&self.crc.to_le_bytes());
+        out.push(self.metadata.fps);
+        out.extend_from_slice(&self.metadata.width.to_le_bytes());
+        out.extend_from_slice(&self.metadata.height.to_le_bytes());
+        out.push(self.metadata.compression_type as u8);
+        out.push(self.metadata.character_set.to_u8());
+        out.push(self.metadata.color_mode.to_u8());
+        out.push(self.metadata.frames_per_batch);
+        out.extend_from_slice(&self.metadata.keyframe_interval.to_le_bytes());
+        out.extend_from_slice(&frame_count.to_le_bytes());
+
+        for frame in &self.frames {
+            let data_len: u32 = frame
+                .data
+                .len()
+                .try_into()
+                .map_err(|_| anyhow!("frame payload too large: {} bytes", frame.data.len()))?;
+
+            out.push(frame.compression_type as u8);
+            out.push(frame.is_keyframe as u8);
+            out.extend_from_slice(&frame.frame_number.to_le_bytes());
+            out.extend_from_slice(&data_len.to_le_bytes());
+            out.extend_from_slice(&frame.data);
+        }
+
+        Ok(out)
+    }
+
+    /// Parse the zero-copy layout produced by `encode_to_bytes_ref`,
+    /// validating lengths and returning a `FrameBatchRef` whose frame
+    /// payloads are slices into `data` - no per-frame heap allocation.
+    pub fn parse_ref(data: &[u8]) -> Result<FrameBatchRef<'_>> {
+        if data.len() < HEADER_LEN {
+            return Err(anyhow!("zero-copy frame batch header too short"));
+        }
+
+        if data[..MAGIC.len()] != MAGIC {
+            return Err(anyhow!("bad zero-copy frame batch magic bytes"));
+        }
+
+        let mut i = MAGIC.len();
+        let version = data[i];
+        if version != VERSION {
+            return Err(anyhow!("unsupported zero-copy frame batch version: {}", version));
+        }
+        i += 1;
+
+        let stream_id: StreamId = data[i..i + 32].try_into().unwrap();
+        i += 32;
+
+        let sequence = u64::from_le_bytes(data[i..i + 8].try_into().unwrap());
+        i += 8;
+
+        let base_timestamp = u64::from_le_bytes(data[i..i + 8].try_into().unwrap());
+        i += 8;
+
+        let crc = u32::from_le_bytes(data[i..i + 4].try_into().unwrap());
+        i += 4;
+
+        let fps = data[i];
+        i += 1;
+
+        let width = u16::from_le_bytes(data[i..i + 2].try_into().unwrap());
+        i += 2;
+
+        let height = u16::from_le_bytes(data[i..i + 2].try_into().unwrap());
+        i += 2;
+
+        let compression_type = CompressionType::from_u8(data[i])
+            .ok_or_else(|| anyhow!("unknown compression type byte: {}", data[i]))?;
+        i += 1;
+
+        let character_set = CharacterSet::from_u8(data[i])
+            .ok_or_else(|| anyhow!("unknown character set byte: {}", data[i]))?;
+        i += 1;
+
+        let color_mode = ColorMode::from_u8(data[i])
+            .ok_or_else(|| anyhow!("unknown color mode byte: {}", data[i]))?;
+        i += 1;
+
+        let frames_per_batch = data[i];
+        i += 1;
+
+        let keyframe_interval = u32::from_le_bytes(data[i..i + 4].try_into().unwrap());
+        i += 4;
+
+        let frame_count = u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+        i += 4;
+
+        let mut frames = Vec::with_capacity(frame_count);
+        for _ in 0..frame_count {
+            if data.len() < i + FRAME_HEADER_LEN {
+                return Err(anyhow!("zero-copy frame batch: truncated frame header"));
+            }
+
+            let frame_compression_type = CompressionType::from_u8(data[i])
+                .ok_or_else(|| anyhow!("unknown frame compression type byte: {}", data[i]))?;
+            let is_keyframe = data[i + 1] != 0;
+            let frame_number = u64::from_le_bytes(data[i + 2..i + 10].try_into().unwrap());
+            let frame_data_len = u32::from_le_bytes(data[i + 10..i + 14].try_into().unwrap()) as usize;
+            i += FRAME_HEADER_LEN;
+
+            if data.len() < i + frame_data_len {
+                return Err(anyhow!("zero-copy frame batch: truncated frame payload"));
+            }
+            let frame_data = &data[i..i + frame_data_len];
+            i += frame_data_len;
+
+            frames.push(CompressedFrameRef {
+                compression_type: frame_compression_type,
+                data: frame_data,
+                frame_number,
+                is_keyframe,
+            });
+        }
+
+        Ok(FrameBatchRef {
+            stream_id,
+            sequence,
+            metadata: StreamMetadata {
+                fps,
+                width,
+                height,
+                compression_type,
+                character_set,
+                color_mode,
+                frames_per_batch,
+                keyframe_interval,
+            },
+            frames,
+            base_timestamp,
+            crc,
+        })
+    }
+}
+
+impl CompressedFrame {
+    /// Borrow this frame's fields as a `CompressedFrameRef`, for callers
+    /// that want to treat owned and zero-copy frames uniformly
+    pub fn as_ref(&self) -> CompressedFrameRef<'_> {
+        CompressedFrameRef {
+            compression_type: self.compression_type,
+            data: &self.data,
+            frame_number: self.frame_number,
+            is_keyframe: self.is_keyframe,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame_delta::DEFAULT_KEYFRAME_INTERVAL;
+
+    fn sample_batch() -> FrameBatch {
+        FrameBatch {
+            stream_id: [9u8; 32],
+            sequence: 7,
+            metadata: StreamMetadata {
+                fps: 24,
+                width: 120,
+                height: 40,
+                compression_type: CompressionType::SpanDelta,
+                character_set: CharacterSet::Dense,
+                color_mode: ColorMode::Palette,
+                frames_per_batch: 3,
+                keyframe_interval: DEFAULT_KEYFRAME_INTERVAL as u32,
+            },
+            frames: vec![
+                CompressedFrame {
+                    compression_type: CompressionType::Rle,
+                    data: vec![1, 2, 3, 4],
+                    frame_number: 100,
+                    is_keyframe: true,
+                },
+                CompressedFrame {
+                    compression_type: CompressionType::Motion,
+                    data: vec![5, 6],
+                    frame_number: 101,
+                    is_keyframe: false,
+                },
+            ],
+            base_frame_number: 100,
+            base_timestamp: 1_700_000_000_123,
+            crc: 0xdead_beef,
+        }
+    }
+
+    #[test]
+    fn test_zerocopy_roundtrip() {
+        let batch = sample_batch();
+        let encoded = batch.encode_to_bytes_ref().unwrap();
+        let parsed = FrameBatch::parse_ref(&encoded).unwrap();
+
+        assert_eq!(parsed.stream_id, batch.stream_id);
+        assert_eq!(parsed.sequence, batch.sequence);
+        assert_eq!(parsed.base_timestamp, batch.base_timestamp);
+        assert_eq!(parsed.crc, batch.crc);
+        assert_eq!(parsed.metadata, batch.metadata);
+        assert_eq!(parsed.frames.len(), batch.frames.len());
+
+        for (parsed_frame, original_frame) in parsed.frames.iter().zip(batch.frames.iter()) {
+            assert_eq!(parsed_frame.compression_type, original_frame.compression_type);
+            assert_eq!(parsed_frame.data, original_frame.data.as_slice());
+            assert_eq!(parsed_frame.frame_number, original_frame.frame_number);
+            assert_eq!(parsed_frame.is_keyframe, original_frame.is_keyframe);
+        }
+    }
+
+    #[test]
+    fn test_zerocopy_rejects_bad_magic() {
+        let mut encoded = sample_batch().encode_to_bytes_ref().unwrap();
+        encoded[0] = b'X';
+        assert!(FrameBatch::parse_ref(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_zerocopy_rejects_truncated_payload() {
+        let encoded = sample_batch().encode_to_bytes_ref().unwrap();
+        let truncated = &encoded[..encoded.len() - 1];
+        assert!(FrameBatch::parse_ref(truncated).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use alloy::{
+    consensus::Transaction,
+    primitives::Address,
+    providers::{Provider, ProviderBuilder, WsConnect},
+};
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use monegle_core::{FrameBatch, FrameBatchCodec};
+use std::str::FromStr;
+use tokio_util::codec::Decoder;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// Subscribes directly to the pending-transaction pubsub feed of a WebSocket
+/// provider, rather than polling confirmed blocks like
+/// [`crate::listener::TransactionListener`] does. This is the "Receiver
+/// monitors transactions via WebSocket RPC subscriptions" path the sender's
+/// docs describe: batches reach the decoder as soon as a matching
+/// transaction hits the mempool, without waiting for a block to confirm it.
+///
+/// Pending-tx delivery has no ordering guarantee and can include the same
+/// transaction more than once (e.g. after a replacement), so batches
+/// extracted here are sent unordered and deduplicated downstream by
+/// [`crate::reassembly::run_reassembly_loop`], which also absorbs any
+/// overlap with the confirmed-block scanner running alongside it.
+pub struct BlockchainReceiver {
+    sender_address: Address,
+}
+
+impl BlockchainReceiver {
+    /// Monitor pending transactions FROM the specified sender address
+    pub fn new(sender_address: &str) -> Result<Self> {
+        let sender_addr = Address::from_str(sender_address)
+            .map_err(|e| anyhow!("Invalid sender address: {}", e))?;
+
+        Ok(Self {
+            sender_address: sender_addr,
+        })
+    }
+
+    /// Connect and stream decoded frame batches from the pending-tx pubsub
+    /// feed until the subscription ends or `raw_tx` is closed by the
+    /// reassembly buffer consuming it.
+    pub async fn start_pubsub_loop(self, ws_url: &str, raw_tx: mpsc::Sender<FrameBatch>) -> Result<()> {
+        let ws = WsConnect::new(ws_url);
+        let provider = ProviderBuilder::new()
+            .on_ws(ws)
+            .await
+            .map_err(|e| anyhow!("Failed to connect to WebSocket: {}", e))?;
+
+        info!("Subscribed to pending transactions, watching for sender {}", self.sender_address);
+
+        let sub = provider
+            .subscribe_full_pending_transactions()
+            .await
+            .map_err(|e| anyhow!("Failed to subscribe to pending transactions: {}", e))?;
+
+        let mut stream = sub.into_stream();
+
+        while let Some(tx) = stream.next().await {
+            if tx.from != self.sender_address {
+                continue;
+            }
+
+            let mut calldata = bytes::BytesMut::from(&tx.inner.input()[..]);
+
+            if calldata.is_empty() {
+                // EIP-4844 blob payloads live in the sidecar, not calldata.
+                // Blobs are pruned from consensus-layer storage within a few
+                // epochs and aren't exposed over this execution-layer
+                // subscription, so a blob-mode batch can't be recovered here
+                // - the reassembly buffer's gap detection will report it as
+                // a missing sequence once the confirmed-block scanner also
+                // fails to supply it.
+                continue;
+            }
+
+            match FrameBatchCodec::new().decode(&mut calldata) {
+                Ok(Some(batch)) => {
+                    debug!(
+                        "Pending tx {:?} decoded batch {} ({} frames)",
+                        tx.inner.tx_hash(),
+                        batch.sequence,
+                        batch.frames.len()
+                    );
+
+                    if raw_tx.send(batch).await.is_err() {
+                        debug!("Raw batch channel closed, stopping pending-tx subscription");
+                        return Ok(());
+                    }
+                }
+                Ok(None) => {
+                    warn!("Truncated frame batch in pending tx {:?}, skipping", tx.inner.tx_hash());
+                }
+                Err(e) => {
+                    warn!("Failed to decode batch from pending tx {:?}: {}", tx.inner.tx_hash(), e);
+                }
+            }
+        }
+
+        Err(anyhow!("Pending transaction subscription ended unexpectedly"))
+    }
+}
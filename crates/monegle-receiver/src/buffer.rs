@@ -1,13 +1,24 @@
 use anyhow::{anyhow, Result};
+use monegle_core::{dashboard::{Dashboard, DashboardCounters, DashboardTick}, decode_batch_frames, TerminalGrid};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
 use tracing::{debug, info, warn};
 
+use crate::recorder::AsciicastRecorder;
+
 /// Frame buffer for smooth playback
 pub struct FrameBuffer {
-    /// Buffered frames: sequence -> frames
-    buffer: HashMap<u64, Vec<String>>,
+    /// Buffered frames: sequence -> (frame text, is_keyframe). Non-keyframe
+    /// text is a `TerminalGrid` cell diff (see `grid`/`next_frame`), not a
+    /// displayable frame by itself.
+    buffer: HashMap<u64, Vec<(String, bool)>>,
+
+    /// Delta+zstd-compressed sequences not yet decompressed. Entries move
+    /// into `buffer` lazily, the first time they're actually read, so
+    /// buffering ahead of playback doesn't force everything into memory as
+    /// plain strings.
+    compressed_buffer: HashMap<u64, Vec<u8>>,
 
     /// Current sequence position
     current_sequence: u64,
@@ -18,35 +29,66 @@ pub struct FrameBuffer {
     /// Buffer capacity (number of sequences)
     capacity: usize,
 
+    /// Frame dimensions, needed to parse/apply `TerminalGrid` cell diffs
+    width: u16,
+    height: u16,
+
+    /// Cell grid reconstructed from the last keyframe plus every diff
+    /// applied since. `None` until a keyframe has been read, and reset to
+    /// `None` on `seek_to_sequence` since a diff read right after a seek
+    /// has no valid base to apply onto.
+    current_grid: Option<TerminalGrid>,
+
     /// Total frames buffered
     frame_count: usize,
+
+    /// Last successfully emitted frame, resent verbatim during concealment
+    last_frame: Option<String>,
+
+    /// How many times a stale frame was resent in place of real data
+    concealed_frames: usize,
+
+    /// How many times playback saw the minimum available sequence drop
+    /// below where it currently was (treated as a stream restart, see
+    /// `next_frame`)
+    backward_jumps: usize,
+
+    /// Total compressed bytes buffered via `add_batch_compressed`, less any
+    /// sequence evicted before it was ever read
+    total_compressed_bytes: usize,
+
+    /// Total decompressed bytes produced so far (only known once a
+    /// compressed sequence is actually read), for ratio reporting
+    total_original_bytes: usize,
 }
 
 impl FrameBuffer {
-    pub fn new(capacity: usize) -> Self {
-        info!("Initializing frame buffer with capacity: {} sequences", capacity);
+    pub fn new(capacity: usize, width: u16, height: u16) -> Self {
+        info!("Initializing frame buffer with capacity: {} sequences ({}x{})", capacity, width, height);
 
         Self {
             buffer: HashMap::new(),
+            compressed_buffer: HashMap::new(),
             current_sequence: 0,
             current_frame_index: 0,
             capacity,
+            width,
+            height,
+            current_grid: None,
             frame_count: 0,
+            last_frame: None,
+            concealed_frames: 0,
+            backward_jumps: 0,
+            total_compressed_bytes: 0,
+            total_original_bytes: 0,
         }
     }
 
-    /// Add a batch of frames to the buffer
-    pub fn add_batch(&mut self, sequence: u64, frames: Vec<String>) {
-        if self.buffer.len() >= self.capacity {
-            // Remove oldest sequence
-            let oldest = self.buffer.keys().min().copied();
-            if let Some(seq) = oldest {
-                if let Some(removed) = self.buffer.remove(&seq) {
-                    self.frame_count -= removed.len();
-                    debug!("Removed old sequence {} ({} frames)", seq, removed.len());
-                }
-            }
-        }
+    /// Add a batch of frames to the buffer. Each frame is paired with
+    /// whether it's a keyframe (full frame text) or a `TerminalGrid` cell
+    /// diff against the previous frame.
+    pub fn add_batch(&mut self, sequence: u64, frames: Vec<(String, bool)>) {
+        self.evict_if_full();
 
         self.frame_count += frames.len();
         self.buffer.insert(sequence, frames);
@@ -72,11 +114,97 @@ impl FrameBuffer {
         );
     }
 
+    /// Add a delta+zstd-compressed batch (see `monegle_core::encode_batch_frames`)
+    /// to the buffer. It is decompressed lazily, the first time playback
+    /// actually reaches this sequence, via `ensure_decompressed`.
+    pub fn add_batch_compressed(&mut self, sequence: u64, compressed: Vec<u8>) {
+        self.evict_if_full();
+
+        self.total_compressed_bytes += compressed.len();
+        debug!("Buffered compressed sequence {} ({} bytes)", sequence, compressed.len());
+        self.compressed_buffer.insert(sequence, compressed);
+    }
+
+    /// Evict the oldest buffered sequence (compressed or not) if the buffer
+    /// is at capacity
+    fn evict_if_full(&mut self) {
+        if self.buffer.len() + self.compressed_buffer.len() < self.capacity {
+            return;
+        }
+
+        let Some(oldest) = self.earliest_available_sequence() else {
+            return;
+        };
+
+        if let Some(removed) = self.buffer.remove(&oldest) {
+            self.frame_count -= removed.len();
+            debug!("Removed old sequence {} ({} frames)", oldest, removed.len());
+        }
+        if let Some(removed) = self.compressed_buffer.remove(&oldest) {
+            self.total_compressed_bytes -= removed.len();
+            debug!("Removed old compressed sequence {} ({} bytes)", oldest, removed.len());
+        }
+    }
+
+    /// Earliest sequence number present in either buffer, compressed or not
+    fn earliest_available_sequence(&self) -> Option<u64> {
+        let plain = self.buffer.keys().min().copied();
+        let compressed = self.compressed_buffer.keys().min().copied();
+        plain.into_iter().chain(compressed).min()
+    }
+
+    /// Decompress `sequence` into `buffer` if it's only present in
+    /// `compressed_buffer`. No-op if it's already decompressed or not
+    /// buffered at all.
+    fn ensure_decompressed(&mut self, sequence: u64) -> Result<()> {
+        if self.buffer.contains_key(&sequence) {
+            return Ok(());
+        }
+
+        let Some(compressed) = self.compressed_buffer.remove(&sequence) else {
+            return Ok(());
+        };
+
+        let frames = decode_batch_frames(&compressed)
+            .map_err(|e| anyhow!("Failed to decompress sequence {}: {}", sequence, e))?;
+
+        self.total_original_bytes += frames.iter().map(|f| f.len()).sum::<usize>();
+        self.frame_count += frames.len();
+        debug!("Decompressed sequence {} lazily ({} frames)", sequence, frames.len());
+        // Frames out of this path are always full reconstructed text, never cell diffs
+        self.buffer.insert(sequence, frames.into_iter().map(|f| (f, true)).collect());
+
+        Ok(())
+    }
+
+    /// Reconstruct the full displayable text for a buffered frame: a
+    /// keyframe is parsed into a fresh grid, a diff is applied to
+    /// `current_grid`. Either way `current_grid` ends up holding the grid
+    /// this frame rendered. Errors if a diff arrives with no keyframe yet
+    /// applied - the invariant `seek_to_sequence` relies on to keep the
+    /// grid well-defined.
+    fn reconstruct(&mut self, text: &str, is_keyframe: bool) -> Result<String> {
+        if is_keyframe {
+            let grid = TerminalGrid::parse(text, self.width, self.height);
+            let rendered = grid.render();
+            self.current_grid = Some(grid);
+            return Ok(rendered);
+        }
+
+        let grid = self.current_grid.as_mut().ok_or_else(|| {
+            anyhow!("Cell diff read with no keyframe applied yet - seek landed between keyframes")
+        })?;
+        grid.apply_diff(text);
+        Ok(grid.render())
+    }
+
     /// Get the next frame for playback
     pub fn next_frame(&mut self) -> Result<String> {
+        self.ensure_decompressed(self.current_sequence)?;
+
         // If current sequence is not in buffer, try to find the earliest available sequence
         if !self.buffer.contains_key(&self.current_sequence) {
-            let min_seq = self.buffer.keys().min().copied();
+            let min_seq = self.earliest_available_sequence();
             if let Some(seq) = min_seq {
                 info!(
                     "Current sequence {} not in buffer, jumping to earliest sequence {}",
@@ -84,6 +212,7 @@ impl FrameBuffer {
                 );
                 self.current_sequence = seq;
                 self.current_frame_index = 0;
+                self.ensure_decompressed(self.current_sequence)?;
             } else {
                 return Err(anyhow!("Buffer is empty"));
             }
@@ -98,17 +227,28 @@ impl FrameBuffer {
             let old_sequence = self.current_sequence;
             self.current_sequence += 1;
             self.current_frame_index = 0;
+            self.ensure_decompressed(self.current_sequence)?;
 
             // Again check if the next sequence exists, if not jump to earliest
             if !self.buffer.contains_key(&self.current_sequence) {
-                let min_seq = self.buffer.keys().min().copied();
+                let min_seq = self.earliest_available_sequence();
                 if let Some(seq) = min_seq {
                     if seq < old_sequence {
-                        warn!("‚ö†Ô∏è  SEQUENCE JUMP BACKWARDS: from {} to {} (buffer underrun or restart)", old_sequence, seq);
-                    } else {
-                        debug!("Sequence {} not available, jumping forward to {}", self.current_sequence, seq);
+                        warn!(
+                            "Sequence jumped backwards: from {} to {} - treating as a stream restart",
+                            old_sequence, seq
+                        );
+                        self.backward_jumps += 1;
+                        self.current_sequence = seq;
+                        self.current_frame_index = 0;
+                        return Err(anyhow!(
+                            "Stream restarted at sequence {}, conceal until it is read", seq
+                        ));
                     }
+
+                    debug!("Sequence {} not available, jumping forward to {}", self.current_sequence, seq);
                     self.current_sequence = seq;
+                    self.ensure_decompressed(self.current_sequence)?;
                 } else {
                     return Err(anyhow!("No more sequences in buffer"));
                 }
@@ -122,7 +262,7 @@ impl FrameBuffer {
             }
         }
 
-        let frame = self.buffer
+        let (text, is_keyframe) = self.buffer
             .get(&self.current_sequence)
             .and_then(|frames| frames.get(self.current_frame_index))
             .ok_or_else(|| anyhow!("Frame not found"))?
@@ -130,9 +270,24 @@ impl FrameBuffer {
 
         self.current_frame_index += 1;
 
+        let frame = self.reconstruct(&text, is_keyframe)?;
+        self.last_frame = Some(frame.clone());
+
         Ok(frame)
     }
 
+    /// Called in place of `next_frame` when it returns `Err`, so playback
+    /// stays on the `frame_interval` beat instead of stalling: resends the
+    /// last successfully emitted frame rather than pausing output. Returns
+    /// `None` only if no frame has ever been emitted yet (nothing to
+    /// conceal with).
+    pub fn conceal(&mut self) -> Option<String> {
+        if self.last_frame.is_some() {
+            self.concealed_frames += 1;
+        }
+        self.last_frame.clone()
+    }
+
     /// Check if buffer has enough frames for playback
     pub fn is_ready(&self) -> bool {
         self.frame_count >= 10 // Wait for at least 10 frames
@@ -141,16 +296,24 @@ impl FrameBuffer {
     /// Get buffer statistics
     pub fn stats(&self) -> BufferStats {
         BufferStats {
-            sequences: self.buffer.len(),
+            sequences: self.buffer.len() + self.compressed_buffer.len(),
             frames: self.frame_count,
             current_sequence: self.current_sequence,
+            concealed_frames: self.concealed_frames,
+            backward_jumps: self.backward_jumps,
+            compressed_bytes: self.total_compressed_bytes,
+            original_bytes: self.total_original_bytes,
         }
     }
 
-    /// Skip to a specific sequence
+    /// Skip to a specific sequence. Clears `current_grid`, since a seek can
+    /// land between keyframes: the next read must be a keyframe (or
+    /// `next_frame` errors, same as landing on a diff with no prior
+    /// keyframe) before cell diffs are well-defined again.
     pub fn seek_to_sequence(&mut self, sequence: u64) {
         self.current_sequence = sequence;
         self.current_frame_index = 0;
+        self.current_grid = None;
         info!("Seeked to sequence {}", sequence);
     }
 }
@@ -160,6 +323,31 @@ pub struct BufferStats {
     pub sequences: usize,
     pub frames: usize,
     pub current_sequence: u64,
+
+    /// How many times a stale frame was resent in place of real data
+    pub concealed_frames: usize,
+
+    /// How many times playback restarted because the minimum available
+    /// sequence dropped below where it currently was
+    pub backward_jumps: usize,
+
+    /// Total compressed bytes buffered so far via `add_batch_compressed`,
+    /// less any sequence evicted before it was ever read
+    pub compressed_bytes: usize,
+
+    /// Total decompressed bytes produced so far (only known once a
+    /// compressed sequence has actually been read). `original_bytes as f64
+    /// / compressed_bytes as f64` gives the running compression ratio.
+    pub original_bytes: usize,
+}
+
+/// A decoded frame paired with the wall-clock offset from playback start
+/// at which it's scheduled to be shown, derived from `target_fps` and a
+/// running output-frame counter - see
+/// `BufferController::start_buffering_loop`.
+struct DecodedFrame {
+    text: String,
+    pts: std::time::Duration,
 }
 
 /// Buffering controller
@@ -169,25 +357,37 @@ pub struct BufferController {
 }
 
 impl BufferController {
-    pub fn new(capacity: usize, initial_buffer_batches: usize) -> Self {
+    pub fn new(capacity: usize, initial_buffer_batches: usize, width: u16, height: u16) -> Self {
         Self {
-            buffer: Arc::new(Mutex::new(FrameBuffer::new(capacity))),
+            buffer: Arc::new(Mutex::new(FrameBuffer::new(capacity, width, height))),
             initial_buffer_batches,
         }
     }
 
-    /// Start buffering and playback loop
+    /// Start buffering and playback loop. When `recorder` is set, every
+    /// frame sent to `tx` is also appended to it as an asciicast-v2 event,
+    /// so the session can be replayed later via `run_replay_mode`. When
+    /// `dashboard` is set, buffer depth/FPS/sequence/counter telemetry is
+    /// rendered into it every tick instead of only surfacing via `info!`
+    /// log lines; a 'q'/Esc keypress on the dashboard stops the loop.
     pub async fn start_buffering_loop(
         self,
-        mut rx: mpsc::Receiver<(u64, Vec<String>)>,
+        mut rx: mpsc::Receiver<(u64, Vec<(String, bool)>)>,
         tx: mpsc::Sender<String>,
         target_fps: f32,
+        mut recorder: Option<AsciicastRecorder>,
+        mut dashboard: Option<Dashboard>,
     ) -> Result<()> {
         info!("Starting buffering loop (target FPS: {}, initial buffer: {} batches)",
             target_fps, self.initial_buffer_batches);
 
         let buffer_clone = self.buffer.clone();
 
+        // Signals end-of-stream: flips to `true` once the producer closes
+        // `rx`, so the playback loop below knows to drain the buffer at
+        // normal speed and return cleanly instead of concealing forever.
+        let (eos_tx, mut eos_rx) = tokio::sync::watch::channel(false);
+
         // Spawn buffering task that continuously receives and buffers
         let buffering_handle = tokio::spawn(async move {
             let mut batch_count = 0;
@@ -205,13 +405,14 @@ impl BufferController {
                     let buffer = buffer_clone.lock().await;
                     let stats = buffer.stats();
                     info!(
-                        "üì• Buffering: received batch {} (total: {}), buffer: {} seqs / {} frames",
+                        "Buffering: received batch {} (total: {}), buffer: {} seqs / {} frames",
                         sequence, batch_count, stats.sequences, stats.frames
                     );
                     last_log = std::time::Instant::now();
                 }
             }
-            warn!("‚ö†Ô∏è Buffering task stopped - no more batches received (total: {})", batch_count);
+            info!("End of stream: no more batches, draining {} buffered frame(s)", batch_count);
+            let _ = eos_tx.send(true);
         });
 
         // Wait for initial buffer
@@ -227,44 +428,164 @@ impl BufferController {
                 break;
             }
 
+            if *eos_rx.borrow() {
+                info!("End of stream reached before initial buffer filled, starting playback with what's buffered");
+                break;
+            }
+
             info!("Buffering... {}/{} batches", stats.sequences, self.initial_buffer_batches);
         }
 
-        // Playback phase with adaptive FPS
+        // Playback phase: presentation-timestamp-driven scheduling. Each
+        // output frame's PTS is `index / target_fps` off a wall-clock
+        // anchor taken here, so display cadence tracks real time instead
+        // of the source's actual production rate - a source that runs
+        // slightly faster or slower than `target_fps` no longer produces
+        // cumulative judder, since frames are shown when their PTS says
+        // to, not one-per-fixed-sleep.
         info!("Starting playback with {}s delay for smooth buffering", self.initial_buffer_batches * 7);
 
+        let frame_period = std::time::Duration::from_secs_f32(1.0 / target_fps);
+        // Re-check the clock/buffer well inside a frame period so timing
+        // isn't rounded to whole frames, and at least every 10ms
+        let tick_interval = std::cmp::min(frame_period / 4, std::time::Duration::from_millis(10));
+
         let mut frame_count = 0u64;
+        let mut next_frame_index = 0u64;
         let start_time = std::time::Instant::now();
         let mut last_stats_time = start_time;
+        let mut drained_cleanly = false;
+        let mut eos_drained = false;
+        // now - expected_pts of the last frame shown; positive means
+        // playback is running behind the schedule
+        let mut drift = std::time::Duration::ZERO;
 
         loop {
-            // Adaptive delay based on buffer depth
-            let buffer_depth = {
+            if let Some(dash) = dashboard.as_ref() {
+                if dash.should_quit()? {
+                    info!("Dashboard quit requested, stopping buffering loop");
+                    break;
+                }
+            }
+
+            let at_eos = *eos_rx.borrow();
+            let stats = {
                 let buffer = self.buffer.lock().await;
-                buffer.stats().frames
+                buffer.stats()
             };
 
-            // Slow down if buffer is getting low, speed up if buffer is large
-            let adaptive_fps = if buffer_depth < 10 {
-                target_fps * 0.5  // Half speed if buffer low
-            } else if buffer_depth > 50 {
-                target_fps * 1.5  // 1.5x speed if buffer high
+            if at_eos && (eos_drained || (stats.sequences == 0 && stats.frames == 0)) {
+                let elapsed = start_time.elapsed().as_secs_f32();
+                let avg_fps = if elapsed > 0.0 { frame_count as f32 / elapsed } else { 0.0 };
+                info!(
+                    "End of stream drained: {} frames ({:.1} FPS average), concealed: {}, backward jumps: {}",
+                    frame_count, avg_fps, stats.concealed_frames, stats.backward_jumps
+                );
+                drained_cleanly = true;
+                break;
+            }
+
+            if let Some(dash) = dashboard.as_mut() {
+                let elapsed = start_time.elapsed().as_secs_f32();
+                let current_fps = if elapsed > 0.0 { frame_count as f32 / elapsed } else { 0.0 };
+                let sequence_range = {
+                    let buffer = self.buffer.lock().await;
+                    let mut seqs: Vec<u64> = buffer.buffer.keys().copied().collect();
+                    seqs.sort();
+                    seqs.first().zip(seqs.last()).map(|(lo, hi)| (*lo, *hi))
+                };
+
+                dash.render(&DashboardTick {
+                    depth: stats.frames,
+                    current_fps,
+                    target_fps,
+                    // No more buffer-depth speed-up/slow-down: the PTS
+                    // schedule below already catches playback up or holds
+                    // it back as needed, so the "adaptive" rate is just
+                    // the schedule itself
+                    adaptive_fps: target_fps,
+                    sequence_range,
+                    counters: DashboardCounters {
+                        underruns: stats.concealed_frames,
+                        backward_jumps: stats.backward_jumps,
+                    },
+                })?;
+            }
+
+            let now = start_time.elapsed();
+            let mut due: Option<DecodedFrame> = None;
+
+            if at_eos {
+                // Flush whatever remains one at a time at the stream's
+                // normal pace rather than dropping the backlog the way a
+                // live, still-producing stream catches up after a stall -
+                // there's no "live edge" to catch up to once the source
+                // has stopped.
+                // Played sequences stay buffered, so "drained" means no
+                // frame is left in the current sequence and none after it
+                let frame = {
+                    let mut buffer = self.buffer.lock().await;
+                    let cur = buffer.current_sequence;
+                    let more_in_current = buffer.compressed_buffer.contains_key(&cur)
+                        || buffer.buffer.get(&cur).map_or(false, |f| buffer.current_frame_index < f.len());
+                    let later = buffer.buffer.keys().chain(buffer.compressed_buffer.keys()).any(|&s| s > cur);
+                    if more_in_current || later { Some(buffer.next_frame()) } else { None }
+                };
+                match frame {
+                    Some(Ok(text)) => {
+                        let pts = std::time::Duration::from_secs_f32(next_frame_index as f32 / target_fps);
+                        next_frame_index += 1;
+                        due = Some(DecodedFrame { text, pts });
+                    }
+                    Some(Err(_)) => {}
+                    None => eos_drained = true,
+                }
             } else {
-                target_fps
-            };
+                // Pop every frame whose presentation time has already
+                // elapsed, keeping only the most recent - this is what
+                // lets playback catch back up after a stall instead of
+                // working through the backlog frame by frame.
+                loop {
+                    let next_pts = std::time::Duration::from_secs_f32(next_frame_index as f32 / target_fps);
+                    if next_pts > now {
+                        break;
+                    }
 
-            let frame_interval = std::time::Duration::from_secs_f32(1.0 / adaptive_fps);
-            tokio::time::sleep(frame_interval).await;
+                    let frame = {
+                        let mut buffer = self.buffer.lock().await;
+                        buffer.next_frame()
+                    };
 
-            // Get next frame
-            let frame = {
-                let mut buffer = self.buffer.lock().await;
-                buffer.next_frame()
-            };
+                    match frame {
+                        Ok(text) => {
+                            next_frame_index += 1;
+                            due = Some(DecodedFrame { text, pts: next_pts });
+                        }
+                        Err(e) => {
+                            let concealed = {
+                                let mut buffer = self.buffer.lock().await;
+                                buffer.conceal()
+                            };
+                            match concealed {
+                                Some(text) => {
+                                    debug!("Buffer underrun ({}), concealing with last-known-good frame", e);
+                                    next_frame_index += 1;
+                                    due = Some(DecodedFrame { text, pts: next_pts });
+                                }
+                                None => {
+                                    debug!("Buffer underrun ({}) with nothing to conceal with yet", e);
+                                }
+                            }
+                            break;
+                        }
+                    }
+                }
+            }
 
-            match frame {
-                Ok(frame) => {
+            match due {
+                Some(frame) => {
                     frame_count += 1;
+                    drift = now.saturating_sub(frame.pts);
 
                     // Log stats every 5 seconds with detailed buffer info
                     if last_stats_time.elapsed().as_secs() >= 5 {
@@ -283,37 +604,47 @@ impl BufferController {
                         };
 
                         info!(
-                            "‚ñ∂Ô∏è  Playback: {} frames ({:.1} FPS), buffer: {} seqs / {} frames (seq range: {}, current: {}, frame idx: {}), adaptive FPS: {:.1}",
+                            "‚ñ∂Ô∏è  Playback: {} frames ({:.1} FPS), buffer: {} seqs / {} frames (seq range: {}, current: {}, frame idx: {}), drift: {:?}, concealed: {}",
                             frame_count, actual_fps, stats.sequences, stats.frames, seq_range,
-                            buffer.current_sequence, buffer.current_frame_index, adaptive_fps
+                            buffer.current_sequence, buffer.current_frame_index, drift, stats.concealed_frames
                         );
                         last_stats_time = std::time::Instant::now();
                     }
 
-                    if tx.send(frame).await.is_err() {
+                    if let Some(recorder) = recorder.as_mut() {
+                        if let Err(e) = recorder.record_frame(&frame.text, start_time.elapsed()) {
+                            warn!("Failed to record frame: {}", e);
+                        }
+                    }
+
+                    if tx.send(frame.text).await.is_err() {
                         warn!("Playback channel closed, stopping buffering loop");
                         break;
                     }
                 }
-                Err(e) => {
-                    let (current_seq, buffer_seqs) = {
-                        let buffer = self.buffer.lock().await;
-                        let stats = buffer.stats();
-                        let seqs: Vec<u64> = buffer.buffer.keys().copied().collect();
-                        (stats.current_sequence, seqs)
-                    };
-
-                    warn!(
-                        "‚ö†Ô∏è Buffer underrun: {} - Current seq: {}, Available seqs: {:?}",
-                        e, current_seq, buffer_seqs
-                    );
-                    warn!("Waiting 2 seconds for more batches...");
-                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                None => {
+                    // Nothing due yet (or nothing available while
+                    // draining) - hold whatever's currently displayed.
                 }
             }
+
+            // Sleep until the next tick, biased by how far behind schedule
+            // we are: a sustained lag shortens the wait so the catch-up
+            // loop above gets to run again sooner, instead of piling a
+            // full tick on top of an already-late frame.
+            let wait = tick_interval.saturating_sub(drift.min(tick_interval));
+            tokio::time::sleep(if wait.is_zero() { std::time::Duration::from_millis(1) } else { wait }).await;
         }
 
-        buffering_handle.abort();
+        if drained_cleanly {
+            let _ = buffering_handle.await;
+        } else {
+            buffering_handle.abort();
+        }
+
+        if let Some(dash) = dashboard {
+            dash.leave()?;
+        }
 
         let elapsed = start_time.elapsed().as_secs_f32();
         let avg_fps = frame_count as f32 / elapsed;
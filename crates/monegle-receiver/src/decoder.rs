@@ -1,33 +1,83 @@
-use anyhow::Result;
-use monegle_core::{decode_frame, CompressedFrame, FrameBatch, StreamMetadata};
+use anyhow::{anyhow, Result};
+use async_stream::stream;
+use futures::Stream;
+use monegle_core::{decode_frame, CompressedFrame, CompressionType, FrameBatch, Lz4StreamDecoder, StreamMetadata};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 /// Frame decoder component
 pub struct FrameDecoder {
     previous_frame: Option<String>,
+
+    /// Frame number of the last successfully decoded frame, used to notice
+    /// a sequence gap (a dropped batch) before it corrupts a delta decode
+    last_frame_number: Option<u64>,
+
+    /// Mirror of the sender's `Lz4StreamEncoder` window, only touched for
+    /// `CompressionType::Lz4Stream` frames - must replay frames strictly
+    /// in order and reset at the same keyframe boundaries or it desyncs
+    lz4_stream_decoder: Lz4StreamDecoder,
 }
 
 impl FrameDecoder {
     pub fn new() -> Self {
         Self {
             previous_frame: None,
+            last_frame_number: None,
+            lz4_stream_decoder: Lz4StreamDecoder::new(),
         }
     }
 
-    /// Decode a single compressed frame
+    /// Decode a single compressed frame. Joining mid-stream or noticing a
+    /// sequence gap both drop `previous_frame`, so any delta frame that
+    /// arrives before the next keyframe is rejected here rather than
+    /// silently decoded against the wrong base - `decode_batch`'s caller
+    /// already tolerates per-frame errors, so this is how the decoder
+    /// "awaits" a keyframe to resync.
     pub fn decode_frame(&mut self, frame: &CompressedFrame) -> Result<String> {
-        let decoded = decode_frame(
-            frame,
+        if !frame.is_keyframe {
+            let gap = self
+                .last_frame_number
+                .map(|last| frame.frame_number != last + 1)
+                .unwrap_or(true);
+
+            if gap || self.previous_frame.is_none() {
+                if gap && self.previous_frame.is_some() {
+                    warn!(
+                        "Sequence gap before frame {} (expected {}), dropping until next keyframe",
+                        frame.frame_number,
+                        self.last_frame_number.unwrap() + 1
+                    );
+                    self.previous_frame = None;
+                }
+
+                self.last_frame_number = Some(frame.frame_number);
+                return Err(anyhow!(
+                    "Frame {} is a delta frame with no keyframe to resync against",
+                    frame.frame_number
+                ));
+            }
+        }
+
+        let decoded = if frame.compression_type == CompressionType::Lz4Stream {
             if frame.is_keyframe {
-                None
-            } else {
-                self.previous_frame.as_deref()
-            },
-        )?;
+                self.lz4_stream_decoder.reset();
+            }
+            self.lz4_stream_decoder.decode(&frame.data)?
+        } else {
+            decode_frame(
+                frame,
+                if frame.is_keyframe {
+                    None
+                } else {
+                    self.previous_frame.as_deref()
+                },
+            )?
+        };
 
         // Store for next delta decode
         self.previous_frame = Some(decoded.clone());
+        self.last_frame_number = Some(frame.frame_number);
 
         debug!(
             "Decoded frame {}: {} chars (keyframe: {})",
@@ -74,12 +124,13 @@ impl FrameDecoder {
             // Log metadata changes
             if last_metadata.as_ref() != Some(&batch.metadata) {
                 info!(
-                    "Stream metadata: {}x{} @ {} FPS, charset: {:?}, color: {:?}",
+                    "Stream metadata: {}x{} @ {} FPS, charset: {:?}, color: {:?}, keyframe every {} frames",
                     batch.metadata.width,
                     batch.metadata.height,
                     batch.metadata.fps,
                     batch.metadata.character_set,
-                    batch.metadata.color_mode
+                    batch.metadata.color_mode,
+                    batch.metadata.keyframe_interval
                 );
                 last_metadata = Some(batch.metadata.clone());
             }
@@ -107,6 +158,51 @@ impl FrameDecoder {
         info!("Decoding loop stopped after {} frames", decoded_count);
         Ok(())
     }
+
+    /// Adapt the decoder into a `Stream` yielding one item per decoded
+    /// frame, instead of the batched `(metadata, sequence, Vec<String>)`
+    /// fan-out `start_decoding_loop` sends over an `mpsc` channel. `self`
+    /// is consumed so `previous_frame`/`last_frame_number` carry across
+    /// yields exactly as they would across iterations of the channel
+    /// loop - this is the same decode path, just re-shaped for
+    /// `StreamExt` combinators (`buffered`, `filter`, `throttle`,
+    /// `chunks`, ...) instead of a fixed receiver.
+    pub fn decode_stream(
+        mut self,
+        mut rx: mpsc::Receiver<FrameBatch>,
+    ) -> impl Stream<Item = Result<(StreamMetadata, u64, String)>> {
+        stream! {
+            let mut last_metadata: Option<StreamMetadata> = None;
+
+            while let Some(batch) = rx.recv().await {
+                if last_metadata.as_ref() != Some(&batch.metadata) {
+                    info!(
+                        "Stream metadata: {}x{} @ {} FPS, charset: {:?}, color: {:?}, keyframe every {} frames",
+                        batch.metadata.width,
+                        batch.metadata.height,
+                        batch.metadata.fps,
+                        batch.metadata.character_set,
+                        batch.metadata.color_mode,
+                        batch.metadata.keyframe_interval
+                    );
+                    last_metadata = Some(batch.metadata.clone());
+                }
+
+                for frame in &batch.frames {
+                    match self.decode_frame(frame) {
+                        Ok(decoded) => yield Ok((batch.metadata.clone(), batch.sequence, decoded)),
+                        Err(e) => {
+                            warn!(
+                                "Failed to decode frame {} in batch {}: {}",
+                                frame.frame_number, batch.sequence, e
+                            );
+                            yield Err(e);
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl Default for FrameDecoder {
@@ -9,14 +9,90 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Gauge, Paragraph},
     Frame, Terminal,
 };
+use std::collections::VecDeque;
 use std::io;
 use std::time::Instant;
 use tokio::sync::mpsc;
 use tracing::info;
 
+use monegle_core::CharacterSet;
+
+use crate::gif_export::GifExporter;
+use crate::recorder::AsciicastRecorder;
+
+/// How many seconds of decoded frames the ratatui display's scrub history
+/// retains, sized off the stream's own FPS so the retained window covers
+/// roughly the same wall-clock span regardless of frame rate
+const HISTORY_SECONDS: u64 = 30;
+
+/// How many seconds `j`/`l` seeks within the retained history
+const SEEK_SECONDS: u64 = 5;
+
+/// Bounded ring buffer of recently decoded frames, indexed by an absolute
+/// position (since stream start) so a scrub cursor stays meaningful across
+/// evictions instead of needing to be re-based every time the oldest frame
+/// is dropped
+struct FrameHistory {
+    frames: VecDeque<String>,
+    capacity: usize,
+    /// Absolute index of the oldest frame still retained in `frames`
+    oldest_index: u64,
+    /// Absolute index that will be assigned to the next pushed frame
+    next_index: u64,
+}
+
+impl FrameHistory {
+    fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            oldest_index: 0,
+            next_index: 0,
+        }
+    }
+
+    /// Append a frame, dropping the oldest one once `capacity` is exceeded
+    fn push(&mut self, frame: String) {
+        if self.frames.len() >= self.capacity {
+            self.frames.pop_front();
+            self.oldest_index += 1;
+        }
+        self.frames.push_back(frame);
+        self.next_index += 1;
+    }
+
+    /// Absolute index of the most recently pushed frame (the live head)
+    fn live_index(&self) -> u64 {
+        self.next_index.saturating_sub(1)
+    }
+
+    /// Absolute index of the oldest frame still retained
+    fn oldest_index(&self) -> u64 {
+        self.oldest_index
+    }
+
+    fn get(&self, absolute_index: u64) -> Option<&String> {
+        if absolute_index < self.oldest_index {
+            return None;
+        }
+        self.frames.get((absolute_index - self.oldest_index) as usize)
+    }
+}
+
+/// Collapse a cursor onto "following live" (`None`) once it reaches the
+/// live head, so stepping or seeking forward far enough snaps back to live
+/// playback instead of leaving the cursor pinned one frame behind it
+fn snap_to_live(idx: u64, live_index: u64) -> Option<u64> {
+    if idx >= live_index {
+        None
+    } else {
+        Some(idx)
+    }
+}
+
 /// Terminal display component
 pub struct TerminalDisplay {
     fps: u8,
@@ -117,7 +193,16 @@ impl TerminalDisplay {
         Ok(())
     }
 
-    /// Ratatui display (for monochrome frames)
+    /// Ratatui display (for monochrome frames). Beyond just playing
+    /// frames forward live, keeps a bounded `FrameHistory` of the last
+    /// `HISTORY_SECONDS` so the user can pause, step, and seek through
+    /// what just went by: `cursor` is `None` while following the live
+    /// head, or `Some(absolute_index)` while frozen on a past frame
+    /// (paused, or scrubbed back with the arrow/seek keys). The input
+    /// channel is drained unconditionally every tick regardless of
+    /// `cursor`, so scrubbing backward never stalls the live stream -
+    /// it just keeps landing in the ring buffer until the user catches
+    /// back up to it.
     async fn start_ratatui_display_loop(
         self,
         mut rx: mpsc::Receiver<String>,
@@ -132,36 +217,103 @@ impl TerminalDisplay {
 
         terminal.clear()?;
 
-        let mut current_frame = first_frame;
+        let history_capacity = (self.fps.max(1) as u64 * HISTORY_SECONDS) as usize;
+        let mut history = FrameHistory::new(history_capacity);
+        history.push(first_frame);
+
         let mut frame_count = 1u64;
         let mut fps_counter = FpsCounter::new();
         fps_counter.tick();
 
+        let mut cursor: Option<u64> = None;
+
         let result = loop {
             // Check for user input (non-blocking)
             if event::poll(std::time::Duration::from_millis(0))? {
                 if let Event::Key(key) = event::read()? {
-                    if key.code == KeyCode::Char('q') || key.code == KeyCode::Esc {
-                        info!("User requested quit");
-                        break Ok(());
+                    let live_index = history.live_index();
+                    let seek_frames = self.fps.max(1) as u64 * SEEK_SECONDS;
+
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => {
+                            info!("User requested quit");
+                            break Ok(());
+                        }
+                        KeyCode::Char(' ') => {
+                            // Pause at the current frame, or resume following live
+                            cursor = match cursor {
+                                None => Some(live_index),
+                                Some(_) => None,
+                            };
+                        }
+                        KeyCode::Left => {
+                            let idx = cursor.unwrap_or(live_index);
+                            cursor = snap_to_live(idx.saturating_sub(1), live_index);
+                        }
+                        KeyCode::Right => {
+                            let idx = cursor.unwrap_or(live_index);
+                            cursor = snap_to_live((idx + 1).min(live_index), live_index);
+                        }
+                        KeyCode::Char('j') => {
+                            let idx = cursor.unwrap_or(live_index);
+                            cursor = snap_to_live(idx.saturating_sub(seek_frames), live_index);
+                        }
+                        KeyCode::Char('l') => {
+                            let idx = cursor.unwrap_or(live_index);
+                            cursor = snap_to_live((idx + seek_frames).min(live_index), live_index);
+                        }
+                        KeyCode::Home => {
+                            cursor = Some(history.oldest_index());
+                        }
+                        KeyCode::End => {
+                            cursor = None;
+                        }
+                        _ => {}
                     }
                 }
             }
 
-            // Try to get next frame (non-blocking)
-            if let Ok(frame) = rx.try_recv() {
-                current_frame = frame;
+            // Keep draining into the ring buffer regardless of `cursor`, so
+            // pausing or scrubbing backward never stalls the live producer
+            while let Ok(frame) = rx.try_recv() {
+                history.push(frame);
                 frame_count += 1;
                 fps_counter.tick();
+
+                // A frozen cursor still points at the same absolute frame
+                // once the oldest entries are evicted; clamp it forward so
+                // it never falls off the front of the ring buffer
+                if let Some(idx) = cursor {
+                    cursor = Some(idx.max(history.oldest_index()));
+                }
+            }
+
+            // Stepping or seeking back can also land before the oldest
+            // retained frame; clamp that the same way
+            if let Some(idx) = cursor {
+                cursor = Some(idx.max(history.oldest_index()));
             }
 
+            let live_index = history.live_index();
+            let oldest_index = history.oldest_index();
+            let displayed_index = cursor.unwrap_or(live_index);
+            let displayed_frame = history
+                .get(displayed_index)
+                .expect("cursor is clamped to [oldest_index, live_index] on every tick")
+                .clone();
+
             // Render
             terminal.draw(|f| {
                 self.render_frame(
                     f,
-                    &current_frame,
+                    &displayed_frame,
                     frame_count,
                     fps_counter.fps(),
+                    displayed_index,
+                    oldest_index,
+                    live_index,
+                    cursor.is_none(),
+                    history_capacity,
                 );
             })?;
 
@@ -179,6 +331,67 @@ impl TerminalDisplay {
         result
     }
 
+    /// Record frames to an asciicast v2 `.cast` file instead of displaying
+    /// them live - the same `mpsc::Receiver<String>` loop `start_display_loop`
+    /// drives, but each frame is timestamped off `start_time` and appended
+    /// via `AsciicastRecorder` rather than drawn to the terminal. Supersedes
+    /// the ad-hoc `/tmp/monegle_frames.log` dump with a standard, seekable
+    /// format any asciinema player can replay.
+    pub async fn start_recording_mode(
+        self,
+        mut rx: mpsc::Receiver<String>,
+        path: &str,
+    ) -> Result<()> {
+        info!("Recording session to {}", path);
+
+        let mut recorder = AsciicastRecorder::new(path, self.width, self.height)?;
+        let start_time = std::time::Instant::now();
+        let mut frame_count = 0u64;
+
+        while let Some(frame) = rx.recv().await {
+            recorder.record_frame(&frame, start_time.elapsed())?;
+            frame_count += 1;
+
+            if frame_count % 100 == 0 {
+                info!("Recorded {} frames to {}", frame_count, path);
+            }
+        }
+
+        info!("Recording stopped after {} frames ({})", frame_count, path);
+        Ok(())
+    }
+
+    /// Export frames to an animated GIF instead of displaying them live.
+    /// `charset` should match the stream's actual `StreamMetadata::
+    /// character_set` so `GifExporter`'s fallback glyph (for block/braille
+    /// shading characters the bundled bitmap font has no letterform for)
+    /// looks up the right density-ordered palette; defaults to `Standard`
+    /// here the same way `self.width`/`self.height`/`self.fps` default
+    /// until the stream's first batch is seen.
+    pub async fn start_gif_export_mode(
+        self,
+        mut rx: mpsc::Receiver<String>,
+        path: &str,
+        charset: CharacterSet,
+    ) -> Result<()> {
+        info!("Exporting session to {}", path);
+
+        let mut exporter = GifExporter::create(path, self.width, self.height, self.fps, charset)?;
+        let mut frame_count = 0u64;
+
+        while let Some(frame) = rx.recv().await {
+            exporter.export_frame(&frame)?;
+            frame_count += 1;
+
+            if frame_count % 100 == 0 {
+                info!("Exported {} frames to {}", frame_count, path);
+            }
+        }
+
+        info!("GIF export finished after {} frames ({})", frame_count, path);
+        Ok(())
+    }
+
     /// File logging mode (for background execution without terminal)
     async fn start_file_logging_mode(
         self,
@@ -238,19 +451,28 @@ impl TerminalDisplay {
         Ok(())
     }
 
-    /// Render a single frame
+    /// Render a single frame, plus (when `live`/`cursor` tracking is in
+    /// play) a timeline gauge showing the scrub position against the live
+    /// head and how full the retained history buffer is
+    #[allow(clippy::too_many_arguments)]
     fn render_frame(
         &self,
         f: &mut Frame,
         ascii_frame: &str,
         frame_count: u64,
         current_fps: f32,
+        displayed_index: u64,
+        oldest_index: u64,
+        live_index: u64,
+        is_live: bool,
+        history_capacity: usize,
     ) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),      // Header
                 Constraint::Min(10),        // Main frame area
+                Constraint::Length(3),      // Timeline
                 Constraint::Length(3),      // Footer
             ])
             .split(f.size());
@@ -263,6 +485,11 @@ impl TerminalDisplay {
                 format!("FPS: {:.1}/{}", current_fps, self.fps),
                 Style::default().fg(Color::Green),
             ),
+            Span::raw("  "),
+            Span::styled(
+                if is_live { "● LIVE" } else { "❙❙ PAUSED" },
+                Style::default().fg(if is_live { Color::Green } else { Color::Yellow }),
+            ),
         ]))
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Left);
@@ -276,17 +503,44 @@ impl TerminalDisplay {
 
         f.render_widget(frame_widget, chunks[1]);
 
-        // Footer with stats
+        // Timeline: scrub position within the retained history vs. the live
+        // head, plus how full the ring buffer is
+        let span = (live_index - oldest_index).max(1);
+        let position_ratio = ((displayed_index - oldest_index) as f64 / span as f64).clamp(0.0, 1.0);
+        let behind_seconds = (live_index - displayed_index) as f32 / self.fps.max(1) as f32;
+        let fill_ratio = ((live_index - oldest_index + 1) as f64 / history_capacity as f64).clamp(0.0, 1.0);
+        let timeline_label = if is_live {
+            "LIVE".to_string()
+        } else {
+            format!("-{:.1}s", behind_seconds)
+        };
+
+        let timeline = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Timeline (buffer {:.0}% full)", fill_ratio * 100.0)),
+            )
+            .gauge_style(Style::default().fg(if is_live { Color::Green } else { Color::Yellow }))
+            .ratio(position_ratio)
+            .label(timeline_label);
+
+        f.render_widget(timeline, chunks[2]);
+
+        // Footer with stats and keybindings
         let footer = Paragraph::new(Line::from(vec![
             Span::raw("Stream: "),
             Span::styled(&self.stream_id, Style::default().fg(Color::Yellow)),
             Span::raw(format!(" | Frames: {} | ", frame_count)),
-            Span::styled("Press 'q' to quit", Style::default().fg(Color::Red)),
+            Span::styled(
+                "Space: pause/resume | ←/→: step | j/l: seek 5s | Home/End: oldest/live | q: quit",
+                Style::default().fg(Color::Red),
+            ),
         ]))
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Left);
 
-        f.render_widget(footer, chunks[2]);
+        f.render_widget(footer, chunks[3]);
     }
 }
 
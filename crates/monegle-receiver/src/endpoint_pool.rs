@@ -0,0 +1,124 @@
+use std::time::{Duration, Instant};
+
+/// Cooldown applied to an endpoint after it fails, doubled on repeated
+/// consecutive failures and capped below.
+const BASE_QUARANTINE: Duration = Duration::from_secs(5);
+const MAX_QUARANTINE: Duration = Duration::from_secs(120);
+
+/// Per-endpoint rolling health, used to pick the currently healthiest RPC
+/// or WebSocket URL and to quarantine misbehaving ones with a cooldown.
+#[derive(Debug)]
+pub struct EndpointHealth {
+    pub url: String,
+    successes: u64,
+    failures: u64,
+    consecutive_failures: u32,
+    last_latency_ms: Option<u64>,
+    quarantined_until: Option<Instant>,
+}
+
+impl EndpointHealth {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            successes: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            last_latency_ms: None,
+            quarantined_until: None,
+        }
+    }
+
+    fn success_rate(&self) -> f64 {
+        let total = self.successes + self.failures;
+        if total == 0 {
+            1.0
+        } else {
+            self.successes as f64 / total as f64
+        }
+    }
+
+    pub fn is_quarantined(&self) -> bool {
+        self.quarantined_until
+            .map(|until| Instant::now() < until)
+            .unwrap_or(false)
+    }
+
+    pub fn record_success(&mut self, latency_ms: u64) {
+        self.successes += 1;
+        self.consecutive_failures = 0;
+        self.last_latency_ms = Some(latency_ms);
+        self.quarantined_until = None;
+    }
+
+    pub fn record_failure(&mut self) -> Duration {
+        self.failures += 1;
+        self.consecutive_failures += 1;
+
+        let cooldown = BASE_QUARANTINE
+            .saturating_mul(1 << (self.consecutive_failures - 1).min(5))
+            .min(MAX_QUARANTINE);
+        self.quarantined_until = Some(Instant::now() + cooldown);
+        cooldown
+    }
+
+    fn score(&self) -> (u32, i64, u64) {
+        let rate_inv = ((1.0 - self.success_rate()) * 1000.0) as i64;
+        (self.consecutive_failures, rate_inv, self.last_latency_ms.unwrap_or(0))
+    }
+}
+
+/// A set of endpoints (RPC or WebSocket URLs) ranked by health so the
+/// listener can fail over to the next-best one on error.
+pub struct EndpointPool {
+    endpoints: Vec<EndpointHealth>,
+}
+
+impl EndpointPool {
+    pub fn new(urls: &[String]) -> Self {
+        Self {
+            endpoints: urls.iter().cloned().map(EndpointHealth::new).collect(),
+        }
+    }
+
+    /// Endpoint indices ordered best-first, skipping quarantined ones unless
+    /// every endpoint is currently quarantined
+    pub fn ranked(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.endpoints.len())
+            .filter(|&i| !self.endpoints[i].is_quarantined())
+            .collect();
+
+        if indices.is_empty() {
+            indices = (0..self.endpoints.len()).collect();
+        }
+
+        indices.sort_by_key(|&i| self.endpoints[i].score());
+        indices
+    }
+
+    pub fn url(&self, idx: usize) -> &str {
+        &self.endpoints[idx].url
+    }
+
+    pub fn record_success(&mut self, idx: usize, latency_ms: u64) {
+        self.endpoints[idx].record_success(latency_ms);
+    }
+
+    pub fn record_failure(&mut self, idx: usize) -> Duration {
+        self.endpoints[idx].record_failure()
+    }
+
+    pub fn quarantined_indices(&self) -> Vec<usize> {
+        (0..self.endpoints.len())
+            .filter(|&i| self.endpoints[i].is_quarantined())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+}
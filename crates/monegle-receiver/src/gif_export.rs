@@ -0,0 +1,195 @@
+use anyhow::{anyhow, Result};
+use gif::{Encoder, Frame, Repeat};
+use monegle_core::{median_cut_quantize, CharacterSet, TerminalGrid};
+use std::borrow::Cow;
+use std::fs::File;
+
+/// Bitmap glyph size in pixels. 5x7 is the classic small monospace bitmap
+/// size (Adafruit GFX's built-in font, HD44780 character LCDs), small
+/// enough that a typical 80x24 stream still exports to a reasonably sized
+/// GIF.
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+/// Foreground used for cells `TerminalGrid::parse` left uncolored
+/// (`ColorMode::None` streams)
+const DEFAULT_FOREGROUND: (u8, u8, u8) = (200, 200, 200);
+
+/// Background every glyph is drawn against
+const BACKGROUND: (u8, u8, u8) = (0, 0, 0);
+
+/// Bundled 5x7 bitmap font, one row per byte with the glyph's 5 columns
+/// packed into the low bits (MSB = leftmost column). Covers digits,
+/// uppercase letters (lowercase is folded to upper - a common
+/// simplification for fonts this small) and a handful of punctuation;
+/// anything else falls back to `fallback_glyph`.
+fn known_glyph(ch: char) -> Option<[u8; GLYPH_HEIGHT]> {
+    Some(match ch.to_ascii_uppercase() {
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01111, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b01111],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01111, 0b10000, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        ',' => [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100, 0b01000],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        ';' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b01000],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        '_' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111],
+        '!' => [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100],
+        '?' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100],
+        '/' => [0b00001, 0b00010, 0b00010, 0b00100, 0b01000, 0b01000, 0b10000],
+        '\\' => [0b10000, 0b01000, 0b01000, 0b00100, 0b00010, 0b00010, 0b00001],
+        '(' => [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010],
+        ')' => [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000],
+        '\'' => [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000],
+        '"' => [0b01010, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000],
+        _ => return None,
+    })
+}
+
+/// Render `ch`'s ink as a block filled proportionally to how far into
+/// `charset`'s density-ordered palette it sits (see `CharacterSet::
+/// palette`), for glyphs `known_glyph` has no letterform for - the block,
+/// braille and detailed charsets draw most of their characters for
+/// *shading* rather than shape, so approximating their visual weight
+/// beats leaving them blank.
+fn fallback_glyph(ch: char, charset: CharacterSet) -> [u8; GLYPH_HEIGHT] {
+    let palette = charset.palette();
+    let position = palette.chars().position(|c| c == ch).unwrap_or(0);
+    let denominator = palette.chars().count().saturating_sub(1).max(1);
+    let filled_rows = (position * GLYPH_HEIGHT) / denominator;
+
+    let mut rows = [0u8; GLYPH_HEIGHT];
+    for row in rows.iter_mut().skip(GLYPH_HEIGHT - filled_rows) {
+        *row = 0b11111;
+    }
+    rows
+}
+
+fn glyph_for(ch: char, charset: CharacterSet) -> [u8; GLYPH_HEIGHT] {
+    if ch == ' ' {
+        return [0u8; GLYPH_HEIGHT];
+    }
+    known_glyph(ch).unwrap_or_else(|| fallback_glyph(ch, charset))
+}
+
+/// Exports a decoded ASCII stream to an animated GIF: each frame is
+/// rasterized back into an RGB pixel buffer with the bundled bitmap font,
+/// quantized to a 256-entry palette with the same median-cut quantizer
+/// `EfficientRgbFrame::encode_palette` uses, and written as a GIF frame
+/// with `StreamMetadata::fps`-derived delay - turning what's otherwise
+/// only viewable live in a terminal into a portable clip.
+pub struct GifExporter {
+    encoder: Encoder<File>,
+    width_cells: u16,
+    height_cells: u16,
+    charset: CharacterSet,
+    delay_centiseconds: u16,
+}
+
+impl GifExporter {
+    pub fn create(path: &str, width: u16, height: u16, fps: u8, charset: CharacterSet) -> Result<Self> {
+        let pixel_width = width as usize * GLYPH_WIDTH;
+        let pixel_height = height as usize * GLYPH_HEIGHT;
+
+        let file = File::create(path)?;
+        let mut encoder = Encoder::new(file, pixel_width as u16, pixel_height as u16, &[])
+            .map_err(|e| anyhow!("Failed to create GIF encoder: {}", e))?;
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| anyhow!("Failed to set GIF loop flag: {}", e))?;
+
+        // GIF frame delay is in hundredths of a second
+        let delay_centiseconds = (100 / fps.max(1) as u32).max(1) as u16;
+
+        Ok(Self {
+            encoder,
+            width_cells: width,
+            height_cells: height,
+            charset,
+            delay_centiseconds,
+        })
+    }
+
+    /// Rasterize one ASCII frame and append it to the GIF. Parses the
+    /// frame the same way the batcher's cell-diff encoding does
+    /// (`TerminalGrid::parse`), so color carries over exactly as rendered.
+    pub fn export_frame(&mut self, ascii_frame: &str) -> Result<()> {
+        let grid = TerminalGrid::parse(ascii_frame, self.width_cells, self.height_cells);
+
+        let pixel_width = self.width_cells as usize * GLYPH_WIDTH;
+        let pixel_height = self.height_cells as usize * GLYPH_HEIGHT;
+        let mut pixels = vec![BACKGROUND; pixel_width * pixel_height];
+
+        for row in 0..self.height_cells as usize {
+            for col in 0..self.width_cells as usize {
+                let Some(cell) = grid.cell(row, col) else {
+                    continue;
+                };
+                let glyph = glyph_for(cell.ch, self.charset);
+                let color = cell.color.unwrap_or(DEFAULT_FOREGROUND);
+
+                for (gy, bits) in glyph.iter().enumerate() {
+                    for gx in 0..GLYPH_WIDTH {
+                        if bits & (1 << (GLYPH_WIDTH - 1 - gx)) != 0 {
+                            let px = col * GLYPH_WIDTH + gx;
+                            let py = row * GLYPH_HEIGHT + gy;
+                            pixels[py * pixel_width + px] = color;
+                        }
+                    }
+                }
+            }
+        }
+
+        let (palette, indices) = median_cut_quantize(&pixels, 256);
+        let mut flat_palette = Vec::with_capacity(palette.len() * 3);
+        for (r, g, b) in &palette {
+            flat_palette.extend_from_slice(&[*r, *g, *b]);
+        }
+
+        let mut frame = Frame::default();
+        frame.width = pixel_width as u16;
+        frame.height = pixel_height as u16;
+        frame.palette = Some(flat_palette);
+        frame.buffer = Cow::Owned(indices);
+        frame.delay = self.delay_centiseconds;
+
+        self.encoder
+            .write_frame(&frame)
+            .map_err(|e| anyhow!("Failed to write GIF frame: {}", e))?;
+
+        Ok(())
+    }
+}
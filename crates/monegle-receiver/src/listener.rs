@@ -6,11 +6,31 @@ use alloy::{
     rpc::types::BlockTransactionsKind,
 };
 use anyhow::{anyhow, Result};
+use futures::stream::FuturesUnordered;
 use futures::StreamExt;
-use monegle_core::FrameBatch;
+use monegle_core::{FrameBatch, FrameBatchCodec};
+use rand::Rng;
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::sync::Arc;
 use tokio::sync::mpsc;
-use tracing::{debug, info, warn, error};
+use tokio::sync::Semaphore;
+use tokio_util::codec::Decoder;
+use tracing::{debug, info, warn};
+
+use crate::endpoint_pool::EndpointPool;
+
+/// Initial reconnect backoff delay
+const RECONNECT_BASE_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Maximum reconnect backoff delay
+const RECONNECT_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How many blocks to fetch concurrently while backfilling a gap
+const BACKFILL_CONCURRENCY: usize = 4;
+
+/// Delay before retrying a missing or failed block fetch during backfill
+const BACKFILL_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
 
 /// Transaction listener component
 /// Uses WebSocket RPC subscriptions to monitor blockchain transactions in real-time
@@ -36,16 +56,109 @@ impl TransactionListener {
         })
     }
 
-    /// Start WebSocket subscription loop
+    /// Start WebSocket subscription loop with unlimited reconnect attempts
     /// Subscribes to new blocks and extracts frame batches from transactions
     pub async fn start_websocket_loop(
         self,
         ws_url: &str,
         batch_tx: mpsc::Sender<FrameBatch>,
     ) -> Result<()> {
-        info!("Starting WebSocket subscription loop");
-        info!("WebSocket URL: {}", ws_url);
+        self.start_websocket_loop_with_retries(ws_url, batch_tx, None).await
+    }
+
+    /// Start WebSocket subscription loop, reconnecting with exponential backoff
+    /// on stream end or provider errors instead of giving up.
+    ///
+    /// `max_retries` bounds how many consecutive reconnect attempts are allowed
+    /// before giving up and returning `Err`; `None` retries forever.
+    pub async fn start_websocket_loop_with_retries(
+        self,
+        ws_url: &str,
+        batch_tx: mpsc::Sender<FrameBatch>,
+        max_retries: Option<u32>,
+    ) -> Result<()> {
+        self.start_websocket_loop_pooled(&[ws_url.to_string()], batch_tx, max_retries).await
+    }
+
+    /// Start WebSocket subscription loop against a pool of candidate
+    /// endpoints. Reconnects rotate to the currently healthiest endpoint
+    /// (quarantining ones that just failed) instead of hammering the same
+    /// URL, so a single flaky RPC provider can't stall block monitoring.
+    pub async fn start_websocket_loop_pooled(
+        self,
+        ws_urls: &[String],
+        batch_tx: mpsc::Sender<FrameBatch>,
+        max_retries: Option<u32>,
+    ) -> Result<()> {
+        if ws_urls.is_empty() {
+            return Err(anyhow!("start_websocket_loop_pooled requires at least one WS URL"));
+        }
 
+        info!("Starting WebSocket subscription loop over {} endpoint(s)", ws_urls.len());
+
+        let mut pool = EndpointPool::new(ws_urls);
+        let mut last_block: Option<u64> = None;
+        let mut backoff = RECONNECT_BASE_DELAY;
+        let mut attempt = 0u32;
+
+        loop {
+            let idx = pool.ranked()[0];
+            let ws_url = pool.url(idx).to_string();
+            let block_before = last_block;
+            let session_start = std::time::Instant::now();
+
+            match self.run_websocket_session(&ws_url, &batch_tx, &mut last_block).await {
+                Ok(()) => {
+                    // Channel closed by the receiver side - nothing more to do
+                    return Ok(());
+                }
+                Err(e) => {
+                    if last_block != block_before {
+                        // We made progress on this endpoint before the disconnect
+                        pool.record_success(idx, session_start.elapsed().as_millis() as u64);
+                        attempt = 0;
+                        backoff = RECONNECT_BASE_DELAY;
+                    } else {
+                        let cooldown = pool.record_failure(idx);
+                        debug!("Endpoint {} quarantined for {:?}", ws_url, cooldown);
+                    }
+
+                    attempt += 1;
+
+                    if let Some(max) = max_retries {
+                        if attempt > max {
+                            return Err(anyhow!(
+                                "WebSocket listener giving up after {} reconnect attempts: {}",
+                                attempt - 1,
+                                e
+                            ));
+                        }
+                    }
+
+                    let jitter = std::time::Duration::from_millis(rand::thread_rng().gen_range(0..100));
+                    let sleep_for = backoff + jitter;
+
+                    warn!(
+                        "WebSocket session on {} ended ({}), reconnecting in {:?} (attempt {})",
+                        ws_url, e, sleep_for, attempt
+                    );
+                    tokio::time::sleep(sleep_for).await;
+
+                    backoff = (backoff * 2).min(RECONNECT_MAX_DELAY);
+                }
+            }
+        }
+    }
+
+    /// Connect, subscribe, and consume blocks until the stream ends or a
+    /// provider error occurs. Returns `Ok(())` only if the output channel
+    /// was closed by the receiver (a deliberate shutdown).
+    async fn run_websocket_session(
+        &self,
+        ws_url: &str,
+        batch_tx: &mpsc::Sender<FrameBatch>,
+        last_block: &mut Option<u64>,
+    ) -> Result<()> {
         // Connect via WebSocket
         let ws = WsConnect::new(ws_url);
         let provider = ProviderBuilder::new()
@@ -62,6 +175,9 @@ impl TransactionListener {
         let mut stream = sub.into_stream();
 
         info!("Subscribed to new blocks via WebSocket");
+        if let Some(block) = last_block {
+            info!("Resuming after last processed block {}", block);
+        }
 
         let mut block_count = 0u64;
         let mut last_heartbeat = std::time::Instant::now();
@@ -78,66 +194,127 @@ impl TransactionListener {
 
             debug!("New block: {}", block_number);
 
-            // Fetch full block with transactions
-            match provider.get_block_by_number(
-                BlockNumberOrTag::Number(block_number),
-                BlockTransactionsKind::Full
-            ).await {
-                Ok(Some(full_block)) => {
-                    // Extract transactions
-                    if let Some(txs) = full_block.transactions.as_transactions() {
-                        for tx in txs {
-                            // Filter: transaction must be FROM our sender address
-                            if tx.from == self.sender_address {
-                                debug!(
-                                    "Found transaction from sender: {:?}, to: {:?}, size: {} bytes",
-                                    tx.inner.tx_hash(),
-                                    tx.inner.to(),
-                                    tx.inner.input().len()
-                                );
-
-                                // Extract calldata
-                                let calldata = tx.inner.input().to_vec();
-
-                                if !calldata.is_empty() {
-                                    // Decode FrameBatch from calldata
-                                    match FrameBatch::decode_from_bytes(&calldata) {
-                                        Ok(batch) => {
-                                            info!(
-                                                "Received batch {} with {} frames from tx {:?}",
-                                                batch.sequence,
-                                                batch.frames.len(),
-                                                tx.inner.tx_hash()
-                                            );
-
-                                            if batch_tx.send(batch).await.is_err() {
-                                                warn!("WebSocket channel closed, stopping loop");
-                                                return Ok(());
-                                            }
-                                        }
-                                        Err(e) => {
-                                            warn!(
-                                                "Failed to decode batch from tx {:?}: {}",
-                                                tx.inner.tx_hash(), e
-                                            );
+            // Scan this block plus any gap since the last one we fully
+            // processed, so a slow subscriber or brief stall never silently
+            // skips blocks (and their frame batches)
+            let start = last_block.map(|b| b + 1).unwrap_or(block_number);
+            if start > block_number {
+                // Already caught up past this header (can happen right after
+                // a backfill) - nothing to do
+                continue;
+            }
+
+            let (highest_scanned, channel_closed) = self
+                .backfill_and_scan(&provider, batch_tx, start..=block_number)
+                .await?;
+            *last_block = Some(highest_scanned);
+
+            if channel_closed {
+                return Ok(());
+            }
+        }
+
+        Err(anyhow!("WebSocket stream ended unexpectedly"))
+    }
+
+    /// Fetch a contiguous block range with bounded concurrency, retrying
+    /// transient fetch errors instead of skipping them, then forward each
+    /// block's matching transactions to the channel strictly in block order.
+    ///
+    /// Returns the highest block number fully fetched and scanned, and
+    /// whether the output channel was closed partway through (in which case
+    /// the caller should stop).
+    async fn backfill_and_scan<P>(
+        &self,
+        provider: &P,
+        batch_tx: &mpsc::Sender<FrameBatch>,
+        range: std::ops::RangeInclusive<u64>,
+    ) -> Result<(u64, bool)>
+    where
+        P: Provider + Clone,
+    {
+        let span = *range.end() - *range.start() + 1;
+        if span > 1 {
+            warn!("Backfilling {} missed block(s): {}..={}", span, range.start(), range.end());
+        }
+
+        let semaphore = Arc::new(Semaphore::new(BACKFILL_CONCURRENCY));
+        let mut in_flight = FuturesUnordered::new();
+
+        for block_num in range.clone() {
+            let provider = provider.clone();
+            let semaphore = semaphore.clone();
+            in_flight.push(async move {
+                let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+                loop {
+                    match provider
+                        .get_block_by_number(BlockNumberOrTag::Number(block_num), BlockTransactionsKind::Full)
+                        .await
+                    {
+                        Ok(Some(block)) => return (block_num, block),
+                        Ok(None) => warn!("Block {} not found during backfill, retrying", block_num),
+                        Err(e) => warn!("Backfill fetch of block {} failed: {} (retrying)", block_num, e),
+                    }
+                    tokio::time::sleep(BACKFILL_RETRY_DELAY).await;
+                }
+            });
+        }
+
+        // Fetches race and complete out of order; buffer them until the next
+        // block in sequence is ready so batches always reach the channel in
+        // block order, and `last_block` only ever advances past a block once
+        // it has actually been scanned
+        let mut fetched = BTreeMap::new();
+        let mut next_to_emit = *range.start();
+
+        while let Some((block_num, block)) = in_flight.next().await {
+            fetched.insert(block_num, block);
+
+            while let Some(block) = fetched.remove(&next_to_emit) {
+                if let Some(txs) = block.transactions.as_transactions() {
+                    for tx in txs {
+                        // Filter: transaction must be FROM our sender address
+                        if tx.from == self.sender_address {
+                            let mut calldata = bytes::BytesMut::from(&tx.inner.input()[..]);
+
+                            if !calldata.is_empty() {
+                                match FrameBatchCodec::new().decode(&mut calldata) {
+                                    Ok(Some(batch)) => {
+                                        info!(
+                                            "Received batch {} with {} frames from tx {:?} (block {})",
+                                            batch.sequence,
+                                            batch.frames.len(),
+                                            tx.inner.tx_hash(),
+                                            next_to_emit
+                                        );
+
+                                        if batch_tx.send(batch).await.is_err() {
+                                            warn!("Channel closed mid-backfill, stopping");
+                                            return Ok((next_to_emit.saturating_sub(1), true));
                                         }
                                     }
+                                    Ok(None) => {
+                                        warn!(
+                                            "Truncated frame batch in tx {:?}, skipping",
+                                            tx.inner.tx_hash()
+                                        );
+                                    }
+                                    Err(e) => {
+                                        warn!(
+                                            "Failed to decode batch from tx {:?}: {}",
+                                            tx.inner.tx_hash(), e
+                                        );
+                                    }
                                 }
                             }
                         }
                     }
                 }
-                Ok(None) => {
-                    warn!("Block {} not found", block_number);
-                }
-                Err(e) => {
-                    error!("Failed to fetch block {}: {}", block_number, e);
-                }
+                next_to_emit += 1;
             }
         }
 
-        warn!("WebSocket stream ended unexpectedly");
-        Ok(())
+        Ok((next_to_emit - 1, false))
     }
 
     /// Start HTTP polling loop (fallback)
@@ -148,16 +325,35 @@ impl TransactionListener {
         batch_tx: mpsc::Sender<FrameBatch>,
         poll_interval_ms: u64,
     ) -> Result<()> {
-        info!("Starting HTTP polling loop (fallback mode)");
-        info!("HTTP RPC URL: {}", rpc_url);
+        self.start_polling_loop_pooled(&[rpc_url.to_string()], batch_tx, poll_interval_ms).await
+    }
+
+    /// Start HTTP polling loop over a pool of candidate RPC endpoints.
+    /// Each tick routes to the currently healthiest endpoint, quarantining
+    /// ones that error out; a quarantined endpoint is probed again by a
+    /// tick's `get_block_number` call once its cooldown expires (or sooner,
+    /// if every endpoint is quarantined).
+    pub async fn start_polling_loop_pooled(
+        self,
+        rpc_urls: &[String],
+        batch_tx: mpsc::Sender<FrameBatch>,
+        poll_interval_ms: u64,
+    ) -> Result<()> {
+        if rpc_urls.is_empty() {
+            return Err(anyhow!("start_polling_loop_pooled requires at least one RPC URL"));
+        }
+
+        info!("Starting HTTP polling loop (fallback mode) over {} endpoint(s)", rpc_urls.len());
         info!("Poll interval: {}ms", poll_interval_ms);
 
-        // Setup HTTP provider
-        let provider = ProviderBuilder::new()
-            .on_http(rpc_url.parse()?)
-;
+        let mut pool = EndpointPool::new(rpc_urls);
+        let providers: Vec<_> = rpc_urls
+            .iter()
+            .map(|url| -> Result<_> { Ok(ProviderBuilder::new().on_http(url.parse()?)) })
+            .collect::<Result<_>>()?;
 
-        let mut last_block = provider.get_block_number().await?;
+        let first = pool.ranked()[0];
+        let mut last_block = providers[first].get_block_number().await?;
         info!("Starting from block: {}", last_block);
 
         let mut interval = tokio::time::interval(
@@ -167,69 +363,45 @@ impl TransactionListener {
         loop {
             interval.tick().await;
 
+            let idx = pool.ranked()[0];
+            let provider = &providers[idx];
+            let start = std::time::Instant::now();
+
             // Get current block
             match provider.get_block_number().await {
                 Ok(current_block) => {
+                    pool.record_success(idx, start.elapsed().as_millis() as u64);
+
                     if current_block <= last_block {
                         continue;
                     }
 
                     debug!(
-                        "Polling blocks {} to {} for transactions from {}",
+                        "Polling blocks {} to {} for transactions from {} (via {})",
                         last_block + 1,
                         current_block,
-                        self.sender_address
+                        self.sender_address,
+                        rpc_urls[idx]
                     );
 
-                    // Process new blocks
-                    for block_num in (last_block + 1)..=current_block {
-                        match provider.get_block_by_number(
-                            BlockNumberOrTag::Number(block_num),
-                            BlockTransactionsKind::Full
-                        ).await {
-                            Ok(Some(block)) => {
-                                if let Some(txs) = block.transactions.as_transactions() {
-                                    for tx in txs {
-                                        // Filter: transaction FROM sender address
-                                        if tx.from == self.sender_address {
-                                            let calldata = tx.inner.input().to_vec();
-
-                                            if !calldata.is_empty() {
-                                                match FrameBatch::decode_from_bytes(&calldata) {
-                                                    Ok(batch) => {
-                                                        info!(
-                                                            "Received batch {} with {} frames",
-                                                            batch.sequence,
-                                                            batch.frames.len()
-                                                        );
-
-                                                        if batch_tx.send(batch).await.is_err() {
-                                                            warn!("Polling channel closed");
-                                                            return Ok(());
-                                                        }
-                                                    }
-                                                    Err(e) => {
-                                                        warn!("Failed to decode batch: {}", e);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            Ok(None) => {
-                                warn!("Block {} not found", block_num);
-                            }
-                            Err(e) => {
-                                error!("Failed to fetch block {}: {}", block_num, e);
-                            }
-                        }
-                    }
+                    // Fetch and scan the whole gap with bounded concurrency,
+                    // retrying failed/missing blocks rather than skipping
+                    // them - `last_block` only advances past what actually landed
+                    let (highest_scanned, channel_closed) = self
+                        .backfill_and_scan(provider, &batch_tx, (last_block + 1)..=current_block)
+                        .await?;
+                    last_block = highest_scanned;
 
-                    last_block = current_block;
+                    if channel_closed {
+                        return Ok(());
+                    }
                 }
                 Err(e) => {
-                    warn!("Failed to get block number: {}", e);
+                    let cooldown = pool.record_failure(idx);
+                    warn!(
+                        "Failed to get block number from {}: {} (quarantined for {:?})",
+                        rpc_urls[idx], e, cooldown
+                    );
                 }
             }
         }
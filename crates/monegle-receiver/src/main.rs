@@ -1,7 +1,14 @@
 mod listener;
+mod blockchain_receiver;
+mod reassembly;
 mod decoder;
+mod parallel_decoder;
 mod buffer;
+mod recorder;
+mod gif_export;
+mod replay;
 mod display;
+mod endpoint_pool;
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
@@ -11,7 +18,11 @@ use tracing::{error, info};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 use listener::TransactionListener;
+use blockchain_receiver::BlockchainReceiver;
+use reassembly::run_reassembly_loop;
 use decoder::FrameDecoder;
+use parallel_decoder::ParallelFrameDecoder;
+use replay::run_replay_mode;
 use display::TerminalDisplay;
 
 #[derive(Parser, Debug)]
@@ -26,17 +37,57 @@ struct Args {
     #[arg(short, long)]
     sender_address: Option<String>,
 
-    /// WebSocket URL (overrides config)
+    /// WebSocket URL (overrides config). Repeat the flag to pool multiple
+    /// endpoints with health-based rotation and failover, e.g.
+    /// `--ws-url wss://a --ws-url wss://b`
     #[arg(long)]
-    ws_url: Option<String>,
+    ws_url: Vec<String>,
+
+    /// HTTP RPC URL for polling mode (overrides config). Repeat the flag to
+    /// pool multiple endpoints, same as `--ws-url`
+    #[arg(long)]
+    rpc_url: Vec<String>,
 
     /// Use HTTP polling instead of WebSocket
     #[arg(long)]
     no_websocket: bool,
 
+    /// Disable the low-latency pending-transaction pubsub subscription
+    /// (only meaningful in WebSocket mode; the confirmed-block scanner
+    /// keeps running either way)
+    #[arg(long)]
+    no_pending_tx: bool,
+
     /// Disable terminal display (headless mode)
     #[arg(long)]
     no_display: bool,
+
+    /// Replay a previously recorded asciicast-v2 file instead of connecting
+    /// to the blockchain
+    #[arg(long)]
+    replay: Option<String>,
+
+    /// Show a live TUI dashboard of buffer/playback telemetry instead of
+    /// only logging it (only used with `--replay`)
+    #[arg(long)]
+    dashboard: bool,
+
+    /// Decode across a pool of worker tasks (sized from available CPU
+    /// cores) instead of strictly serially. Speeds up high-FPS streams on
+    /// multi-core machines; falls back to serial decoding around stream
+    /// joins and sequence gaps regardless
+    #[arg(long)]
+    parallel_decode: bool,
+
+    /// Record the session to this path as an asciicast v2 `.cast` file
+    /// instead of displaying it live
+    #[arg(long)]
+    record: Option<String>,
+
+    /// Export the session to this path as an animated GIF instead of
+    /// displaying it live
+    #[arg(long)]
+    export_gif: Option<String>,
 }
 
 #[tokio::main]
@@ -54,6 +105,27 @@ async fn main() -> Result<()> {
 
     info!("Monegle Receiver starting...");
 
+    if let Some(replay_path) = &args.replay {
+        const REPLAY_TARGET_FPS: f32 = 15.0;
+
+        info!("Replay mode: {}", replay_path);
+        let frame_rx = run_replay_mode(replay_path, REPLAY_TARGET_FPS, args.dashboard).await?;
+
+        if args.no_display {
+            info!("Headless mode - replaying frames without display");
+            let mut frame_rx = frame_rx;
+            while frame_rx.recv().await.is_some() {}
+        } else {
+            let display = TerminalDisplay::new(REPLAY_TARGET_FPS as u8, 80, 60, replay_path.clone());
+            if let Err(e) = display.start_display_loop(frame_rx).await {
+                error!("Display error: {}", e);
+            }
+        }
+
+        info!("Replay finished");
+        return Ok(());
+    }
+
     // Load configuration
     let config = Config::from_file(&args.config)?;
 
@@ -72,82 +144,183 @@ async fn main() -> Result<()> {
     // Initialize transaction listener
     let listener = TransactionListener::new(&sender_address)?;
 
-    // Create channels for pipeline
+    // Create channels for pipeline. Every raw source (confirmed-block scan,
+    // pending-tx pubsub) feeds `raw_tx`; the reassembly buffer turns that
+    // unordered, possibly-duplicated stream into the strictly-ordered
+    // `batch_tx` the decoder consumes.
+    let (raw_tx, raw_rx) = mpsc::channel::<FrameBatch>(32);
     let (batch_tx, mut batch_rx) = mpsc::channel::<FrameBatch>(10);
+    let (gap_tx, mut gap_rx) = mpsc::channel::<reassembly::GapDetected>(10);
     let (frame_tx, mut frame_rx) = mpsc::channel::<String>(100);
 
     // Determine connection method
     let use_websocket = !args.no_websocket && receiver_config.use_websocket;
 
     if use_websocket {
-        let ws_url = args.ws_url
-            .or(Some(network_config.ws_url.clone()))
-            .ok_or_else(|| anyhow!("WebSocket URL not specified"))?;
+        let ws_urls = if args.ws_url.is_empty() {
+            vec![network_config.ws_url.clone()]
+        } else {
+            args.ws_url.clone()
+        };
 
-        info!("Using WebSocket subscription: {}", ws_url);
+        info!("Using WebSocket subscription over {} endpoint(s): {}", ws_urls.len(), ws_urls.join(", "));
 
-        // Spawn WebSocket listener
-        let batch_tx_clone = batch_tx.clone();
+        // Spawn the confirmed-block scanner (reliable, but confirmation-latency bound)
+        let raw_tx_clone = raw_tx.clone();
         tokio::spawn(async move {
-            if let Err(e) = listener.start_websocket_loop(&ws_url, batch_tx_clone).await {
+            if let Err(e) = listener.start_websocket_loop_pooled(&ws_urls, raw_tx_clone, None).await {
                 error!("WebSocket listener error: {}", e);
             }
         });
+
+        // Spawn the pending-tx pubsub subscriber (low latency, unordered) on
+        // the first endpoint, unless disabled
+        if !args.no_pending_tx && receiver_config.pending_tx_subscription {
+            let pending_ws_url = if args.ws_url.is_empty() {
+                network_config.ws_url.clone()
+            } else {
+                args.ws_url[0].clone()
+            };
+            let pending_receiver = BlockchainReceiver::new(&sender_address)?;
+            let raw_tx_clone = raw_tx.clone();
+            tokio::spawn(async move {
+                if let Err(e) = pending_receiver.start_pubsub_loop(&pending_ws_url, raw_tx_clone).await {
+                    error!("Pending-tx subscription error: {}", e);
+                }
+            });
+        }
     } else {
-        let rpc_url = network_config.rpc_url.clone();
+        let rpc_urls = if args.rpc_url.is_empty() {
+            vec![network_config.rpc_url.clone()]
+        } else {
+            args.rpc_url.clone()
+        };
         let poll_interval = receiver_config.polling_interval;
 
-        info!("Using HTTP polling: {} (interval: {}ms)", rpc_url, poll_interval);
+        info!(
+            "Using HTTP polling over {} endpoint(s): {} (interval: {}ms)",
+            rpc_urls.len(), rpc_urls.join(", "), poll_interval
+        );
 
         // Spawn HTTP polling listener
-        let batch_tx_clone = batch_tx.clone();
+        let raw_tx_clone = raw_tx.clone();
         tokio::spawn(async move {
-            if let Err(e) = listener.start_polling_loop(&rpc_url, batch_tx_clone, poll_interval).await {
+            if let Err(e) = listener.start_polling_loop_pooled(&rpc_urls, raw_tx_clone, poll_interval).await {
                 error!("Polling listener error: {}", e);
             }
         });
     }
 
-    // Spawn decoder task
-    let mut last_metadata: Option<monegle_core::StreamMetadata> = None;
+    drop(raw_tx);
+
+    // Spawn the reassembly buffer that turns the raw, unordered stream into
+    // a strictly sequence-ordered one for the decoder
     tokio::spawn(async move {
-        let mut decoder = FrameDecoder::new();
-
-        while let Some(batch) = batch_rx.recv().await {
-            // Log metadata changes
-            if last_metadata.as_ref() != Some(&batch.metadata) {
-                info!(
-                    "Stream metadata: {}x{} @ {} FPS, charset: {:?}, color: {:?}, compression: {:?}",
-                    batch.metadata.width,
-                    batch.metadata.height,
-                    batch.metadata.fps,
-                    batch.metadata.character_set,
-                    batch.metadata.color_mode,
-                    batch.metadata.compression_type
-                );
-                last_metadata = Some(batch.metadata.clone());
+        if let Err(e) = run_reassembly_loop(raw_rx, batch_tx, gap_tx).await {
+            error!("Reassembly buffer error: {}", e);
+        }
+    });
+
+    // Log gaps as they're reported rather than silently dropping them
+    tokio::spawn(async move {
+        while let Some(gap) = gap_rx.recv().await {
+            error!(
+                "Gap detected: sequence {} never arrived within {:?}, skipped",
+                gap.expected_sequence, gap.waited
+            );
+        }
+    });
+
+    // Spawn decoder task
+    if args.parallel_decode {
+        let (decoded_tx, mut decoded_rx) = mpsc::channel::<(monegle_core::StreamMetadata, u64, Vec<String>)>(10);
+
+        tokio::spawn(async move {
+            let decoder = ParallelFrameDecoder::new();
+            if let Err(e) = decoder.start_decoding_loop(batch_rx, decoded_tx).await {
+                error!("Parallel decoder error: {}", e);
             }
+        });
 
-            match decoder.decode_batch(&batch) {
-                Ok(frames) => {
-                    for frame in frames {
-                        if frame_tx.send(frame).await.is_err() {
-                            info!("Frame channel closed, stopping decoder");
-                            return;
-                        }
+        tokio::spawn(async move {
+            while let Some((_metadata, _sequence, frames)) = decoded_rx.recv().await {
+                for frame in frames {
+                    if frame_tx.send(frame).await.is_err() {
+                        info!("Frame channel closed, stopping decoder");
+                        return;
                     }
                 }
-                Err(e) => {
-                    error!("Failed to decode batch {}: {}", batch.sequence, e);
+            }
+
+            info!("Decoder stopped");
+        });
+    } else {
+        let mut last_metadata: Option<monegle_core::StreamMetadata> = None;
+        tokio::spawn(async move {
+            let mut decoder = FrameDecoder::new();
+
+            while let Some(batch) = batch_rx.recv().await {
+                // Log metadata changes
+                if last_metadata.as_ref() != Some(&batch.metadata) {
+                    info!(
+                        "Stream metadata: {}x{} @ {} FPS, charset: {:?}, color: {:?}, compression: {:?}",
+                        batch.metadata.width,
+                        batch.metadata.height,
+                        batch.metadata.fps,
+                        batch.metadata.character_set,
+                        batch.metadata.color_mode,
+                        batch.metadata.compression_type
+                    );
+                    last_metadata = Some(batch.metadata.clone());
+                }
+
+                match decoder.decode_batch(&batch) {
+                    Ok(frames) => {
+                        for frame in frames {
+                            if frame_tx.send(frame).await.is_err() {
+                                info!("Frame channel closed, stopping decoder");
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to decode batch {}: {}", batch.sequence, e);
+                    }
                 }
             }
-        }
 
-        info!("Decoder stopped");
-    });
+            info!("Decoder stopped");
+        });
+    }
 
     // Display frames
-    if !args.no_display && receiver_config.display_terminal {
+    if let Some(record_path) = &args.record {
+        // Use sensible defaults for recording parameters
+        // (These will be overridden by actual metadata from the stream)
+        let display = TerminalDisplay::new(
+            15,  // Default FPS (will be updated from metadata)
+            80,  // Default width (will be updated from metadata)
+            60,  // Default height (will be updated from metadata)
+            sender_address.clone(),  // Stream ID
+        );
+
+        if let Err(e) = display.start_recording_mode(frame_rx, record_path).await {
+            error!("Recording error: {}", e);
+        }
+    } else if let Some(gif_path) = &args.export_gif {
+        // Use sensible defaults for export parameters
+        // (These will be overridden by actual metadata from the stream)
+        let display = TerminalDisplay::new(
+            15,  // Default FPS (will be updated from metadata)
+            80,  // Default width (will be updated from metadata)
+            60,  // Default height (will be updated from metadata)
+            sender_address.clone(),  // Stream ID
+        );
+
+        if let Err(e) = display.start_gif_export_mode(frame_rx, gif_path, monegle_core::CharacterSet::Standard).await {
+            error!("GIF export error: {}", e);
+        }
+    } else if !args.no_display && receiver_config.display_terminal {
         info!("Starting terminal display");
         info!("Waiting for stream metadata from first batch...");
 
@@ -0,0 +1,296 @@
+use anyhow::Result;
+use monegle_core::{decode_frame, CompressedFrame, FrameBatch, StreamMetadata};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::decoder::FrameDecoder;
+
+/// A single frame plus the batch-level context needed to re-emit it in its
+/// original `(metadata, sequence, Vec<String>)` grouping once decoded.
+#[derive(Clone)]
+struct TaggedFrame {
+    metadata: StreamMetadata,
+    batch_sequence: u64,
+    frame: CompressedFrame,
+}
+
+/// A contiguous, self-contained run of frames starting at an
+/// `is_keyframe` frame: the first frame decodes with no predecessor, and
+/// every later frame chains off the one before it in this same run.
+/// Because it owns its own keyframe, a GOP decodes independently of every
+/// other GOP, making it the unit of work handed to the worker pool.
+struct Gop {
+    frames: Vec<TaggedFrame>,
+}
+
+/// Decode every frame in a GOP, chaining each delta frame off the one
+/// before it. A failed frame leaves the running `previous` untouched -
+/// matching `FrameDecoder::decode_frame`, later frames in the run keep
+/// chaining off the last *successful* decode rather than the failed one.
+fn decode_gop(gop: Gop) -> Vec<(TaggedFrame, Result<String>)> {
+    let mut previous: Option<String> = None;
+
+    gop.frames
+        .into_iter()
+        .map(|tagged| {
+            let result = decode_frame(
+                &tagged.frame,
+                if tagged.frame.is_keyframe { None } else { previous.as_deref() },
+            );
+            if let Ok(decoded) = &result {
+                previous = Some(decoded.clone());
+            }
+            (tagged, result)
+        })
+        .collect()
+}
+
+/// A decoded unit (GOP or lone fallback frame) tagged with the
+/// monotonically increasing dispatch order it was created in, so the
+/// collector can reassemble units that finish decoding out of order
+/// across the worker pool.
+struct DecodedUnit {
+    order: u64,
+    frames: Vec<(TaggedFrame, Result<String>)>,
+}
+
+impl PartialEq for DecodedUnit {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order
+    }
+}
+impl Eq for DecodedUnit {}
+impl PartialOrd for DecodedUnit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for DecodedUnit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order.cmp(&other.order)
+    }
+}
+
+/// Hand a GOP to the next worker in round-robin order. Returns `false` if
+/// every worker has shut down.
+async fn dispatch_gop(
+    gop: Gop,
+    worker_txs: &[mpsc::Sender<(u64, Gop)>],
+    next_worker: &mut usize,
+    next_order: &mut u64,
+) -> bool {
+    let worker = &worker_txs[*next_worker % worker_txs.len()];
+    *next_worker = next_worker.wrapping_add(1);
+
+    let order = *next_order;
+    *next_order += 1;
+
+    worker.send((order, gop)).await.is_ok()
+}
+
+/// Decode a single frame serially against the fallback decoder's own
+/// running state and forward the result straight to the collector.
+async fn dispatch_fallback(
+    tagged: TaggedFrame,
+    serial: &mut FrameDecoder,
+    result_tx: &mpsc::Sender<DecodedUnit>,
+    next_order: &mut u64,
+) -> bool {
+    let result = serial.decode_frame(&tagged.frame);
+
+    let order = *next_order;
+    *next_order += 1;
+
+    result_tx
+        .send(DecodedUnit {
+            order,
+            frames: vec![(tagged, result)],
+        })
+        .await
+        .is_ok()
+}
+
+/// Decodes `FrameBatch`es across a pool of worker tasks by exploiting the
+/// keyframe structure: a GOP (a keyframe and the delta frames chained off
+/// it) decodes independently of every other GOP, so whole GOPs are handed
+/// round-robin to a fixed-size worker pool and reassembled in order
+/// afterwards. A frame that can't be isolated into a self-contained GOP -
+/// one that arrives before the stream's first keyframe, or one whose
+/// delta chain a sequence gap has broken - falls back to a serial
+/// `FrameDecoder`, which never sees the keyframes the GOPs own and so
+/// rejects these delta frames, until the next keyframe re-establishes an
+/// independent GOP.
+pub struct ParallelFrameDecoder {
+    worker_count: usize,
+}
+
+impl ParallelFrameDecoder {
+    pub fn new() -> Self {
+        let worker_count = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        info!("Parallel frame decoder starting with {} worker(s)", worker_count);
+        Self { worker_count }
+    }
+
+    /// Run the parallel decoding loop until `rx` closes, emitting decoded
+    /// batches on `tx` in the same `(metadata, sequence, frames)` shape as
+    /// `FrameDecoder::start_decoding_loop`.
+    pub async fn start_decoding_loop(
+        self,
+        mut rx: mpsc::Receiver<FrameBatch>,
+        tx: mpsc::Sender<(StreamMetadata, u64, Vec<String>)>,
+    ) -> Result<()> {
+        info!("Starting parallel decoding loop");
+
+        let (result_tx, mut result_rx) = mpsc::channel::<DecodedUnit>(self.worker_count * 4);
+        let mut worker_txs = Vec::with_capacity(self.worker_count);
+
+        for _ in 0..self.worker_count {
+            let (worker_tx, mut worker_rx) = mpsc::channel::<(u64, Gop)>(4);
+            let result_tx = result_tx.clone();
+
+            tokio::spawn(async move {
+                while let Some((order, gop)) = worker_rx.recv().await {
+                    let frames = decode_gop(gop);
+                    if result_tx.send(DecodedUnit { order, frames }).await.is_err() {
+                        break;
+                    }
+                }
+            });
+
+            worker_txs.push(worker_tx);
+        }
+
+        let reassembly_tx = tx.clone();
+        let reassembly = tokio::spawn(async move {
+            let mut heap: BinaryHeap<Reverse<DecodedUnit>> = BinaryHeap::new();
+            let mut next_order = 0u64;
+            let mut current_batch: Option<(StreamMetadata, u64, Vec<String>)> = None;
+            let mut decoded_count = 0u64;
+
+            while let Some(unit) = result_rx.recv().await {
+                heap.push(Reverse(unit));
+
+                while matches!(heap.peek(), Some(Reverse(unit)) if unit.order == next_order) {
+                    let Reverse(unit) = heap.pop().unwrap();
+                    next_order += 1;
+
+                    for (tagged, result) in unit.frames {
+                        match result {
+                            Ok(decoded) => {
+                                decoded_count += 1;
+                                if decoded_count % 100 == 0 {
+                                    info!("Decoded {} frames", decoded_count);
+                                }
+
+                                match &mut current_batch {
+                                    Some((_, seq, frames)) if *seq == tagged.batch_sequence => {
+                                        frames.push(decoded);
+                                    }
+                                    _ => {
+                                        if let Some(batch) = current_batch.take() {
+                                            if reassembly_tx.send(batch).await.is_err() {
+                                                return;
+                                            }
+                                        }
+                                        current_batch =
+                                            Some((tagged.metadata, tagged.batch_sequence, vec![decoded]));
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                warn!(
+                                    "Failed to decode frame {} in batch {}: {}",
+                                    tagged.frame.frame_number, tagged.batch_sequence, e
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(batch) = current_batch.take() {
+                let _ = reassembly_tx.send(batch).await;
+            }
+
+            info!("Parallel decoding loop stopped after {} frames", decoded_count);
+        });
+
+        let mut serial = FrameDecoder::new();
+        let mut open_gop: Option<Gop> = None;
+        let mut fallback_mode = true; // no keyframe anchor seen yet
+        let mut last_frame_number: Option<u64> = None;
+        let mut next_worker = 0usize;
+        let mut next_dispatch_order = 0u64;
+        let mut last_metadata: Option<StreamMetadata> = None;
+
+        'outer: while let Some(batch) = rx.recv().await {
+            if last_metadata.as_ref() != Some(&batch.metadata) {
+                info!(
+                    "Stream metadata: {}x{} @ {} FPS, charset: {:?}, color: {:?}, compression: {:?}",
+                    batch.metadata.width,
+                    batch.metadata.height,
+                    batch.metadata.fps,
+                    batch.metadata.character_set,
+                    batch.metadata.color_mode,
+                    batch.metadata.compression_type
+                );
+                last_metadata = Some(batch.metadata.clone());
+            }
+
+            for frame in &batch.frames {
+                let tagged = TaggedFrame {
+                    metadata: batch.metadata.clone(),
+                    batch_sequence: batch.sequence,
+                    frame: frame.clone(),
+                };
+
+                let contiguous = last_frame_number.map(|n| frame.frame_number == n + 1).unwrap_or(false);
+                last_frame_number = Some(frame.frame_number);
+
+                if frame.is_keyframe {
+                    if let Some(gop) = open_gop.take() {
+                        if !dispatch_gop(gop, &worker_txs, &mut next_worker, &mut next_dispatch_order).await {
+                            break 'outer;
+                        }
+                    }
+                    open_gop = Some(Gop { frames: vec![tagged] });
+                    fallback_mode = false;
+                } else if !fallback_mode && contiguous {
+                    open_gop
+                        .as_mut()
+                        .expect("fallback_mode false implies an open GOP")
+                        .frames
+                        .push(tagged);
+                } else {
+                    if let Some(gop) = open_gop.take() {
+                        if !dispatch_gop(gop, &worker_txs, &mut next_worker, &mut next_dispatch_order).await {
+                            break 'outer;
+                        }
+                    }
+                    fallback_mode = true;
+                    if !dispatch_fallback(tagged, &mut serial, &result_tx, &mut next_dispatch_order).await {
+                        break 'outer;
+                    }
+                }
+            }
+        }
+
+        if let Some(gop) = open_gop.take() {
+            let _ = dispatch_gop(gop, &worker_txs, &mut next_worker, &mut next_dispatch_order).await;
+        }
+
+        drop(worker_txs);
+        drop(result_tx);
+        let _ = reassembly.await;
+
+        Ok(())
+    }
+}
+
+impl Default for ParallelFrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
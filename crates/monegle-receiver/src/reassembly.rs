@@ -0,0 +1,121 @@
+use anyhow::Result;
+use monegle_core::FrameBatch;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// How long the buffer waits for a missing sequence number before giving up
+/// on it and moving on, rather than stalling the stream forever
+const GAP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Emitted when a sequence number never arrives within `GAP_TIMEOUT` and the
+/// buffer has to skip past it to keep the stream moving
+#[derive(Debug, Clone)]
+pub struct GapDetected {
+    /// The sequence number that was expected but never arrived
+    pub expected_sequence: u64,
+    /// How long the buffer waited before giving up on it
+    pub waited: Duration,
+}
+
+/// Reorders `FrameBatch`es by `sequence` before they reach the decoder.
+///
+/// Batches can arrive out of order (and even duplicated) because they come
+/// from more than one source feeding the same raw channel - the pending-tx
+/// pubsub subscription in [`crate::blockchain_receiver::BlockchainReceiver`]
+/// delivers low-latency but unordered batches, while the confirmed-block
+/// scanner in [`crate::listener::TransactionListener`] delivers the same
+/// batches again, in block order, as a reliability backstop. This buffer
+/// merges both: batches are held until every earlier sequence has been
+/// emitted, a sequence seen twice is dropped the second time, and a
+/// sequence that never shows up within `GAP_TIMEOUT` is skipped (after
+/// reporting a [`GapDetected`] event) so one lost batch can't wedge the
+/// whole stream. A batch that fails its [`FrameBatch::verify`] CRC check
+/// is logged and dropped on arrival, before it can occupy a sequence slot
+/// or be mistaken for the genuine batch that should fill it.
+pub async fn run_reassembly_loop(
+    mut raw_rx: mpsc::Receiver<FrameBatch>,
+    batch_tx: mpsc::Sender<FrameBatch>,
+    gap_tx: mpsc::Sender<GapDetected>,
+) -> Result<()> {
+    info!("Starting reassembly buffer (gap timeout: {:?})", GAP_TIMEOUT);
+
+    let mut buffered: BTreeMap<u64, FrameBatch> = BTreeMap::new();
+    let mut next_sequence: Option<u64> = None;
+    let mut gap_deadline: Option<Instant> = None;
+
+    loop {
+        let expected_before = next_sequence;
+        let wait_for_gap = async {
+            match gap_deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            maybe_batch = raw_rx.recv() => {
+                let Some(batch) = maybe_batch else {
+                    debug!("Raw batch channel closed, stopping reassembly buffer");
+                    return Ok(());
+                };
+
+                if let Err(e) = batch.verify() {
+                    warn!("Dropping corrupt batch {}: {}", batch.sequence, e);
+                    continue;
+                }
+
+                let expected = *next_sequence.get_or_insert(batch.sequence);
+
+                if batch.sequence < expected {
+                    debug!("Dropping duplicate batch {} (already emitted)", batch.sequence);
+                    continue;
+                }
+
+                let sequence = batch.sequence;
+                if buffered.insert(sequence, batch).is_some() {
+                    debug!("Dropping duplicate batch {} (already buffered)", sequence);
+                }
+            }
+            _ = wait_for_gap => {
+                let expected = next_sequence.expect("gap deadline only set once a sequence is pending");
+                warn!(
+                    "Sequence {} never arrived within {:?}, skipping it",
+                    expected, GAP_TIMEOUT
+                );
+
+                if gap_tx.send(GapDetected { expected_sequence: expected, waited: GAP_TIMEOUT }).await.is_err() {
+                    debug!("Gap event channel closed");
+                }
+
+                next_sequence = Some(expected + 1);
+            }
+        }
+
+        while let Some(expected) = next_sequence {
+            let Some(batch) = buffered.remove(&expected) else {
+                break;
+            };
+
+            if batch_tx.send(batch).await.is_err() {
+                debug!("Ordered batch channel closed, stopping reassembly buffer");
+                return Ok(());
+            }
+
+            next_sequence = Some(expected + 1);
+        }
+
+        // Restart the timeout only when the awaited sequence changes, so a
+        // steady flow of later batches can't keep postponing the skip
+        if next_sequence != expected_before {
+            gap_deadline = None;
+        }
+        gap_deadline = if next_sequence.is_some() && !buffered.is_empty() {
+            Some(gap_deadline.unwrap_or_else(|| Instant::now() + GAP_TIMEOUT))
+        } else {
+            None
+        };
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use serde_json::json;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Records playback frames to an asciicast v2 file: a JSON header line
+/// followed by one `[elapsed_seconds, "o", frame]` event per frame (see
+/// https://docs.asciinema.org/manual/asciicast/v2/). Lets a live session be
+/// captured for offline inspection and later fed back through
+/// `run_replay_mode` for deterministic replay.
+pub struct AsciicastRecorder {
+    writer: BufWriter<File>,
+}
+
+impl AsciicastRecorder {
+    pub fn new(path: &str, width: u16, height: u16) -> Result<Self> {
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let header = json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": timestamp,
+        });
+        writeln!(writer, "{}", header)?;
+
+        Ok(Self { writer })
+    }
+
+    /// Append a frame, `elapsed` measured from playback start
+    pub fn record_frame(&mut self, frame: &str, elapsed: Duration) -> Result<()> {
+        let event = json!([elapsed.as_secs_f64(), "o", frame]);
+        writeln!(self.writer, "{}", event)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use anyhow::{anyhow, Result};
+use monegle_core::dashboard::Dashboard;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::buffer::BufferController;
+
+/// Buffer capacity / initial buffering depth used for replay
+const REPLAY_BUFFER_CAPACITY: usize = 30;
+const REPLAY_INITIAL_BUFFER_BATCHES: usize = 1;
+
+/// Read an asciicast v2 file written by `AsciicastRecorder` and replay it
+/// through the same buffering/adaptive-FPS path a live stream uses, so
+/// recorded sessions (including counter test-mode captures) are
+/// reproducible without a blockchain connection. Each asciicast frame
+/// becomes its own single-frame batch; returns the resulting frame stream
+/// for the caller to display the same way it would a live one. When
+/// `dashboard` is set, the buffering loop renders its telemetry into a
+/// live TUI instead of only logging it.
+pub async fn run_replay_mode(path: &str, target_fps: f32, dashboard: bool) -> Result<mpsc::Receiver<String>> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open replay file {}: {}", path, e))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header_line = lines
+        .next()
+        .ok_or_else(|| anyhow!("Empty asciicast file: {}", path))??;
+    let header: Value = serde_json::from_str(&header_line)
+        .map_err(|e| anyhow!("Malformed asciicast header in {}: {}", path, e))?;
+
+    let width = header["width"].as_u64().ok_or_else(|| anyhow!("Malformed asciicast width in {}", path))? as u16;
+    let height = header["height"].as_u64().ok_or_else(|| anyhow!("Malformed asciicast height in {}", path))? as u16;
+
+    info!(
+        "Replaying {} (asciicast v{}, {}x{})",
+        path, header["version"], width, height
+    );
+
+    let (batch_tx, batch_rx) = mpsc::channel::<(u64, Vec<(String, bool)>)>(32);
+    let (frame_tx, frame_rx) = mpsc::channel::<String>(100);
+
+    tokio::spawn(async move {
+        let mut sequence = 0u64;
+
+        for line in lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    warn!("Failed to read replay line: {}", e);
+                    break;
+                }
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let event: Value = match serde_json::from_str(&line) {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("Skipping malformed asciicast event: {}", e);
+                    continue;
+                }
+            };
+
+            let Some(frame) = event.get(2).and_then(Value::as_str) else {
+                warn!("Skipping asciicast event with no frame payload: {}", line);
+                continue;
+            };
+
+            // Each asciicast frame is a complete, independently rendered
+            // screen (that's what `next_frame` recorded), so it's always
+            // replayed as a keyframe rather than a cell diff
+            if batch_tx.send((sequence, vec![(frame.to_string(), true)])).await.is_err() {
+                break;
+            }
+            sequence += 1;
+        }
+
+        info!("Replay source exhausted after {} frames", sequence);
+    });
+
+    let dashboard = if dashboard {
+        Some(Dashboard::enter(format!("Replay: {}", path))?)
+    } else {
+        None
+    };
+
+    let controller = BufferController::new(REPLAY_BUFFER_CAPACITY, REPLAY_INITIAL_BUFFER_BATCHES, width, height);
+    tokio::spawn(async move {
+        if let Err(e) = controller.start_buffering_loop(batch_rx, frame_tx, target_fps, None, dashboard).await {
+            error!("Replay buffering loop error: {}", e);
+        }
+    });
+
+    Ok(frame_rx)
+}
@@ -0,0 +1,192 @@
+use anyhow::{anyhow, Result};
+use monegle_core::StreamId;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+use crate::rpc_pool::RpcPool;
+
+/// Shared knobs the control server mutates and the batch-submission loop in
+/// `main.rs` polls each iteration - a lightweight stand-in for the
+/// `Arc<Mutex<…>>` handles a real capture/convert/batch pipeline would wire
+/// a control plane through.
+pub struct PipelineControl {
+    pool: Arc<RpcPool>,
+    stream_id: StreamId,
+    width: u16,
+    height: u16,
+    fps: AtomicU8,
+    paused: AtomicBool,
+}
+
+impl PipelineControl {
+    pub fn new(pool: Arc<RpcPool>, stream_id: StreamId, width: u16, height: u16, fps: u8) -> Self {
+        Self {
+            pool,
+            stream_id,
+            width,
+            height,
+            fps: AtomicU8::new(fps),
+            paused: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether the submission loop should skip generating/submitting the
+    /// next batch this tick
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Target FPS the submission loop should derive `frames_per_batch` from
+    /// on its next tick
+    pub fn fps(&self) -> u8 {
+        self.fps.load(Ordering::Relaxed)
+    }
+}
+
+/// Bind `addr` and serve control-plane connections until the process exits.
+/// Each connection speaks newline-delimited JSON-RPC 2.0: one request object
+/// per line in, one response object per line out.
+pub async fn run_control_server(addr: &str, control: Arc<PipelineControl>) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow!("Failed to bind control server on {}: {}", addr, e))?;
+    info!("Control server listening on {}", addr);
+
+    loop {
+        let (socket, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                warn!("Control server accept failed: {}", e);
+                continue;
+            }
+        };
+
+        debug!("Control server: connection from {}", peer);
+        let control = control.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(socket, control).await {
+                debug!("Control server connection {} ended: {}", peer, e);
+            }
+        });
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcErrorBody>,
+    id: Value,
+}
+
+#[derive(Serialize)]
+struct RpcErrorBody {
+    code: i32,
+    message: String,
+}
+
+async fn handle_connection(socket: TcpStream, control: Arc<PipelineControl>) -> Result<()> {
+    let (read_half, mut write_half) = socket.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(request) => {
+                let id = request.id.clone();
+                match dispatch(&control, &request.method, request.params).await {
+                    Ok(result) => RpcResponse { jsonrpc: "2.0", result: Some(result), error: None, id },
+                    Err(e) => RpcResponse {
+                        jsonrpc: "2.0",
+                        result: None,
+                        error: Some(RpcErrorBody { code: -32000, message: e.to_string() }),
+                        id,
+                    },
+                }
+            }
+            Err(e) => RpcResponse {
+                jsonrpc: "2.0",
+                result: None,
+                error: Some(RpcErrorBody { code: -32700, message: format!("Parse error: {}", e) }),
+                id: Value::Null,
+            },
+        };
+
+        let mut payload = serde_json::to_vec(&response)?;
+        payload.push(b'\n');
+        write_half.write_all(&payload).await?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch a single JSON-RPC method: `get_metrics`, `get_summary`, and
+/// `get_stream_info` are read-only snapshots of the running sender;
+/// `pause`/`resume`/`set_fps` mutate the shared `PipelineControl` the
+/// submission loop polls
+async fn dispatch(control: &PipelineControl, method: &str, params: Value) -> Result<Value> {
+    match method {
+        "get_metrics" => Ok(serde_json::to_value(control.pool.get_metrics().await)?),
+        "get_summary" => {
+            let metrics = control.pool.get_metrics().await;
+            let latency = control.pool.latency_summary().await;
+            let successful = metrics.iter().filter(|m| m.success).count();
+            Ok(serde_json::json!({
+                "total": metrics.len(),
+                "successful": successful,
+                "failed": metrics.len() - successful,
+                "latency": latency,
+            }))
+        }
+        "get_stream_info" => Ok(serde_json::json!({
+            "stream_id": hex::encode(control.stream_id),
+            "sender_address": format!("{:?}", control.pool.sender_address()),
+            "fps": control.fps(),
+            "width": control.width,
+            "height": control.height,
+            "paused": control.is_paused(),
+        })),
+        "pause" => {
+            control.paused.store(true, Ordering::Relaxed);
+            info!("Control server: pipeline paused");
+            Ok(serde_json::json!({ "paused": true }))
+        }
+        "resume" => {
+            control.paused.store(false, Ordering::Relaxed);
+            info!("Control server: pipeline resumed");
+            Ok(serde_json::json!({ "paused": false }))
+        }
+        "set_fps" => {
+            let fps = params
+                .get("fps")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| anyhow!("set_fps requires a \"fps\" parameter"))?;
+            let fps: u8 = fps
+                .try_into()
+                .map_err(|_| anyhow!("fps must fit in a u8"))?;
+            control.fps.store(fps, Ordering::Relaxed);
+            info!("Control server: target FPS set to {}", fps);
+            Ok(serde_json::json!({ "fps": fps }))
+        }
+        other => Err(anyhow!("Unknown method: {}", other)),
+    }
+}
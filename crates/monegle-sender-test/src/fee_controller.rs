@@ -0,0 +1,210 @@
+use anyhow::Result;
+use std::collections::VecDeque;
+
+/// Multiplicative step applied to the priority tip when the previous batch
+/// missed its one-block inclusion budget
+const ESCALATION_FACTOR: f64 = 1.25;
+
+/// Multiplicative step applied to decay the tip back toward the feeHistory
+/// baseline once batches are landing within budget again
+const DECAY_FACTOR: f64 = 0.9;
+
+/// Number of trailing blocks sampled from `eth_feeHistory`
+const FEE_HISTORY_BLOCK_COUNT: u64 = 10;
+
+/// How many of the most recent confirmation latencies `CongestionTracker`
+/// keeps around to compute its median from - long enough to smooth over a
+/// single slow block, short enough to react to mempool conditions changing
+/// within a few seconds of streaming
+const CONGESTION_WINDOW: usize = 20;
+
+/// Multiplicative step applied to the congestion multiplier when the recent
+/// median latency exceeds target, analogous to a compute-unit-price
+/// escalation strategy
+const CONGESTION_GROWTH_FACTOR: f64 = 1.25;
+
+/// Multiplicative step applied to decay the congestion multiplier back
+/// toward `CONGESTION_FLOOR` once latency has stayed on target for
+/// `CONGESTION_DECAY_STREAK` consecutive batches
+const CONGESTION_DECAY_FACTOR: f64 = 0.9;
+
+/// Consecutive on-target batches required before the congestion multiplier
+/// is allowed to decay a step
+const CONGESTION_DECAY_STREAK: u32 = 5;
+
+/// Floor and ceiling the congestion multiplier is clamped to - it can never
+/// discount the baseline tip, and never inflate it past 8x
+const CONGESTION_FLOOR: f64 = 1.0;
+const CONGESTION_CEILING: f64 = 8.0;
+
+/// Tracks a sliding window of recent end-to-end confirmation latencies and
+/// derives a multiplier applied to the priority tip: when the window's
+/// median latency exceeds `target_latency_ms` (set from the stream's own
+/// FPS - see `RpcClient::new_with_fees`), the multiplier escalates by
+/// `CONGESTION_GROWTH_FACTOR`; once it's stayed on target for
+/// `CONGESTION_DECAY_STREAK` consecutive batches, the multiplier decays
+/// back down by `CONGESTION_DECAY_FACTOR`. Complements `FeeController`'s
+/// per-batch `on_missed_inclusion`/`on_immediate_inclusion` escalation
+/// (which reacts within a single submission's stall/resubmit loop) with a
+/// trend-level signal that shifts where *new* batches start bidding from.
+pub struct CongestionTracker {
+    target_latency_ms: u64,
+    window: VecDeque<u64>,
+    multiplier: f64,
+    on_target_streak: u32,
+}
+
+impl CongestionTracker {
+    pub fn new(target_latency_ms: u64) -> Self {
+        Self {
+            target_latency_ms: target_latency_ms.max(1),
+            window: VecDeque::with_capacity(CONGESTION_WINDOW),
+            multiplier: CONGESTION_FLOOR,
+            on_target_streak: 0,
+        }
+    }
+
+    /// Current multiplier to apply to the baseline priority tip
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Fold a newly confirmed batch's end-to-end latency into the window,
+    /// escalating or decaying the multiplier as needed
+    pub fn record_latency(&mut self, latency_ms: u64) {
+        if self.window.len() >= CONGESTION_WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(latency_ms);
+
+        if median(&self.window) > self.target_latency_ms {
+            self.multiplier = (self.multiplier * CONGESTION_GROWTH_FACTOR).min(CONGESTION_CEILING);
+            self.on_target_streak = 0;
+            return;
+        }
+
+        self.on_target_streak += 1;
+        if self.on_target_streak >= CONGESTION_DECAY_STREAK {
+            self.multiplier = (self.multiplier * CONGESTION_DECAY_FACTOR).max(CONGESTION_FLOOR);
+            self.on_target_streak = 0;
+        }
+    }
+}
+
+/// Median of a latency window (sorting a copy rather than the deque itself,
+/// since the deque's arrival order matters for the next `pop_front`)
+fn median(window: &VecDeque<u64>) -> u64 {
+    if window.is_empty() {
+        return 0;
+    }
+    let mut sorted: Vec<u64> = window.iter().copied().collect();
+    sorted.sort_unstable();
+    sorted[sorted.len() / 2]
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FeeQuote {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+/// Closed-loop EIP-1559 fee controller. Combines a fresh `eth_feeHistory`
+/// baseline with the observed inclusion latency of previous batches: escalate
+/// the tip when a batch misses its one-block budget, decay it back toward
+/// baseline when batches keep landing immediately. Also carries a
+/// `CongestionTracker` that applies a slower-moving multiplier on top of
+/// that baseline, tracking the trailing median confirmation latency against
+/// the stream's own FPS rather than a single batch's stall/resubmit.
+pub struct FeeController {
+    fee_percentile: f64,
+    max_priority_fee_wei: u128,
+    current_tip_wei: u128,
+    last_baseline_tip_wei: u128,
+    congestion: CongestionTracker,
+}
+
+impl FeeController {
+    /// `target_latency_ms` is the congestion tracker's target - `RpcClient`
+    /// sets it to two frame intervals at the stream's FPS
+    pub fn new(fee_percentile: f64, max_priority_fee_wei: u128, target_latency_ms: u64) -> Self {
+        Self {
+            fee_percentile,
+            max_priority_fee_wei,
+            current_tip_wei: 0,
+            last_baseline_tip_wei: 0,
+            congestion: CongestionTracker::new(target_latency_ms),
+        }
+    }
+
+    pub fn fee_history_block_count(&self) -> u64 {
+        FEE_HISTORY_BLOCK_COUNT
+    }
+
+    pub fn fee_percentile(&self) -> f64 {
+        self.fee_percentile
+    }
+
+    /// Fold a feeHistory baseline into the controller's current tip (which
+    /// carries escalation/decay state from previous batches) and produce the
+    /// fees to submit with the next transaction, scaled by the trailing
+    /// congestion multiplier
+    pub fn quote(&mut self, base_fee_per_gas: u128, baseline_tip_wei: u128) -> FeeQuote {
+        self.last_baseline_tip_wei = baseline_tip_wei;
+
+        if self.current_tip_wei == 0 {
+            self.current_tip_wei = baseline_tip_wei;
+        }
+        // Never bid below what the network itself is paying
+        self.current_tip_wei = self.current_tip_wei.max(baseline_tip_wei);
+        self.current_tip_wei = self.current_tip_wei.min(self.max_priority_fee_wei);
+
+        let congested_tip = ((self.current_tip_wei as f64 * self.congestion.multiplier()) as u128)
+            .min(self.max_priority_fee_wei);
+
+        FeeQuote {
+            // Headroom over the current base fee in case it rises before inclusion
+            max_fee_per_gas: base_fee_per_gas
+                .saturating_mul(2)
+                .saturating_add(congested_tip),
+            max_priority_fee_per_gas: congested_tip,
+        }
+    }
+
+    /// The previous batch missed its one-block inclusion budget - bid higher
+    pub fn on_missed_inclusion(&mut self) -> u128 {
+        let bumped = (self.current_tip_wei.max(1) as f64 * ESCALATION_FACTOR) as u128;
+        self.current_tip_wei = bumped.min(self.max_priority_fee_wei);
+        self.current_tip_wei
+    }
+
+    /// The previous batch was included immediately - ease back toward the
+    /// baseline tip observed in the most recent `quote()` call
+    pub fn on_immediate_inclusion(&mut self) {
+        let decayed = (self.current_tip_wei as f64 * DECAY_FACTOR) as u128;
+        self.current_tip_wei = decayed.max(self.last_baseline_tip_wei);
+    }
+
+    /// Feed a confirmed batch's end-to-end latency into the sliding-window
+    /// congestion tracker, which shifts where future `quote()` calls start
+    /// bidding from
+    pub fn record_latency(&mut self, latency_ms: u64) {
+        self.congestion.record_latency(latency_ms);
+    }
+
+    /// The congestion tracker's current multiplier, for surfacing alongside
+    /// the fee paid in the run summary
+    pub fn congestion_multiplier(&self) -> f64 {
+        self.congestion.multiplier()
+    }
+}
+
+/// Baseline priority tip from a set of `eth_feeHistory` reward samples at the
+/// configured percentile: the median across the sampled blocks
+pub fn baseline_tip_from_rewards(rewards: &[u128]) -> Result<u128> {
+    if rewards.is_empty() {
+        return Ok(0);
+    }
+    let mut sorted = rewards.to_vec();
+    sorted.sort();
+    Ok(sorted[sorted.len() / 2])
+}
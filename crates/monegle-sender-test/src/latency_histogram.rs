@@ -0,0 +1,101 @@
+/// Streaming log-spaced latency histogram. Buckets are fixed up front and
+/// updated one sample at a time, so memory stays O(bucket count) regardless
+/// of how many transactions a run submits - unlike keeping every datapoint
+/// around just to sort it at the end.
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    /// Upper bound (inclusive) of each bucket, in milliseconds, log-spaced
+    bucket_bounds_ms: Vec<u64>,
+    counts: Vec<u64>,
+    count: u64,
+    max_ms: u64,
+}
+
+const NUM_BUCKETS: usize = 64;
+/// Growth factor between buckets - 64 buckets at this factor span roughly
+/// 1ms to 45 hours, which comfortably covers both fast confirmations and
+/// a stalled/rebroadcast batch
+const BUCKET_GROWTH: f64 = 1.35;
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        let mut bucket_bounds_ms = Vec::with_capacity(NUM_BUCKETS);
+        let mut bound = 1.0_f64;
+        for _ in 0..NUM_BUCKETS {
+            bucket_bounds_ms.push(bound.ceil() as u64);
+            bound *= BUCKET_GROWTH;
+        }
+
+        Self {
+            bucket_bounds_ms,
+            counts: vec![0; NUM_BUCKETS],
+            count: 0,
+            max_ms: 0,
+        }
+    }
+
+    pub fn record(&mut self, latency_ms: u64) {
+        self.count += 1;
+        self.max_ms = self.max_ms.max(latency_ms);
+
+        let idx = self
+            .bucket_bounds_ms
+            .iter()
+            .position(|&bound| latency_ms <= bound)
+            .unwrap_or(NUM_BUCKETS - 1);
+        self.counts[idx] += 1;
+    }
+
+    /// Fold another histogram's counts into this one (same fixed bucket
+    /// layout on both sides, so it's just bucket-wise addition)
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (mine, theirs) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *mine += theirs;
+        }
+        self.count += other.count;
+        self.max_ms = self.max_ms.max(other.max_ms);
+    }
+
+    /// Upper bound of the bucket containing the given percentile (0.0-1.0)
+    pub fn percentile(&self, p: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+
+        let target = ((self.count as f64) * p).ceil() as u64;
+        let mut cumulative = 0u64;
+        for (i, &c) in self.counts.iter().enumerate() {
+            cumulative += c;
+            if cumulative >= target {
+                return self.bucket_bounds_ms[i];
+            }
+        }
+        self.max_ms
+    }
+
+    pub fn p50(&self) -> u64 {
+        self.percentile(0.50)
+    }
+
+    pub fn p90(&self) -> u64 {
+        self.percentile(0.90)
+    }
+
+    pub fn p99(&self) -> u64 {
+        self.percentile(0.99)
+    }
+
+    pub fn max(&self) -> u64 {
+        self.max_ms
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,20 +1,30 @@
+mod control_server;
+mod fee_controller;
+mod latency_histogram;
 mod rpc_client;
+mod rpc_pool;
+mod ws_confirmation;
 
 use anyhow::Result;
 use clap::Parser;
+use control_server::PipelineControl;
 use monegle_core::{CompressionType, StreamId, SyntheticFrameGenerator};
-use rpc_client::RpcClient;
+use rpc_pool::RpcPool;
+use std::sync::Arc;
 use std::time::Duration;
-use tracing::{info, warn};
+use tokio::sync::Semaphore;
+use tracing::{debug, info, warn};
 use tracing_subscriber::EnvFilter;
 
 #[derive(Parser)]
 #[command(name = "monegle-sender-test")]
 #[command(about = "RPC throughput test for Monegle", long_about = None)]
 struct Args {
-    /// RPC endpoint URL
-    #[arg(short, long)]
-    rpc_url: String,
+    /// RPC endpoint URL(s). Repeat the flag to pool multiple endpoints
+    /// with health-based rotation and failover, e.g.
+    /// `--rpc-url https://a --rpc-url https://b`
+    #[arg(short, long, required = true)]
+    rpc_url: Vec<String>,
 
     /// Private key (or use MONAD_PRIVATE_KEY env var)
     #[arg(short, long)]
@@ -47,6 +57,52 @@ struct Args {
     /// Use static frames (better compression)
     #[arg(long)]
     static_frames: bool,
+
+    /// Ceiling on the adaptive priority fee, in gwei. The fee controller
+    /// escalates the tip toward this when batches miss their one-block
+    /// inclusion budget, and decays back toward the `eth_feeHistory`
+    /// baseline otherwise.
+    #[arg(long, default_value = "5")]
+    max_priority_fee: u64,
+
+    /// `eth_feeHistory` reward percentile used as the tip baseline (e.g.
+    /// 50 for the median, 75 to bid more aggressively)
+    #[arg(long, default_value = "50")]
+    fee_percentile: f64,
+
+    /// WebSocket RPC URL for event-driven confirmation (subscribes to new
+    /// block headers and resolves inclusion as soon as a receipt appears,
+    /// instead of busy-polling `get_transaction_receipt` every 500ms). Falls
+    /// back to the existing HTTP polling path when omitted.
+    #[arg(long)]
+    ws_url: Option<String>,
+
+    /// Submit batches without waiting for each one's confirmation before
+    /// sending the next, keeping up to `max_in_flight` transactions pending
+    /// at once instead of capping throughput at one tx per block-time
+    #[arg(long)]
+    pipelined: bool,
+
+    /// Max transactions kept in flight at once in `--pipelined` mode
+    #[arg(long, default_value = "8")]
+    max_in_flight: usize,
+
+    /// How long `submit_batch` waits on a submission before rebroadcasting
+    /// it under the same nonce with a bumped fee, in milliseconds
+    #[arg(long, default_value = "800")]
+    stuck_timeout_ms: u64,
+
+    /// Ceiling on same-nonce rebroadcasts per batch before giving up and
+    /// recording a confirmation timeout
+    #[arg(long, default_value = "5")]
+    max_rebroadcasts: u32,
+
+    /// Bind a JSON-RPC control server here (e.g. "127.0.0.1:9000") exposing
+    /// `get_metrics`/`get_summary`/`get_stream_info` and `pause`/`resume`/
+    /// `set_fps` over newline-delimited JSON-RPC, so a long-running test
+    /// can be monitored/tuned without restarting it. Disabled by default.
+    #[arg(long)]
+    control_addr: Option<String>,
 }
 
 #[tokio::main]
@@ -69,7 +125,7 @@ async fn main() -> Result<()> {
     println!("\n╔═══════════════════════════════════════════════════════╗");
     println!("║     MONEGLE RPC THROUGHPUT FEASIBILITY TEST          ║");
     println!("╠═══════════════════════════════════════════════════════╣");
-    println!("║ RPC URL:      {:<40}║", truncate(&args.rpc_url, 40));
+    println!("║ RPC URL(s):   {:<40}║", truncate(&args.rpc_url.join(", "), 40));
     println!("║ Target:       {:<40}║", &args.target_address);
     println!("║ Quality:      {} FPS, {}×{} chars{:<20}║",
         args.fps, args.width, args.height, "");
@@ -78,41 +134,72 @@ async fn main() -> Result<()> {
         if args.static_frames { "Static (high compression)" } else { "Random (realistic)" });
     println!("╚═══════════════════════════════════════════════════════╝\n");
 
-    // Initialize RPC client
-    let client = RpcClient::new(
-        &args.rpc_url,
-        &private_key,
-        args.target_address.parse()?,
-    )
-    .await?;
+    // Initialize RPC pool (a single URL just means a pool of one)
+    let client = Arc::new(
+        RpcPool::new_with_resubmit(
+            &args.rpc_url,
+            &private_key,
+            args.target_address.parse()?,
+            args.fee_percentile,
+            args.max_priority_fee as u128 * 1_000_000_000,
+            args.fps,
+            args.ws_url.as_deref(),
+            Duration::from_millis(args.stuck_timeout_ms),
+            args.max_rebroadcasts,
+        )
+        .await?,
+    );
+
+    let prober_handle = tokio::spawn(client.clone().start_health_prober(Duration::from_secs(15)));
 
     // Initialize synthetic frame generator
     let mut generator = SyntheticFrameGenerator::new(args.width, args.height);
 
-    // Calculate frames per batch (based on 400ms block time)
-    // 2.5 blocks/sec × frame_interval = frames_per_batch
-    let frames_per_batch = ((0.4 * args.fps as f32).ceil() as usize).max(1);
     let batch_interval = Duration::from_millis(400);
 
     let stream_id: StreamId = [0u8; 32]; // Dummy stream ID for testing
 
-    info!(
-        "Batching: {} frames per batch, every {} ms",
-        frames_per_batch,
-        batch_interval.as_millis()
-    );
     info!("Target rate: 2.5 transactions/second");
 
+    // Shared FPS/pause knobs the optional control server mutates - the
+    // submission loop re-derives `frames_per_batch` from `control.fps()`
+    // every tick instead of the fixed value computed once up front
+    let control = Arc::new(PipelineControl::new(client.clone(), stream_id, args.width, args.height, args.fps));
+    if let Some(control_addr) = args.control_addr.clone() {
+        let control = control.clone();
+        tokio::spawn(async move {
+            if let Err(e) = control_server::run_control_server(&control_addr, control).await {
+                warn!("Control server stopped: {}", e);
+            }
+        });
+    }
+
     println!("Starting test...\n");
 
+    if args.pipelined {
+        info!("Pipelined mode: up to {} batches in flight at once", args.max_in_flight);
+    }
+
     // Run test
     let start_time = std::time::Instant::now();
     let mut sequence = 0u64;
     let mut ticker = tokio::time::interval(batch_interval);
+    let in_flight = Arc::new(Semaphore::new(args.max_in_flight));
+    let mut pipelined_tasks = Vec::new();
 
     while start_time.elapsed().as_secs() < args.duration {
         ticker.tick().await;
 
+        if control.is_paused() {
+            debug!("Paused via control server, skipping tick");
+            continue;
+        }
+
+        // Frames per batch (based on 400ms block time), re-derived every
+        // tick so a `set_fps` control call takes effect on the next one
+        // 2.5 blocks/sec × frame_interval = frames_per_batch
+        let frames_per_batch = ((0.4 * control.fps() as f32).ceil() as usize).max(1);
+
         // Generate batch
         let batch = if args.static_frames {
             generator.generate_static_batch(
@@ -141,29 +228,54 @@ async fn main() -> Result<()> {
         );
 
         // Submit to blockchain
-        match client.submit_batch(sequence, encoded).await {
-            Ok(metric) => {
-                if !metric.success {
-                    warn!("Batch {} failed: {:?}", sequence, metric.error);
+        if args.pipelined {
+            let client = client.clone();
+            let permit = in_flight.clone().acquire_owned().await.expect("semaphore closed");
+            pipelined_tasks.push(tokio::spawn(async move {
+                let _permit = permit;
+                match client.submit_batch_pipelined(sequence, encoded).await {
+                    Ok(tx_hash) => debug!("Batch {} submitted: {:?} (confirmation pending)", sequence, tx_hash),
+                    Err(e) => warn!("Batch {} submission error: {:?}", sequence, e),
+                }
+            }));
+        } else {
+            match client.submit_batch(sequence, encoded).await {
+                Ok(metric) => {
+                    if !metric.success {
+                        warn!("Batch {} failed: {:?}", sequence, metric.error);
+                    }
+                }
+                Err(e) => {
+                    warn!("Batch {} error: {:?}", sequence, e);
                 }
-            }
-            Err(e) => {
-                warn!("Batch {} error: {:?}", sequence, e);
             }
         }
 
         sequence += 1;
     }
 
+    if args.pipelined {
+        for task in pipelined_tasks {
+            let _ = task.await;
+        }
+        info!("All batches submitted, draining outstanding confirmations...");
+        client.drain_pipelined(Duration::from_secs(30)).await;
+    }
+
     println!("\nTest complete!\n");
 
     // Print summary
     client.print_summary().await;
 
-    // Export metrics if requested
+    // Export metrics if requested, alongside the percentile/throughput summary
     if let Some(output_path) = args.output {
         let metrics = client.get_metrics().await;
-        let json = serde_json::to_string_pretty(&metrics)?;
+        let latency = client.latency_summary().await;
+        let export = serde_json::json!({
+            "metrics": metrics,
+            "latency_summary": latency,
+        });
+        let json = serde_json::to_string_pretty(&export)?;
         std::fs::write(&output_path, json)?;
         println!("✓ Metrics exported to: {}\n", output_path);
     }
@@ -187,8 +299,8 @@ async fn main() -> Result<()> {
         println!("║ ⚠️  MODERATE: {}% success rate                     ║", success_rate as u32);
         println!("║                                                       ║");
         println!("║ Recommendations:                                      ║");
-        println!("║   • Implement RPC rotation (multiple endpoints)      ║");
-        println!("║   • Add retry logic for failed transactions          ║");
+        println!("║   • Pass more --rpc-url flags to pool endpoints      ║");
+        println!("║   • Check logs for endpoints being quarantined       ║");
         println!("║   • Consider reducing FPS slightly                   ║");
     } else {
         println!("║ ❌ POOR: {}% success rate                          ║", success_rate as u32);
@@ -202,6 +314,8 @@ async fn main() -> Result<()> {
 
     println!("╚═══════════════════════════════════════════════════════╝\n");
 
+    prober_handle.abort();
+
     Ok(())
 }
 
@@ -1,20 +1,59 @@
 use alloy::{
+    eips::BlockNumberOrTag,
     network::{EthereumWallet, TransactionBuilder},
     primitives::{Address, Bytes, B256},
     providers::{Provider, ProviderBuilder, RootProvider},
     signers::local::PrivateKeySigner,
     transports::http::{Client, Http},
 };
+use alloy::rpc::types::AccessList;
 use alloy::rpc::types::TransactionReceipt;
 use alloy::rpc::types::TransactionRequest;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
+use crate::fee_controller::{baseline_tip_from_rewards, FeeController, FeeQuote};
+use crate::latency_histogram::LatencyHistogram;
+use crate::ws_confirmation::WsConfirmationTracker;
+
+/// Target inclusion latency: one Monegle block (see the 400ms batch cadence
+/// in `main.rs`). A batch that isn't confirmed within this window is
+/// considered to have missed its budget and its tip gets escalated.
+const BLOCK_BUDGET_MS: u64 = 400;
+
+/// How long to wait for the original submission before the stuck-tx
+/// subsystem below considers it stalled and rebroadcasts under the same
+/// nonce with a bumped fee. Also the default for the configurable
+/// `stuck_timeout` (see `RpcClient::new_with_resubmit`).
+const STALL_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(BLOCK_BUDGET_MS * 2);
+const CONFIRM_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Default `stuck_timeout`, re-exported for `RpcPool`'s own cascading
+/// constructors to default to
+pub(crate) const DEFAULT_STUCK_TIMEOUT: std::time::Duration = STALL_TIMEOUT;
+
+/// Default ceiling on same-nonce rebroadcasts per batch before giving up and
+/// recording a timeout, used by `RpcClient::new_with_ws`/`RpcPool::new_with_fees`
+pub(crate) const DEFAULT_MAX_REBROADCASTS: u32 = 5;
+
+/// Minimum percentage a replacement's fees must exceed the previous
+/// attempt's by - below this a node rejects the replacement as underpriced
+/// (most clients enforce 10% on both `maxFeePerGas` and
+/// `maxPriorityFeePerGas`)
+const MIN_REPLACEMENT_BUMP_PCT: u128 = 10;
+
+/// How many batches to measure with the cached EIP-2930 access list attached
+/// before deciding whether it's actually realizing gas savings over a plain
+/// tx and should keep being used for the rest of the run
+const ACCESS_LIST_PROBE_BATCHES: usize = 3;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TxMetrics {
     pub sequence: u64,
@@ -22,11 +61,57 @@ pub struct TxMetrics {
     pub tx_hash: B256,
     pub submit_time_ms: u64,
     pub confirm_time_ms: Option<u64>,
+    /// Total end-to-end latency: submission to on-chain confirmation
     pub latency_ms: Option<u64>,
+    /// Time for the node to accept the transaction into its mempool
+    pub submit_ack_ms: Option<u64>,
+    /// Time from mempool acceptance to on-chain confirmation
+    pub confirmation_ms: Option<u64>,
+    pub block_submitted: Option<u64>,
+    pub block_included: Option<u64>,
+    /// How many blocks after submission the transaction actually landed in
+    pub inclusion_blocks: Option<u64>,
     pub gas_used: Option<u128>,
     pub success: bool,
     pub error: Option<String>,
     pub data_size: usize,
+    /// Fees the landing (or final, if reverted/timed-out) submission used,
+    /// for correlating fee paid against confirmation latency in the summary -
+    /// also the "final effective gas price" after any stuck-tx rebroadcasts
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    /// How many times this batch was rebroadcast under the same nonce with
+    /// a bumped fee after stalling past `stuck_timeout`
+    pub rebroadcast_count: u32,
+    /// Tx hashes superseded by a later rebroadcast, oldest first - the hash
+    /// that actually lands (if any) is `tx_hash` above, not in this list
+    #[serde(with = "serde_b256_vec")]
+    pub replacement_hashes: Vec<B256>,
+    /// Whether this batch's transaction carried a cached EIP-2930 access
+    /// list (see `RpcClient::create_access_list`) rather than a plain tx
+    pub used_access_list: bool,
+    /// Realized `gas_used` minus the stream's first batch (always sent
+    /// without a list, establishing the baseline) - negative means this
+    /// batch used less gas. `None` until a baseline has been established.
+    pub gas_delta: Option<i64>,
+}
+
+/// Percentile breakdown pulled from the streaming latency histograms, kept
+/// separate from `TxMetrics` since it summarizes a whole run rather than one tx
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub submit_ack_p50_ms: u64,
+    pub submit_ack_p90_ms: u64,
+    pub submit_ack_p99_ms: u64,
+    pub submit_ack_max_ms: u64,
+    pub confirmation_p50_ms: u64,
+    pub confirmation_p90_ms: u64,
+    pub confirmation_p99_ms: u64,
+    pub confirmation_max_ms: u64,
+    pub throughput_bytes_per_sec: f64,
+    /// Mean priority fee paid across successful submissions, to correlate
+    /// against the confirmation percentiles above
+    pub avg_priority_fee_wei: u128,
 }
 
 // Helper module for B256 serialization
@@ -50,6 +135,31 @@ mod serde_b256 {
     }
 }
 
+// Helper module for Vec<B256> serialization, same representation as serde_b256
+mod serde_b256_vec {
+    use alloy::primitives::B256;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(value: &[B256], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let strings: Vec<String> = value.iter().map(|h| format!("{:?}", h)).collect();
+        strings.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<B256>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let strings = Vec::<String>::deserialize(deserializer)?;
+        strings
+            .into_iter()
+            .map(|s| s.parse().map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
 type FilledProvider = alloy::providers::fillers::FillProvider<
     alloy::providers::fillers::JoinFill<
         alloy::providers::fillers::JoinFill<
@@ -72,11 +182,46 @@ type FilledProvider = alloy::providers::fillers::FillProvider<
     alloy::network::Ethereum,
 >;
 
+/// Tracks the one-time `eth_createAccessList` probe for a stream: the first
+/// batch establishes a `gas_used` baseline without a list, the next few
+/// probe batches attach the cached list and measure realized gas against
+/// that baseline, and `decided` latches in whichever mode wins for the
+/// remainder of the run.
+#[derive(Default)]
+struct AccessListState {
+    access_list: Option<AccessList>,
+    baseline_gas_used: Option<u128>,
+    probe_gas_used: Vec<u128>,
+    decided: Option<bool>,
+}
+
 pub struct RpcClient {
     provider: FilledProvider,
+    rpc_url: String,
+    sender_address: Address,
     target_address: Address,
     metrics: Arc<Mutex<Vec<TxMetrics>>>,
     start_time: Instant,
+    fee_controller: Mutex<FeeController>,
+    ack_histogram: Mutex<LatencyHistogram>,
+    confirm_histogram: Mutex<LatencyHistogram>,
+    /// When set, `submit_batch` awaits a receipt pushed by this tracker's
+    /// block-header watcher instead of polling `get_transaction_receipt`
+    ws_confirmations: Option<Arc<WsConfirmationTracker>>,
+    /// Next nonce to stamp on a pipelined submission. `None` until the first
+    /// pipelined submit fetches the starting value from the chain; kept
+    /// separate from the sequential `submit_batch` path, which fetches its
+    /// own pending nonce per call (leaving it to `NonceFiller` only if that
+    /// fetch fails).
+    next_nonce: Mutex<Option<u64>>,
+    /// How long `submit_batch` waits on an attempt before rebroadcasting
+    /// under the same nonce with a bumped fee
+    stuck_timeout: std::time::Duration,
+    /// Ceiling on same-nonce rebroadcasts per batch before giving up
+    max_rebroadcasts: u32,
+    /// EIP-2930 access-list probe/decision state for this stream - see
+    /// `AccessListState`
+    access_list_state: Mutex<AccessListState>,
 }
 
 impl RpcClient {
@@ -84,6 +229,79 @@ impl RpcClient {
         rpc_url: &str,
         private_key: &str,
         target_address: Address,
+    ) -> Result<Self> {
+        Self::new_with_fees(rpc_url, private_key, target_address, 50.0, 5_000_000_000, 15).await
+    }
+
+    /// Construct a client with explicit fee-bidding knobs: `fee_percentile`
+    /// is the `eth_feeHistory` reward percentile used as the tip baseline
+    /// (e.g. 50.0 for the median), `max_priority_fee_wei` is the ceiling the
+    /// adaptive controller will not escalate past, and `stream_fps` sets the
+    /// congestion tracker's target latency to two frame intervals at that
+    /// rate.
+    pub async fn new_with_fees(
+        rpc_url: &str,
+        private_key: &str,
+        target_address: Address,
+        fee_percentile: f64,
+        max_priority_fee_wei: u128,
+        stream_fps: u8,
+    ) -> Result<Self> {
+        Self::new_with_ws(
+            rpc_url,
+            private_key,
+            target_address,
+            fee_percentile,
+            max_priority_fee_wei,
+            stream_fps,
+            None,
+        )
+        .await
+    }
+
+    /// Same as [`RpcClient::new_with_fees`] but taking an already-connected
+    /// `WsConfirmationTracker` to await receipts through instead of busy-
+    /// polling `get_transaction_receipt`. `None` preserves the HTTP polling
+    /// behavior.
+    pub async fn new_with_ws(
+        rpc_url: &str,
+        private_key: &str,
+        target_address: Address,
+        fee_percentile: f64,
+        max_priority_fee_wei: u128,
+        stream_fps: u8,
+        ws_confirmations: Option<Arc<WsConfirmationTracker>>,
+    ) -> Result<Self> {
+        Self::new_with_resubmit(
+            rpc_url,
+            private_key,
+            target_address,
+            fee_percentile,
+            max_priority_fee_wei,
+            stream_fps,
+            ws_confirmations,
+            STALL_TIMEOUT,
+            DEFAULT_MAX_REBROADCASTS,
+        )
+        .await
+    }
+
+    /// Same as [`RpcClient::new_with_ws`] but with explicit stuck-tx
+    /// rebroadcast knobs: `stuck_timeout` is how long `submit_batch` waits
+    /// on an attempt before rebroadcasting under the same nonce with a
+    /// bumped fee, and `max_rebroadcasts` caps how many times that can
+    /// happen per batch before it's recorded as a timeout.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new_with_resubmit(
+        rpc_url: &str,
+        private_key: &str,
+        target_address: Address,
+        fee_percentile: f64,
+        max_priority_fee_wei: u128,
+        stream_fps: u8,
+        ws_confirmations: Option<Arc<WsConfirmationTracker>>,
+        stuck_timeout: std::time::Duration,
+        max_rebroadcasts: u32,
     ) -> Result<Self> {
         info!("Initializing RPC client");
         info!("RPC URL: {}", rpc_url);
@@ -91,6 +309,7 @@ impl RpcClient {
 
         let signer = PrivateKeySigner::from_str(private_key)
             .map_err(|e| anyhow!("Invalid private key: {}", e))?;
+        let sender_address = signer.address();
 
         let wallet = EthereumWallet::from(signer);
 
@@ -100,14 +319,169 @@ impl RpcClient {
             .wallet(wallet)
             .on_http(rpc_url_parsed);
 
+        // Two frame intervals at the stream's own FPS
+        let target_latency_ms = 2 * (1000 / stream_fps.max(1) as u64);
+
         Ok(Self {
             provider,
+            rpc_url: rpc_url.to_string(),
+            sender_address,
             target_address,
             metrics: Arc::new(Mutex::new(Vec::new())),
             start_time: Instant::now(),
+            fee_controller: Mutex::new(FeeController::new(
+                fee_percentile,
+                max_priority_fee_wei,
+                target_latency_ms,
+            )),
+            ack_histogram: Mutex::new(LatencyHistogram::new()),
+            confirm_histogram: Mutex::new(LatencyHistogram::new()),
+            ws_confirmations,
+            next_nonce: Mutex::new(None),
+            stuck_timeout,
+            max_rebroadcasts,
+            access_list_state: Mutex::new(AccessListState::default()),
         })
     }
 
+    /// The RPC URL this client was constructed with (for logging/health tracking)
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// The wallet address every submission is sent from
+    pub fn sender_address(&self) -> Address {
+        self.sender_address
+    }
+
+    /// Cheap liveness probe used by the pool's health prober
+    pub async fn get_block_number(&self) -> Result<u64> {
+        self.provider
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("get_block_number failed: {}", e))
+    }
+
+    /// Sample `eth_feeHistory` and fold it into the adaptive fee controller
+    /// to get the `maxFeePerGas`/`maxPriorityFeePerGas` for the next submission
+    async fn fee_quote(&self) -> Result<FeeQuote> {
+        let mut controller = self.fee_controller.lock().await;
+
+        let history = self
+            .provider
+            .get_fee_history(
+                controller.fee_history_block_count(),
+                BlockNumberOrTag::Latest,
+                &[controller.fee_percentile()],
+            )
+            .await
+            .map_err(|e| anyhow!("get_fee_history failed: {}", e))?;
+
+        let base_fee_per_gas = *history.base_fee_per_gas.last().unwrap_or(&0);
+
+        let rewards: Vec<u128> = history
+            .reward
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|block_rewards| block_rewards.first().copied())
+            .collect();
+        let baseline_tip = baseline_tip_from_rewards(&rewards)?;
+
+        Ok(controller.quote(base_fee_per_gas, baseline_tip))
+    }
+
+    /// Decide whether this batch's transaction(s) should carry the cached
+    /// access list: the stream's very first batch always goes out without
+    /// one (it becomes the `gas_used` baseline) while kicking off the
+    /// one-time `eth_createAccessList` probe for later batches; once a
+    /// baseline exists, the next `ACCESS_LIST_PROBE_BATCHES` attach the
+    /// cached list (if any) to measure it, and after that `decided` is
+    /// followed either way.
+    async fn access_list_for_batch(&self, calldata: &[u8], fees: FeeQuote) -> Option<AccessList> {
+        let mut state = self.access_list_state.lock().await;
+
+        if state.baseline_gas_used.is_none() {
+            if state.access_list.is_none() {
+                drop(state);
+                let probed = self.create_access_list(calldata, fees).await;
+                state = self.access_list_state.lock().await;
+                state.access_list = probed;
+            }
+            return None;
+        }
+
+        match state.decided {
+            Some(true) => state.access_list.clone(),
+            Some(false) => None,
+            None if state.probe_gas_used.len() < ACCESS_LIST_PROBE_BATCHES => state.access_list.clone(),
+            None => None,
+        }
+    }
+
+    /// Call `eth_createAccessList` for a representative transaction to this
+    /// stream's `target_address`/calldata shape, caching the result for
+    /// subsequent batches. Any failure (unsupported method, RPC error) is
+    /// treated as "no access list available" rather than surfaced - this
+    /// degrades silently to plain transactions rather than failing a batch
+    /// over a gas-optimization probe.
+    async fn create_access_list(&self, calldata: &[u8], fees: FeeQuote) -> Option<AccessList> {
+        let tx = TransactionRequest::default()
+            .from(self.sender_address)
+            .to(self.target_address)
+            .with_input(Bytes::from(calldata.to_vec()))
+            .max_fee_per_gas(fees.max_fee_per_gas)
+            .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+        match self.provider.create_access_list(&tx).await {
+            Ok(result) => {
+                debug!(
+                    "Cached access list for target {} ({} entries)",
+                    self.target_address,
+                    result.access_list.0.len()
+                );
+                Some(result.access_list)
+            }
+            Err(e) => {
+                debug!("eth_createAccessList unsupported or failed, disabling access-list optimization: {:?}", e);
+                None
+            }
+        }
+    }
+
+    /// Fold a confirmed batch's realized gas usage into the access-list
+    /// probe/decision state and return its `gas_delta` against the baseline.
+    /// The first confirmed batch of a stream sets the baseline itself rather
+    /// than being compared against it; once `ACCESS_LIST_PROBE_BATCHES`
+    /// probe batches have been measured with the list attached, decide
+    /// whether to keep using it or fall back to plain transactions.
+    async fn record_access_list_outcome(&self, attached: bool, gas_used: u128) -> Option<i64> {
+        let mut state = self.access_list_state.lock().await;
+
+        let Some(baseline) = state.baseline_gas_used else {
+            state.baseline_gas_used = Some(gas_used);
+            return None;
+        };
+        let delta = gas_used as i64 - baseline as i64;
+
+        if attached && state.decided.is_none() {
+            state.probe_gas_used.push(gas_used);
+            if state.probe_gas_used.len() >= ACCESS_LIST_PROBE_BATCHES {
+                let avg_probed: u128 =
+                    state.probe_gas_used.iter().sum::<u128>() / state.probe_gas_used.len() as u128;
+                let keep = avg_probed < baseline;
+                info!(
+                    "Access-list probe complete: avg {} gas with list vs {} baseline, {}",
+                    avg_probed,
+                    baseline,
+                    if keep { "keeping it" } else { "reverting to plain txs" }
+                );
+                state.decided = Some(keep);
+            }
+        }
+
+        Some(delta)
+    }
+
     pub async fn submit_batch(&self, sequence: u64, calldata: Vec<u8>) -> Result<TxMetrics> {
         let submit_time = Instant::now();
         let submit_time_ms = submit_time.duration_since(self.start_time).as_millis() as u64;
@@ -115,172 +489,616 @@ impl RpcClient {
 
         debug!("Submitting batch {} ({} bytes)", sequence, data_size);
 
-        // Create transaction (let GasFiller estimate gas automatically)
-        let tx = TransactionRequest::default()
-            .to(self.target_address)
-            .with_input(Bytes::from(calldata));
-
         let mut metric = TxMetrics {
             sequence,
             tx_hash: B256::ZERO,
             submit_time_ms,
             confirm_time_ms: None,
             latency_ms: None,
+            submit_ack_ms: None,
+            confirmation_ms: None,
+            block_submitted: None,
+            block_included: None,
+            inclusion_blocks: None,
             gas_used: None,
             success: false,
             error: None,
             data_size,
+            max_fee_per_gas: 0,
+            max_priority_fee_per_gas: 0,
+            rebroadcast_count: 0,
+            replacement_hashes: Vec::new(),
+            used_access_list: false,
+            gas_delta: None,
         };
 
-        // Send transaction using the provider (which handles signing with wallet)
-        match self.provider.send_transaction(tx).await {
-            Ok(pending_tx) => {
-                metric.tx_hash = *pending_tx.tx_hash();
-                debug!("Transaction sent: {:?}", metric.tx_hash);
-
-                // Wait for confirmation with timeout
-                match tokio::time::timeout(
-                    std::time::Duration::from_secs(30),
-                    pending_tx.get_receipt()
-                ).await {
-                    Ok(Ok(receipt)) => {
-                        let confirm_time = Instant::now();
-                        let confirm_time_ms =
-                            confirm_time.duration_since(self.start_time).as_millis() as u64;
-                        let latency = confirm_time.duration_since(submit_time).as_millis() as u64;
-
-                        metric.confirm_time_ms = Some(confirm_time_ms);
-                        metric.latency_ms = Some(latency);
-                        metric.gas_used = Some(receipt.gas_used);
-                        metric.success = receipt.status();
-
-                        if metric.success {
-                            info!(
-                                "Batch {} confirmed in {} ms, gas: {}",
-                                sequence, latency, receipt.gas_used
-                            );
-                        } else {
-                            warn!("Batch {} reverted", sequence);
-                            metric.error = Some("Transaction reverted".to_string());
-                        }
-                    }
-                    Ok(Err(e)) => {
-                        error!("Confirmation failed for batch {}: {:?}", sequence, e);
-                        metric.error = Some(format!("Confirmation failed: {:?}", e));
-                    }
-                    Err(_) => {
-                        error!("Timeout waiting for batch {}", sequence);
-                        metric.error = Some("Confirmation timeout (30s)".to_string());
-                    }
-                }
+        metric.block_submitted = self.provider.get_block_number().await.ok();
+
+        let mut fees = self.fee_quote().await?;
+        let access_list = self.access_list_for_batch(&calldata, fees).await;
+        metric.used_access_list = access_list.is_some();
+
+        // Pin the nonce up front so a stalled submission can be replaced
+        // under the same nonce instead of queuing behind the next batch
+        let nonce = match self
+            .provider
+            .get_transaction_count(self.sender_address)
+            .pending()
+            .await
+        {
+            Ok(n) => Some(n),
+            Err(e) => {
+                warn!("Failed to fetch pending nonce for batch {}: {}", sequence, e);
+                None
             }
+        };
+
+        // Every rebroadcast's receipt wait runs in its own task and reports
+        // back on this channel - whichever attempt confirms first resolves
+        // the batch, since a slow node can still mine a transaction that was
+        // already replaced. `registered_hashes` tracks which ones need
+        // unregistering from the WS tracker once the batch resolves.
+        let (result_tx, mut result_rx) =
+            tokio::sync::mpsc::unbounded_channel::<(B256, Result<TransactionReceipt>)>();
+        let mut wait_handles: Vec<tokio::task::JoinHandle<()>> = Vec::new();
+        let mut registered_hashes: Vec<B256> = Vec::new();
+        let mut ack_time = submit_time;
+        let mut remaining = CONFIRM_TIMEOUT;
+
+        let outcome = loop {
+            metric.max_fee_per_gas = fees.max_fee_per_gas;
+            metric.max_priority_fee_per_gas = fees.max_priority_fee_per_gas;
+
+            let mut tx = TransactionRequest::default()
+                .to(self.target_address)
+                .with_input(Bytes::from(calldata.clone()))
+                .max_fee_per_gas(fees.max_fee_per_gas)
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+            if let Some(list) = &access_list {
+                tx = tx.access_list(list.clone());
+            }
+            if let Some(n) = nonce {
+                tx = tx.nonce(n);
+            }
+
+            let pending_tx = match self.provider.send_transaction(tx).await {
+                Ok(pending_tx) => pending_tx,
                 Err(e) => {
                     error!("Submit failed for batch {}: {:?}", sequence, e);
                     metric.error = Some(format!("Submit failed: {:?}", e));
+                    break None;
                 }
+            };
+
+            let tx_hash = *pending_tx.tx_hash();
+            if metric.tx_hash == B256::ZERO {
+                metric.tx_hash = tx_hash;
+                ack_time = Instant::now();
+                let submit_ack_ms = ack_time.duration_since(submit_time).as_millis() as u64;
+                metric.submit_ack_ms = Some(submit_ack_ms);
+                self.ack_histogram.lock().await.record(submit_ack_ms);
             }
 
+            debug!("Transaction sent: {:?} (nonce {:?})", tx_hash, nonce);
+
+            let wait_for_receipt: Pin<Box<dyn Future<Output = Result<TransactionReceipt>> + Send>> =
+                if let Some(tracker) = &self.ws_confirmations {
+                    let receipt_rx = tracker.register(tx_hash).await;
+                    registered_hashes.push(tx_hash);
+                    Box::pin(async move {
+                        receipt_rx.await.map_err(|_| anyhow!("WS confirmation channel closed"))
+                    })
+                } else {
+                    Box::pin(async move {
+                        pending_tx.get_receipt().await.map_err(|e| anyhow!("{:?}", e))
+                    })
+                };
+
+            let sender = result_tx.clone();
+            wait_handles.push(tokio::spawn(async move {
+                let result = wait_for_receipt.await;
+                let _ = sender.send((tx_hash, result));
+            }));
+
+            let round_budget = self.stuck_timeout.min(remaining);
+            match tokio::time::timeout(round_budget, result_rx.recv()).await {
+                Ok(Some((hash, Ok(receipt)))) => break Some((hash, receipt)),
+                Ok(Some((hash, Err(e)))) => {
+                    error!("Confirmation failed for batch {} (tx {:?}): {:?}", sequence, hash, e);
+                    metric.error = Some(format!("Confirmation failed: {:?}", e));
+                    break None;
+                }
+                Ok(None) => {
+                    metric.error = Some("Confirmation channel closed unexpectedly".to_string());
+                    break None;
+                }
+                Err(_) if nonce.is_some() && metric.rebroadcast_count < self.max_rebroadcasts
+                    && remaining > round_budget =>
+                {
+                    // Missed the stuck-timeout budget: bump fees by at least
+                    // the replacement threshold and resubmit under the same
+                    // nonce rather than queuing behind it. The stalled
+                    // attempt stays registered above, so if it lands before
+                    // this one propagates, it still resolves the batch.
+                    let controller_tip = self.fee_controller.lock().await.on_missed_inclusion();
+                    let next_fees = bump_for_replacement(fees, controller_tip);
+                    warn!(
+                        "Batch {} stalled past {:?} budget, rebroadcasting under nonce {:?} ({} -> {} wei tip)",
+                        sequence, self.stuck_timeout, nonce, fees.max_priority_fee_per_gas, next_fees.max_priority_fee_per_gas
+                    );
+                    metric.replacement_hashes.push(tx_hash);
+                    metric.rebroadcast_count += 1;
+                    fees = next_fees;
+                    remaining = remaining.saturating_sub(round_budget);
+                    continue;
+                }
+                Err(_) => {
+                    error!("Timeout waiting for batch {}", sequence);
+                    metric.error = Some(format!("Confirmation timeout ({:?})", CONFIRM_TIMEOUT));
+                    break None;
+                }
+            }
+        };
+
+        // Whichever attempt resolved the batch (if any), every other
+        // outstanding wait is now moot - abort the polling tasks and drop
+        // the rest from the WS tracker so they don't linger in its map
+        for handle in wait_handles {
+            handle.abort();
+        }
+        if let Some(tracker) = &self.ws_confirmations {
+            let landed = outcome.as_ref().map(|(hash, _)| *hash);
+            for hash in &registered_hashes {
+                if Some(*hash) != landed {
+                    tracker.unregister(hash).await;
+                }
+            }
+        }
+
+        if let Some((_, receipt)) = outcome {
+            let escalated = metric.rebroadcast_count > 0;
+            self.record_inclusion(&mut metric, submit_time, ack_time, receipt, escalated).await;
+            if metric.success {
+                if let Some(gas_used) = metric.gas_used {
+                    metric.gas_delta = self.record_access_list_outcome(metric.used_access_list, gas_used).await;
+                }
+            }
+        }
+
         // Store metrics
         self.metrics.lock().await.push(metric.clone());
 
         Ok(metric)
     }
 
-    async fn wait_for_receipt(&self, tx_hash: B256) -> Result<TransactionReceipt> {
-        loop {
-            match self.provider.get_transaction_receipt(tx_hash).await? {
-                Some(receipt) => return Ok(receipt),
-                None => {
-                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    /// Record a landed receipt and feed the observed inclusion latency back
+    /// into the fee controller: immediate inclusions decay the tip, batches
+    /// that only landed after escalation keep the bumped tip in place
+    async fn record_inclusion(
+        &self,
+        metric: &mut TxMetrics,
+        submit_time: Instant,
+        ack_time: Instant,
+        receipt: TransactionReceipt,
+        escalated: bool,
+    ) {
+        let confirm_time = Instant::now();
+        let confirm_time_ms = confirm_time.duration_since(self.start_time).as_millis() as u64;
+        let latency = confirm_time.duration_since(submit_time).as_millis() as u64;
+        let confirmation_ms = confirm_time.duration_since(ack_time).as_millis() as u64;
+
+        metric.confirm_time_ms = Some(confirm_time_ms);
+        metric.latency_ms = Some(latency);
+        metric.confirmation_ms = Some(confirmation_ms);
+        metric.block_included = Some(receipt.block_number.unwrap_or(0));
+        metric.inclusion_blocks = match (metric.block_submitted, metric.block_included) {
+            (Some(submitted), Some(included)) => Some(included.saturating_sub(submitted)),
+            _ => None,
+        };
+        metric.gas_used = Some(receipt.gas_used);
+        metric.success = receipt.status();
+
+        self.confirm_histogram.lock().await.record(confirmation_ms);
+
+        if metric.success {
+            info!(
+                "Batch {} confirmed in {} ms ({} ms ack + {} ms on-chain, {} block(s)), gas: {}",
+                metric.sequence,
+                latency,
+                metric.submit_ack_ms.unwrap_or(0),
+                confirmation_ms,
+                metric.inclusion_blocks.unwrap_or(0),
+                receipt.gas_used
+            );
+
+            let mut controller = self.fee_controller.lock().await;
+            controller.record_latency(latency);
+            if !escalated && latency <= BLOCK_BUDGET_MS {
+                controller.on_immediate_inclusion();
+            }
+        } else {
+            warn!("Batch {} reverted", metric.sequence);
+            metric.error = Some("Transaction reverted".to_string());
+        }
+    }
+
+    /// Reserve the next nonce for a pipelined submission without an RPC
+    /// round-trip: the first call fetches the pending nonce from the chain,
+    /// every call after that hands out the local counter and increments it.
+    /// Keeping this under its own `Mutex` (rather than relying on
+    /// `NonceFiller`, which itself serializes on a fresh `eth_getTransactionCount`
+    /// per send) is what lets `submit_batch_pipelined` keep several
+    /// transactions in flight at once.
+    async fn reserve_nonce(&self) -> Result<u64> {
+        let mut next = self.next_nonce.lock().await;
+        if next.is_none() {
+            let n = self
+                .provider
+                .get_transaction_count(self.sender_address)
+                .pending()
+                .await
+                .map_err(|e| anyhow!("Failed to fetch starting nonce: {}", e))?;
+            *next = Some(n);
+        }
+        let nonce = next.expect("just initialized above");
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Refetch the pending nonce from the chain and overwrite the local
+    /// counter - used when the provider rejects a submission because its
+    /// nonce was already used, which means the local counter has drifted
+    /// from the chain's view (e.g. a transaction landed that this process
+    /// didn't send, or a prior resync raced with an in-flight submission).
+    async fn resync_nonce(&self) -> Result<u64> {
+        let n = self
+            .provider
+            .get_transaction_count(self.sender_address)
+            .pending()
+            .await
+            .map_err(|e| anyhow!("Failed to resync nonce: {}", e))?;
+        *self.next_nonce.lock().await = Some(n);
+        Ok(n)
+    }
+
+    /// Submit a batch without waiting for confirmation, stamping it with an
+    /// explicitly-reserved nonce and returning as soon as the node accepts
+    /// it into its mempool. A placeholder `TxMetrics` is stored immediately
+    /// so `get_metrics`/`print_summary` account for it even before it
+    /// lands; a background task fills in `confirm_time_ms`/`latency_ms`/
+    /// `gas_used` (matched back to `sequence`, out of order with respect to
+    /// other in-flight batches) once a receipt appears. Callers bound how
+    /// many of these run concurrently (e.g. with a `Semaphore`) to cap
+    /// `max_in_flight`.
+    pub async fn submit_batch_pipelined(self: Arc<Self>, sequence: u64, calldata: Vec<u8>) -> Result<B256> {
+        let submit_time = Instant::now();
+        let submit_time_ms = submit_time.duration_since(self.start_time).as_millis() as u64;
+        let data_size = calldata.len();
+
+        let fees = self.fee_quote().await?;
+        let nonce = self.reserve_nonce().await?;
+
+        let tx = TransactionRequest::default()
+            .to(self.target_address)
+            .with_input(Bytes::from(calldata))
+            .max_fee_per_gas(fees.max_fee_per_gas)
+            .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+            .nonce(nonce);
+
+        let pending_tx = match self.provider.send_transaction(tx).await {
+            Ok(pending_tx) => pending_tx,
+            Err(e) => {
+                if is_nonce_too_low(&e) {
+                    warn!(
+                        "Nonce {} for batch {} rejected as already used, resyncing from chain",
+                        nonce, sequence
+                    );
+                    let _ = self.resync_nonce().await;
+                }
+                return Err(anyhow!("Pipelined submit failed for batch {}: {:?}", sequence, e));
+            }
+        };
+
+        let tx_hash = *pending_tx.tx_hash();
+        let ack_time = Instant::now();
+        let submit_ack_ms = ack_time.duration_since(submit_time).as_millis() as u64;
+        self.ack_histogram.lock().await.record(submit_ack_ms);
+
+        debug!("Batch {} submitted pipelined: {:?} (nonce {})", sequence, tx_hash, nonce);
+
+        self.metrics.lock().await.push(TxMetrics {
+            sequence,
+            tx_hash,
+            submit_time_ms,
+            confirm_time_ms: None,
+            latency_ms: None,
+            submit_ack_ms: Some(submit_ack_ms),
+            confirmation_ms: None,
+            block_submitted: None,
+            block_included: None,
+            inclusion_blocks: None,
+            gas_used: None,
+            success: false,
+            error: None,
+            data_size,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+            rebroadcast_count: 0,
+            replacement_hashes: Vec::new(),
+            used_access_list: false,
+            gas_delta: None,
+        });
+
+        // Built up front (same ws-tracker-or-polling choice `submit_batch`
+        // makes) so registration with the WS tracker happens before this
+        // task is handed off, not racing the block watcher that resolves it
+        let wait_for_receipt: Pin<Box<dyn Future<Output = Result<TransactionReceipt>> + Send>> =
+            if let Some(tracker) = &self.ws_confirmations {
+                let receipt_rx = tracker.register(tx_hash).await;
+                Box::pin(async move {
+                    receipt_rx.await.map_err(|_| anyhow!("WS confirmation channel closed"))
+                })
+            } else {
+                Box::pin(async move { pending_tx.get_receipt().await.map_err(|e| anyhow!("{:?}", e)) })
+            };
+
+        let client = self.clone();
+        tokio::spawn(async move {
+            client
+                .reconcile_pipelined_confirmation(sequence, tx_hash, submit_time, ack_time, wait_for_receipt)
+                .await;
+        });
+
+        Ok(tx_hash)
+    }
+
+    /// Await one pipelined submission's confirmation and, once resolved
+    /// (landed, reverted, or timed out), update its stored `TxMetrics` in
+    /// place by matching on `sequence`
+    async fn reconcile_pipelined_confirmation(
+        self: Arc<Self>,
+        sequence: u64,
+        tx_hash: B256,
+        submit_time: Instant,
+        ack_time: Instant,
+        wait_for_receipt: Pin<Box<dyn Future<Output = Result<TransactionReceipt>> + Send>>,
+    ) {
+        let result = tokio::time::timeout(CONFIRM_TIMEOUT, wait_for_receipt).await;
+
+        if !matches!(result, Ok(Ok(_))) {
+            if let Some(tracker) = &self.ws_confirmations {
+                tracker.unregister(&tx_hash).await;
+            }
+        }
+
+        let mut metrics = self.metrics.lock().await;
+        let Some(metric) = metrics.iter_mut().find(|m| m.sequence == sequence) else {
+            return;
+        };
+
+        match result {
+            Ok(Ok(receipt)) => {
+                // Not `escalated` - pipelined submissions don't retry under
+                // a bumped tip, so the tip decays whenever the batch landed
+                // within `BLOCK_BUDGET_MS`
+                self.record_inclusion(metric, submit_time, ack_time, receipt, false).await;
+                if !metric.success {
+                    warn!(
+                        "Batch {} (nonce-ordered before later in-flight batches) reverted - \
+                         later-nonced pipelined submissions may be stuck behind it",
+                        sequence
+                    );
                 }
             }
+            Ok(Err(e)) => {
+                error!("Confirmation failed for pipelined batch {}: {:?}", sequence, e);
+                metric.error = Some(format!("Confirmation failed: {:?}", e));
+            }
+            Err(_) => {
+                error!("Timeout waiting for pipelined batch {}", sequence);
+                metric.error = Some(format!("Confirmation timeout ({:?})", CONFIRM_TIMEOUT));
+            }
         }
     }
 
+    /// Percentile breakdown from the streaming latency histograms, plus a
+    /// bytes/sec throughput figure, for comparing runs across endpoints
+    pub async fn latency_summary(&self) -> LatencySummary {
+        let metrics = self.get_metrics().await;
+        let (ack, confirm) = self.histograms().await;
+        build_latency_summary(&ack, &confirm, &metrics)
+    }
+
+    /// Clones of the streaming submit-acceptance/confirmation histograms, for
+    /// merging across endpoints in an `RpcPool`
+    pub async fn histograms(&self) -> (LatencyHistogram, LatencyHistogram) {
+        (
+            self.ack_histogram.lock().await.clone(),
+            self.confirm_histogram.lock().await.clone(),
+        )
+    }
+
     pub async fn get_metrics(&self) -> Vec<TxMetrics> {
         self.metrics.lock().await.clone()
     }
 
     pub async fn print_summary(&self) {
         let metrics = self.get_metrics().await;
-        let total = metrics.len();
-        let successful = metrics.iter().filter(|m| m.success).count();
-        let failed = total - successful;
+        let summary = self.latency_summary().await;
+        print_summary(&metrics, &summary);
+    }
+}
 
-        let latencies: Vec<u64> = metrics.iter().filter_map(|m| m.latency_ms).collect();
-        let avg_latency = if !latencies.is_empty() {
-            latencies.iter().sum::<u64>() as f64 / latencies.len() as f64
-        } else {
-            0.0
-        };
+/// Bump `prev` for a same-nonce replacement: at least `MIN_REPLACEMENT_BUMP_PCT`%
+/// over the previous attempt on both `maxFeePerGas` and `maxPriorityFeePerGas`
+/// (below this most clients reject the replacement as underpriced), taking
+/// the fee controller's own escalated tip as a floor so a rebroadcast also
+/// benefits from its congestion-aware bidding rather than just the bare minimum
+fn bump_for_replacement(prev: FeeQuote, controller_tip: u128) -> FeeQuote {
+    let min_bump = |fee: u128| {
+        // Ceiling division so the bump is never a hair under the required
+        // percentage due to integer truncation
+        fee + ((fee.saturating_mul(MIN_REPLACEMENT_BUMP_PCT) + 99) / 100).max(1)
+    };
 
-        let mut sorted_latencies = latencies.clone();
-        sorted_latencies.sort();
-        let p50 = if !sorted_latencies.is_empty() {
-            sorted_latencies[sorted_latencies.len() / 2]
-        } else {
-            0
-        };
-        let p95 = if !sorted_latencies.is_empty() {
-            sorted_latencies[(sorted_latencies.len() as f64 * 0.95) as usize]
-        } else {
-            0
-        };
-        let p99 = if !sorted_latencies.is_empty() {
-            sorted_latencies[(sorted_latencies.len() as f64 * 0.99) as usize]
-        } else {
-            0
-        };
+    let max_priority_fee_per_gas = controller_tip.max(min_bump(prev.max_priority_fee_per_gas));
+    let max_fee_per_gas = prev
+        .max_fee_per_gas
+        .max(min_bump(prev.max_fee_per_gas))
+        .max(max_priority_fee_per_gas);
 
-        let gas_values: Vec<u128> = metrics.iter().filter_map(|m| m.gas_used).collect();
-        let total_gas: u128 = gas_values.iter().sum();
-        let avg_gas = if !gas_values.is_empty() {
-            total_gas / gas_values.len() as u128
-        } else {
-            0
-        };
+    FeeQuote {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    }
+}
 
-        let total_data: usize = metrics.iter().map(|m| m.data_size).sum();
-
-        println!("\n╔═══════════════════════════════════════════════════════╗");
-        println!("║           RPC CLIENT TEST SUMMARY                     ║");
-        println!("╠═══════════════════════════════════════════════════════╣");
-        println!("║ Transactions                                          ║");
-        println!("║   Total:      {:>8}                                ║", total);
-        println!(
-            "║   Successful: {:>8} ({:>5.1}%)                      ║",
-            successful,
-            (successful as f64 / total as f64) * 100.0
-        );
-        println!(
-            "║   Failed:     {:>8} ({:>5.1}%)                      ║",
-            failed,
-            (failed as f64 / total as f64) * 100.0
-        );
-        println!("║                                                       ║");
-        println!("║ Latency (ms)                                          ║");
-        println!("║   Average:    {:>8.0}                                ║", avg_latency);
-        println!("║   P50:        {:>8}                                ║", p50);
-        println!("║   P95:        {:>8}                                ║", p95);
-        println!("║   P99:        {:>8}                                ║", p99);
-        println!("║                                                       ║");
-        println!("║ Gas Usage                                             ║");
-        println!("║   Average:    {:>8}                                ║", avg_gas);
-        println!("║   Total:      {:>8}                                ║", total_gas);
-        println!("║                                                       ║");
-        println!("║ Data                                                  ║");
-        println!("║   Total sent: {:>8} KB                            ║", total_data / 1024);
-        println!("╚═══════════════════════════════════════════════════════╝\n");
-
-        // Print errors if any
-        if failed > 0 {
-            println!("Errors encountered:");
-            for metric in metrics.iter() {
-                if let Some(error) = &metric.error {
-                    println!("  [Seq {}] {}", metric.sequence, error);
-                }
+/// Best-effort sniff of a send error for the node's "nonce too low"
+/// rejection - alloy surfaces this as an opaque JSON-RPC error string rather
+/// than a typed variant, so matching on the phrase every major client
+/// (geth, reth, erigon) uses is the practical option. "already known" and
+/// "replacement transaction underpriced" are matched too, since both also
+/// mean the nonce is already taken
+fn is_nonce_too_low(error: &impl std::fmt::Debug) -> bool {
+    let message = format!("{:?}", error).to_lowercase();
+    message.contains("nonce too low") || message.contains("already known") || message.contains("replacement transaction underpriced")
+}
+
+/// Build the percentile/throughput summary from a pair of streaming
+/// histograms plus the raw metrics (used for total bytes, time span and the
+/// average priority fee of successful submissions)
+pub fn build_latency_summary(
+    ack: &LatencyHistogram,
+    confirm: &LatencyHistogram,
+    metrics: &[TxMetrics],
+) -> LatencySummary {
+    let total_data: usize = metrics.iter().map(|m| m.data_size).sum();
+    let submit_times: Vec<u64> = metrics.iter().map(|m| m.submit_time_ms).collect();
+    let span_ms = match (submit_times.iter().min(), submit_times.iter().max()) {
+        (Some(&min), Some(&max)) if max > min => max - min,
+        _ => 0,
+    };
+    let throughput_bytes_per_sec = if span_ms > 0 {
+        total_data as f64 / (span_ms as f64 / 1000.0)
+    } else {
+        0.0
+    };
+
+    let successful_fees: Vec<u128> = metrics
+        .iter()
+        .filter(|m| m.success)
+        .map(|m| m.max_priority_fee_per_gas)
+        .collect();
+    let avg_priority_fee_wei = if !successful_fees.is_empty() {
+        successful_fees.iter().sum::<u128>() / successful_fees.len() as u128
+    } else {
+        0
+    };
+
+    LatencySummary {
+        submit_ack_p50_ms: ack.p50(),
+        submit_ack_p90_ms: ack.p90(),
+        submit_ack_p99_ms: ack.p99(),
+        submit_ack_max_ms: ack.max(),
+        confirmation_p50_ms: confirm.p50(),
+        confirmation_p90_ms: confirm.p90(),
+        confirmation_p99_ms: confirm.p99(),
+        confirmation_max_ms: confirm.max(),
+        throughput_bytes_per_sec,
+        avg_priority_fee_wei,
+    }
+}
+
+/// Render the test summary for a set of collected metrics plus their latency
+/// histogram breakdown (shared between a single `RpcClient` and an `RpcPool`
+/// aggregating across endpoints)
+pub fn print_summary(metrics: &[TxMetrics], latency: &LatencySummary) {
+    let total = metrics.len();
+    let successful = metrics.iter().filter(|m| m.success).count();
+    let failed = total - successful;
+
+    let gas_values: Vec<u128> = metrics.iter().filter_map(|m| m.gas_used).collect();
+    let total_gas: u128 = gas_values.iter().sum();
+    let avg_gas = if !gas_values.is_empty() {
+        total_gas / gas_values.len() as u128
+    } else {
+        0
+    };
+
+    let total_data: usize = metrics.iter().map(|m| m.data_size).sum();
+    let rebroadcast_batches = metrics.iter().filter(|m| m.rebroadcast_count > 0).count();
+    let total_rebroadcasts: u32 = metrics.iter().map(|m| m.rebroadcast_count).sum();
+
+    let access_list_batches = metrics.iter().filter(|m| m.used_access_list).count();
+    let gas_deltas: Vec<i64> = metrics.iter().filter_map(|m| m.gas_delta).collect();
+    let avg_gas_delta = if !gas_deltas.is_empty() {
+        Some(gas_deltas.iter().sum::<i64>() as f64 / gas_deltas.len() as f64)
+    } else {
+        None
+    };
+
+    println!("\n╔═══════════════════════════════════════════════════════╗");
+    println!("║           RPC CLIENT TEST SUMMARY                     ║");
+    println!("╠═══════════════════════════════════════════════════════╣");
+    println!("║ Transactions                                          ║");
+    println!("║   Total:      {:>8}                                ║", total);
+    println!(
+        "║   Successful: {:>8} ({:>5.1}%)                      ║",
+        successful,
+        (successful as f64 / total as f64) * 100.0
+    );
+    println!(
+        "║   Failed:     {:>8} ({:>5.1}%)                      ║",
+        failed,
+        (failed as f64 / total as f64) * 100.0
+    );
+    println!("║                                                       ║");
+    println!("║ Submit-acceptance latency (ms)                        ║");
+    println!("║   P50:        {:>8}                                ║", latency.submit_ack_p50_ms);
+    println!("║   P90:        {:>8}                                ║", latency.submit_ack_p90_ms);
+    println!("║   P99:        {:>8}                                ║", latency.submit_ack_p99_ms);
+    println!("║   Max:        {:>8}                                ║", latency.submit_ack_max_ms);
+    println!("║                                                       ║");
+    println!("║ On-chain confirmation latency (ms)                    ║");
+    println!("║   P50:        {:>8}                                ║", latency.confirmation_p50_ms);
+    println!("║   P90:        {:>8}                                ║", latency.confirmation_p90_ms);
+    println!("║   P99:        {:>8}                                ║", latency.confirmation_p99_ms);
+    println!("║   Max:        {:>8}                                ║", latency.confirmation_max_ms);
+    println!("║                                                       ║");
+    println!("║ Fees                                                  ║");
+    println!("║   Avg priority fee: {:>8} wei                  ║", latency.avg_priority_fee_wei);
+    println!("║                                                       ║");
+    println!("║ Stuck-tx rebroadcasts                                 ║");
+    println!("║   Batches rebroadcast: {:>8}                       ║", rebroadcast_batches);
+    println!("║   Total rebroadcasts:  {:>8}                       ║", total_rebroadcasts);
+    println!("║                                                       ║");
+    println!("║ EIP-2930 access list                                  ║");
+    println!("║   Batches with list:   {:>8}                       ║", access_list_batches);
+    match avg_gas_delta {
+        Some(delta) => println!("║   Avg gas delta:       {:>8.0}                       ║", delta),
+        None => println!("║   Avg gas delta:            n/a                       ║"),
+    }
+    println!("║                                                       ║");
+    println!("║ Gas Usage                                             ║");
+    println!("║   Average:    {:>8}                                ║", avg_gas);
+    println!("║   Total:      {:>8}                                ║", total_gas);
+    println!("║                                                       ║");
+    println!("║ Data                                                  ║");
+    println!("║   Total sent: {:>8} KB                            ║", total_data / 1024);
+    println!(
+        "║   Throughput: {:>8.1} KB/s                        ║",
+        latency.throughput_bytes_per_sec / 1024.0
+    );
+    println!("╚═══════════════════════════════════════════════════════╝\n");
+
+    // Print errors if any
+    if failed > 0 {
+        println!("Errors encountered:");
+        for metric in metrics.iter() {
+            if let Some(error) = &metric.error {
+                println!("  [Seq {}] {}", metric.sequence, error);
             }
-            println!();
         }
+        println!();
     }
 }
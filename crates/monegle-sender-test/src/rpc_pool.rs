@@ -0,0 +1,343 @@
+use alloy::primitives::{Address, B256};
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::latency_histogram::LatencyHistogram;
+use crate::rpc_client::{self, build_latency_summary, LatencySummary, RpcClient, TxMetrics};
+use crate::ws_confirmation::WsConfirmationTracker;
+
+/// Cooldown applied to an endpoint after it fails, doubled on repeated
+/// consecutive failures and capped below.
+const BASE_QUARANTINE: Duration = Duration::from_secs(5);
+const MAX_QUARANTINE: Duration = Duration::from_secs(120);
+
+/// Per-endpoint rolling health used to pick the "healthiest" RPC to route to
+#[derive(Debug)]
+struct EndpointHealth {
+    url: String,
+    successes: u64,
+    failures: u64,
+    consecutive_failures: u32,
+    last_latency_ms: Option<u64>,
+    quarantined_until: Option<Instant>,
+}
+
+impl EndpointHealth {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            successes: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            last_latency_ms: None,
+            quarantined_until: None,
+        }
+    }
+
+    fn success_rate(&self) -> f64 {
+        let total = self.successes + self.failures;
+        if total == 0 {
+            1.0 // Assume healthy until proven otherwise
+        } else {
+            self.successes as f64 / total as f64
+        }
+    }
+
+    fn is_quarantined(&self) -> bool {
+        self.quarantined_until
+            .map(|until| Instant::now() < until)
+            .unwrap_or(false)
+    }
+
+    fn record_success(&mut self, latency_ms: u64) {
+        self.successes += 1;
+        self.consecutive_failures = 0;
+        self.last_latency_ms = Some(latency_ms);
+        self.quarantined_until = None;
+    }
+
+    fn record_failure(&mut self) {
+        self.failures += 1;
+        self.consecutive_failures += 1;
+
+        let cooldown = BASE_QUARANTINE
+            .saturating_mul(1 << (self.consecutive_failures - 1).min(5))
+            .min(MAX_QUARANTINE);
+        self.quarantined_until = Some(Instant::now() + cooldown);
+
+        warn!(
+            "Endpoint {} quarantined for {:?} ({} consecutive failures)",
+            self.url, cooldown, self.consecutive_failures
+        );
+    }
+
+    /// Score endpoints so the healthiest sorts first: fewer consecutive
+    /// failures, then higher success rate, then lower latency.
+    fn score(&self) -> (u32, i64, u64) {
+        let rate_inv = ((1.0 - self.success_rate()) * 1000.0) as i64;
+        (self.consecutive_failures, rate_inv, self.last_latency_ms.unwrap_or(0))
+    }
+}
+
+/// A pool of RPC endpoints that routes submissions to the currently
+/// healthiest one, fails over to the next on error, and quarantines
+/// endpoints that misbehave until their cooldown expires or, sooner, a
+/// background probe of them succeeds. An endpoint whose cooldown runs out
+/// returns to rotation without being probed first.
+pub struct RpcPool {
+    clients: Vec<Arc<RpcClient>>,
+    health: Mutex<Vec<EndpointHealth>>,
+}
+
+impl RpcPool {
+    /// Connect to every RPC URL in the list up front
+    pub async fn new(
+        rpc_urls: &[String],
+        private_key: &str,
+        target_address: Address,
+    ) -> Result<Self> {
+        Self::new_with_fees(rpc_urls, private_key, target_address, 50.0, 5_000_000_000, 15, None).await
+    }
+
+    /// Same as [`RpcPool::new`] but with explicit adaptive fee-bidding knobs,
+    /// forwarded to every `RpcClient` in the pool. `stream_fps` sets each
+    /// client's congestion-tracking target latency. `ws_url`, if given,
+    /// opens a single `WsConfirmationTracker` shared by every client in the
+    /// pool - block headers are chain-wide, so one subscription suffices
+    /// regardless of which endpoint a batch was submitted through.
+    pub async fn new_with_fees(
+        rpc_urls: &[String],
+        private_key: &str,
+        target_address: Address,
+        fee_percentile: f64,
+        max_priority_fee_wei: u128,
+        stream_fps: u8,
+        ws_url: Option<&str>,
+    ) -> Result<Self> {
+        Self::new_with_resubmit(
+            rpc_urls,
+            private_key,
+            target_address,
+            fee_percentile,
+            max_priority_fee_wei,
+            stream_fps,
+            ws_url,
+            rpc_client::DEFAULT_STUCK_TIMEOUT,
+            rpc_client::DEFAULT_MAX_REBROADCASTS,
+        )
+        .await
+    }
+
+    /// Same as [`RpcPool::new_with_fees`] but with explicit stuck-tx
+    /// rebroadcast knobs, forwarded to every `RpcClient` in the pool - see
+    /// `RpcClient::new_with_resubmit`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new_with_resubmit(
+        rpc_urls: &[String],
+        private_key: &str,
+        target_address: Address,
+        fee_percentile: f64,
+        max_priority_fee_wei: u128,
+        stream_fps: u8,
+        ws_url: Option<&str>,
+        stuck_timeout: Duration,
+        max_rebroadcasts: u32,
+    ) -> Result<Self> {
+        if rpc_urls.is_empty() {
+            return Err(anyhow!("RpcPool requires at least one RPC URL"));
+        }
+
+        info!("Initializing RPC pool with {} endpoint(s)", rpc_urls.len());
+
+        let ws_confirmations = match ws_url {
+            Some(url) => Some(Arc::new(WsConfirmationTracker::connect(url).await?)),
+            None => None,
+        };
+
+        let mut clients = Vec::with_capacity(rpc_urls.len());
+        let mut health = Vec::with_capacity(rpc_urls.len());
+
+        for url in rpc_urls {
+            let client = RpcClient::new_with_resubmit(
+                url,
+                private_key,
+                target_address,
+                fee_percentile,
+                max_priority_fee_wei,
+                stream_fps,
+                ws_confirmations.clone(),
+                stuck_timeout,
+                max_rebroadcasts,
+            )
+            .await?;
+            clients.push(Arc::new(client));
+            health.push(EndpointHealth::new(url.clone()));
+        }
+
+        Ok(Self {
+            clients,
+            health: Mutex::new(health),
+        })
+    }
+
+    /// The wallet address every endpoint in the pool submits from (every
+    /// client shares the same private key, so any one's is representative)
+    pub fn sender_address(&self) -> Address {
+        self.clients[0].sender_address()
+    }
+
+    /// Order endpoint indices best-first, skipping quarantined ones
+    async fn ranked_endpoints(&self) -> Vec<usize> {
+        let health = self.health.lock().await;
+        let mut indices: Vec<usize> = (0..health.len())
+            .filter(|&i| !health[i].is_quarantined())
+            .collect();
+
+        // If everything is quarantined, fall back to trying all of them
+        // anyway rather than failing the batch outright
+        if indices.is_empty() {
+            indices = (0..health.len()).collect();
+        }
+
+        indices.sort_by_key(|&i| health[i].score());
+        indices
+    }
+
+    /// Submit a batch, routing to the healthiest endpoint and retrying
+    /// against the next-best one on failure or timeout
+    pub async fn submit_batch(&self, sequence: u64, calldata: Vec<u8>) -> Result<TxMetrics> {
+        let ranked = self.ranked_endpoints().await;
+        let mut last_err = None;
+
+        for idx in ranked {
+            let start = Instant::now();
+
+            match self.clients[idx].submit_batch(sequence, calldata.clone()).await {
+                Ok(metric) if metric.success => {
+                    let latency_ms = start.elapsed().as_millis() as u64;
+                    self.health.lock().await[idx].record_success(latency_ms);
+                    return Ok(metric);
+                }
+                Ok(metric) => {
+                    // Submitted but reverted/failed on-chain - still counts
+                    // against this endpoint's health since it didn't land
+                    self.health.lock().await[idx].record_failure();
+                    last_err = Some(anyhow!(
+                        "batch {} failed on endpoint {}: {:?}",
+                        sequence, self.clients[idx].rpc_url(), metric.error
+                    ));
+                }
+                Err(e) => {
+                    self.health.lock().await[idx].record_failure();
+                    warn!("Batch {} failed on endpoint {}: {}", sequence, self.clients[idx].rpc_url(), e);
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow!("No RPC endpoints available for batch {}", sequence)))
+    }
+
+    /// Submit a batch without waiting for confirmation, for a pipelined run
+    /// with up to K batches in flight at once. Pinned to whichever endpoint
+    /// is healthiest at call time for the rest of that submission's
+    /// lifetime rather than retried across endpoints like `submit_batch` -
+    /// nonce sequencing lives on the individual `RpcClient`, and
+    /// round-robining submissions for one account across endpoints with
+    /// independent local counters would split that sequence and collide.
+    pub async fn submit_batch_pipelined(&self, sequence: u64, calldata: Vec<u8>) -> Result<B256> {
+        let idx = self.ranked_endpoints().await[0];
+        self.clients[idx].clone().submit_batch_pipelined(sequence, calldata).await
+    }
+
+    /// Block until every metric currently tracked has reached a terminal
+    /// state (confirmed or errored) or `timeout` elapses, whichever comes
+    /// first - for draining in-flight pipelined confirmations once a run's
+    /// ticker stops firing, so the final summary reflects every submitted
+    /// batch instead of only whatever had landed by then
+    pub async fn drain_pipelined(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let metrics = self.get_metrics().await;
+            let pending = metrics.iter().filter(|m| !m.success && m.error.is_none()).count();
+            if pending == 0 {
+                return;
+            }
+            if Instant::now() >= deadline {
+                warn!("{} pipelined batch(es) still unconfirmed after drain timeout", pending);
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+
+    /// Collected metrics across every endpoint in the pool, in submission order
+    pub async fn get_metrics(&self) -> Vec<TxMetrics> {
+        let mut all = Vec::new();
+        for client in &self.clients {
+            all.extend(client.get_metrics().await);
+        }
+        all.sort_by_key(|m| m.submit_time_ms);
+        all
+    }
+
+    pub async fn print_summary(&self) {
+        let metrics = self.get_metrics().await;
+        let latency = self.latency_summary().await;
+        rpc_client::print_summary(&metrics, &latency);
+    }
+
+    /// Percentile/throughput summary merged across every endpoint's
+    /// streaming latency histograms
+    pub async fn latency_summary(&self) -> LatencySummary {
+        let mut ack = LatencyHistogram::new();
+        let mut confirm = LatencyHistogram::new();
+
+        for client in &self.clients {
+            let (client_ack, client_confirm) = client.histograms().await;
+            ack.merge(&client_ack);
+            confirm.merge(&client_confirm);
+        }
+
+        let metrics = self.get_metrics().await;
+        build_latency_summary(&ack, &confirm, &metrics)
+    }
+
+    /// Periodically re-probe quarantined endpoints with a cheap call and
+    /// return them to rotation once they respond successfully again
+    pub async fn start_health_prober(self: Arc<Self>, probe_interval: Duration) {
+        info!("Starting RPC pool health prober (interval: {:?})", probe_interval);
+
+        let mut interval = tokio::time::interval(probe_interval);
+
+        loop {
+            interval.tick().await;
+
+            let quarantined: Vec<usize> = {
+                let health = self.health.lock().await;
+                (0..health.len()).filter(|&i| health[i].is_quarantined()).collect()
+            };
+
+            for idx in quarantined {
+                let start = Instant::now();
+                match self.clients[idx].get_block_number().await {
+                    Ok(block) => {
+                        let latency_ms = start.elapsed().as_millis() as u64;
+                        let mut health = self.health.lock().await;
+                        health[idx].record_success(latency_ms);
+                        info!(
+                            "Endpoint {} re-probed successfully (block {}), returning to rotation",
+                            health[idx].url, block
+                        );
+                    }
+                    Err(e) => {
+                        debug!("Endpoint {} still unhealthy: {}", self.clients[idx].rpc_url(), e);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use alloy::primitives::B256;
+use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+use alloy::rpc::types::TransactionReceipt;
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, Mutex};
+use tracing::{debug, info, warn};
+
+/// Initial reconnect backoff delay, doubled after every reconnect up to
+/// `RECONNECT_MAX_DELAY`; it is not reset after a session that stayed up
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(250);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Event-driven replacement for `wait_for_receipt`'s busy-poll: subscribes
+/// to new block headers over a WebSocket provider and, on every block,
+/// checks whether a receipt has appeared for any transaction hash
+/// `RpcClient::submit_batch` is waiting on. A confirmed batch hears about
+/// its receipt the instant the block containing it arrives instead of up to
+/// 500ms later, and no `get_transaction_receipt` call is wasted on hashes
+/// nobody's watching for.
+pub struct WsConfirmationTracker {
+    pending: Arc<Mutex<HashMap<B256, oneshot::Sender<TransactionReceipt>>>>,
+}
+
+impl WsConfirmationTracker {
+    /// Connect to `ws_url` and spawn the background block-watching task.
+    /// The task reconnects with exponential backoff on disconnect rather
+    /// than giving up - a caller's `submit_batch` still has its own
+    /// `CONFIRM_TIMEOUT` as a backstop while a reconnect is in progress.
+    pub async fn connect(ws_url: &str) -> Result<Self> {
+        let pending: Arc<Mutex<HashMap<B256, oneshot::Sender<TransactionReceipt>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+
+        let watch_pending = pending.clone();
+        let ws_url = ws_url.to_string();
+        tokio::spawn(async move {
+            let mut backoff = RECONNECT_BASE_DELAY;
+            loop {
+                match run_block_watch(&ws_url, &watch_pending).await {
+                    Ok(()) => return,
+                    Err(e) => {
+                        warn!("WS confirmation watcher on {} ended ({}), reconnecting in {:?}", ws_url, e, backoff);
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(RECONNECT_MAX_DELAY);
+                    }
+                }
+            }
+        });
+
+        Ok(Self { pending })
+    }
+
+    /// Register interest in `tx_hash`'s receipt, returning the receiver
+    /// half of the oneshot the block watcher resolves. Callers must
+    /// `unregister` if they stop waiting without the receiver firing
+    /// (timeout, resubmission under a new hash), so the map doesn't
+    /// accumulate stale entries for transactions that were replaced.
+    pub async fn register(&self, tx_hash: B256) -> oneshot::Receiver<TransactionReceipt> {
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(tx_hash, tx);
+        rx
+    }
+
+    pub async fn unregister(&self, tx_hash: &B256) {
+        self.pending.lock().await.remove(tx_hash);
+    }
+}
+
+/// Connect, subscribe to new block headers, and on each one resolve any
+/// pending hash whose receipt has appeared. Returns only on a fatal stream
+/// end so the caller's reconnect loop can take over.
+async fn run_block_watch(
+    ws_url: &str,
+    pending: &Arc<Mutex<HashMap<B256, oneshot::Sender<TransactionReceipt>>>>,
+) -> Result<()> {
+    let ws = WsConnect::new(ws_url);
+    let provider = ProviderBuilder::new()
+        .on_ws(ws)
+        .await
+        .map_err(|e| anyhow!("Failed to connect WS confirmation watcher: {}", e))?;
+
+    info!("WS confirmation watcher subscribed to new blocks on {}", ws_url);
+
+    let sub = provider
+        .subscribe_blocks()
+        .await
+        .map_err(|e| anyhow!("Failed to subscribe to blocks: {}", e))?;
+    let mut stream = sub.into_stream();
+
+    while stream.next().await.is_some() {
+        let hashes: Vec<B256> = pending.lock().await.keys().copied().collect();
+        if hashes.is_empty() {
+            continue;
+        }
+
+        for hash in hashes {
+            match provider.get_transaction_receipt(hash).await {
+                Ok(Some(receipt)) => {
+                    if let Some(sender) = pending.lock().await.remove(&hash) {
+                        debug!("WS confirmation watcher resolved receipt for {:?}", hash);
+                        let _ = sender.send(receipt);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => debug!("WS confirmation watcher receipt lookup for {:?} failed: {}", hash, e),
+            }
+        }
+    }
+
+    Err(anyhow!("WS confirmation watcher block stream ended unexpectedly"))
+}
@@ -1,5 +1,5 @@
 use anyhow::{anyhow, Result};
-use monegle_core::{CompressedFrame, FrameBatch, StreamMetadata, StreamId, get_encoder, CompressionType};
+use monegle_core::{CompressedFrame, FrameBatch, StreamMetadata, StreamId, TerminalGrid, get_encoder, CompressionType, Lz4StreamEncoder};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
@@ -15,7 +15,16 @@ pub struct FrameBatcher {
     current_batch: Vec<CompressedFrame>,
     sequence_counter: u64,
     frame_counter: u64,
-    previous_frame: Option<String>,
+
+    /// Cell grid of the last frame submitted, used to compute the
+    /// cursor-addressed cell diff for non-keyframes (see `TerminalGrid`).
+    /// `None` before the first keyframe.
+    previous_grid: Option<TerminalGrid>,
+
+    /// Streaming LZ4 window, carried across calls instead of rebuilt per
+    /// call like `get_encoder`'s stateless encoders - only touched when
+    /// `compression_type` is `Lz4Stream` (see `Lz4StreamEncoder`)
+    lz4_stream_encoder: Lz4StreamEncoder,
 }
 
 impl FrameBatcher {
@@ -50,23 +59,34 @@ impl FrameBatcher {
             current_batch: Vec::with_capacity(frames_per_batch),
             sequence_counter: 0,
             frame_counter: 0,
-            previous_frame: None,
+            previous_grid: None,
+            lz4_stream_encoder: Lz4StreamEncoder::new(),
         }
     }
 
-    /// Add a frame to the batch
+    /// Add a frame to the batch. Keyframes carry the full frame text;
+    /// every other frame is reduced to a `TerminalGrid` cell diff against
+    /// the previous frame first (only the cells that actually changed,
+    /// addressed by cursor position), which is what actually gets handed
+    /// to `compress_frame` - on-chain payload shrinks well beyond what
+    /// `CompressionType` alone buys, since most cells repeat frame to
+    /// frame.
     pub fn add_frame(&mut self, ascii_frame: String) -> Result<Option<FrameBatch>> {
         let is_keyframe = self.frame_counter % self.keyframe_interval == 0;
+        let grid = TerminalGrid::parse(&ascii_frame, self.metadata.width, self.metadata.height);
 
-        // Compress the frame
-        let compressed = self.compress_frame(
-            &ascii_frame,
-            if is_keyframe { None } else { self.previous_frame.as_deref() },
-            is_keyframe,
-        )?;
+        let frame_to_compress = if is_keyframe {
+            ascii_frame
+        } else {
+            let blank = TerminalGrid::blank(self.metadata.width, self.metadata.height);
+            grid.diff(self.previous_grid.as_ref().unwrap_or(&blank))
+        };
 
-        // Store for next delta encoding
-        self.previous_frame = Some(ascii_frame);
+        self.previous_grid = Some(grid);
+
+        // The cell diff is already the delta against the previous frame,
+        // so the underlying codec never needs its own `previous` context
+        let compressed = self.compress_frame(&frame_to_compress, None, is_keyframe)?;
 
         // Check if adding this frame would exceed the limit BEFORE adding it
         // This prevents oversized batches
@@ -116,16 +136,25 @@ impl FrameBatcher {
         Ok(None)
     }
 
-    /// Compress a single frame
+    /// Compress a single frame. `Lz4Stream` is handled separately from
+    /// `get_encoder`'s stateless encoders: it carries a ring-buffer window
+    /// across calls, reset on every keyframe boundary so a receiver
+    /// resyncing after a gap never decodes against window contents it
+    /// never saw.
     fn compress_frame(
-        &self,
+        &mut self,
         frame: &str,
         previous: Option<&str>,
         is_keyframe: bool,
     ) -> Result<CompressedFrame> {
-        let encoder = get_encoder(self.compression_type);
-
-        let data = encoder.encode(frame, previous)?;
+        let data = if self.compression_type == CompressionType::Lz4Stream {
+            if is_keyframe {
+                self.lz4_stream_encoder.reset();
+            }
+            self.lz4_stream_encoder.encode(frame)?
+        } else {
+            get_encoder(self.compression_type).encode(frame, previous)?
+        };
 
         Ok(CompressedFrame {
             compression_type: self.compression_type,
@@ -142,11 +171,13 @@ impl FrameBatcher {
             stream_id: self.stream_id,
             sequence: self.sequence_counter,
             metadata: self.metadata.clone(),
+            base_frame_number: self.current_batch.first().map(|f| f.frame_number).unwrap_or(0),
             frames: self.current_batch.clone(),
-            timestamp: SystemTime::now()
+            base_timestamp: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64,
+            crc: 0,
         };
 
         temp_batch.size_bytes()
@@ -163,13 +194,16 @@ impl FrameBatcher {
             .map_err(|e| anyhow!("System time error: {}", e))?
             .as_millis() as u64;
 
-        let batch = FrameBatch {
+        let mut batch = FrameBatch {
             stream_id: self.stream_id,
             sequence: self.sequence_counter,
             metadata: self.metadata.clone(),
+            base_frame_number: self.current_batch.first().map(|f| f.frame_number).unwrap_or(0),
             frames: self.current_batch.clone(),
-            timestamp,
+            base_timestamp: timestamp,
+            crc: 0,
         };
+        batch.crc = batch.compute_crc()?;
 
         let size = batch.size_bytes();
         debug!(
@@ -1,5 +1,6 @@
 use alloy::{
-    network::{EthereumWallet, TransactionBuilder},
+    consensus::{SidecarBuilder, SimpleCoder},
+    network::{EthereumWallet, TransactionBuilder, TransactionBuilder4844},
     primitives::{Address, Bytes},
     providers::{Provider, ProviderBuilder},
     rpc::types::{TransactionReceipt, TransactionRequest},
@@ -7,11 +8,36 @@ use alloy::{
     transports::http::reqwest::Url,
 };
 use anyhow::{anyhow, Result};
-use monegle_core::FrameBatch;
+use monegle_core::{FrameBatch, FrameBatchCodec};
 use std::str::FromStr;
-use tokio::sync::mpsc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Semaphore};
+use tokio_util::codec::Encoder;
 use tracing::{debug, info, warn, error};
 
+/// How many transactions may be in flight (submitted, not yet confirmed) at
+/// once in the pipelined submission loop
+const DEFAULT_PIPELINE_WINDOW: usize = 8;
+
+/// Starting priority fee for a batch's first submission
+const BASE_PRIORITY_FEE_WEI: u128 = 1_000_000_000; // 1 gwei
+
+/// Ceiling the fee-bump escalation will not go past
+const MAX_PRIORITY_FEE_WEI: u128 = 50_000_000_000; // 50 gwei
+
+/// Multiplicative bump applied to the priority fee when a submission stalls
+/// past its budget or a resubmission is rejected as underpriced
+const FEE_BUMP_FACTOR: f64 = 1.25;
+
+/// How long to wait for a receipt before bumping the fee and resubmitting
+/// under the same nonce
+const STALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Ceiling on how many times a single nonce will have its fee bumped before
+/// the batch is given up on
+const MAX_FEE_BUMP_ATTEMPTS: u32 = 8;
+
 type FilledProvider = alloy::providers::fillers::FillProvider<
     alloy::providers::fillers::JoinFill<
         alloy::providers::fillers::JoinFill<
@@ -34,25 +60,52 @@ type FilledProvider = alloy::providers::fillers::FillProvider<
     alloy::network::Ethereum,
 >;
 
+/// How a `BlockchainSender` ships frame batches on-chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubmissionMode {
+    /// Frame data goes in transaction calldata - cheap setup, but calldata
+    /// is expensive per byte
+    #[default]
+    Calldata,
+
+    /// Frame data goes in an EIP-4844 blob sidecar - much cheaper per byte
+    /// and fits far more data (multiple ~128KB blobs) in one transaction
+    Blob,
+}
+
 /// Blockchain sender component
-/// Uses raw transaction approach: sends frame data in calldata to target address
-/// No smart contract needed! Receiver monitors transactions via WebSocket RPC
+/// Uses raw transaction approach: sends frame data in calldata (or, in Blob
+/// mode, a blob sidecar) to target address. No smart contract needed!
+/// Receiver monitors transactions via WebSocket RPC
 pub struct BlockchainSender {
     provider: FilledProvider,
     target_address: Address,
     sender_address: Address,
+    submission_mode: SubmissionMode,
 }
 
 impl BlockchainSender {
     /// Initialize blockchain sender with raw transactions (no contract needed!)
+    /// Defaults to calldata submission; use `new_with_mode` for blob mode.
     pub async fn new(
         rpc_url: &str,
         private_key: &str,
         target_address: &str,
+    ) -> Result<Self> {
+        Self::new_with_mode(rpc_url, private_key, target_address, SubmissionMode::default()).await
+    }
+
+    /// Initialize blockchain sender with an explicit submission mode
+    pub async fn new_with_mode(
+        rpc_url: &str,
+        private_key: &str,
+        target_address: &str,
+        submission_mode: SubmissionMode,
     ) -> Result<Self> {
         info!("Initializing blockchain sender with raw transaction approach");
         info!("RPC URL: {}", rpc_url);
         info!("Target: {}", target_address);
+        info!("Submission mode: {:?}", submission_mode);
 
         // Parse private key
         let signer = PrivateKeySigner::from_str(private_key)
@@ -80,6 +133,7 @@ impl BlockchainSender {
             provider,
             target_address: target_addr,
             sender_address,
+            submission_mode,
         })
     }
 
@@ -94,9 +148,11 @@ impl BlockchainSender {
     pub async fn submit_batch_fast(&self, batch: &FrameBatch) -> Result<String> {
         debug!("Submitting batch {} via raw transaction", batch.sequence);
 
-        // Encode frame batch to bytes
-        let encoded = batch.encode_to_bytes()?;
-        let calldata = Bytes::from(encoded);
+        // Frame the batch with the same `FrameBatchCodec` used by non-chain
+        // transports, so calldata and e.g. a raw TCP stream share one wire format
+        let mut encoded = bytes::BytesMut::new();
+        FrameBatchCodec::new().encode(batch.clone(), &mut encoded)?;
+        let calldata = Bytes::from(encoded.freeze());
         let calldata_len = calldata.len();
 
         debug!("Batch {} encoded: {} bytes", batch.sequence, calldata_len);
@@ -126,89 +182,257 @@ impl BlockchainSender {
         Ok(tx_hash)
     }
 
-    /// Start submission loop (RATE-LIMITED MODE - submits with small delay between txs)
+    /// Submit a batch of frames via an EIP-4844 blob sidecar instead of
+    /// calldata. Blobs cost far less per byte than calldata and each one
+    /// carries up to ~128KB (a transaction can carry several), so this is
+    /// the throughput path for large or batched frames; `submit_batch_fast`
+    /// stays the cheaper choice for small ones since a blob transaction has
+    /// fixed overhead of its own. Receivers must read the blob sidecar
+    /// rather than calldata for transactions submitted this way.
+    pub async fn submit_batch_as_blob(&self, batch: &FrameBatch) -> Result<String> {
+        debug!("Submitting batch {} via blob sidecar", batch.sequence);
+
+        // Frame the batch with the same `FrameBatchCodec` used by calldata
+        // and non-chain transports, so all three share one wire format
+        let mut encoded = bytes::BytesMut::new();
+        FrameBatchCodec::new().encode(batch.clone(), &mut encoded)?;
+
+        let mut sidecar_builder = SidecarBuilder::<SimpleCoder>::new();
+        sidecar_builder.ingest(&encoded);
+        let sidecar = sidecar_builder
+            .build()
+            .map_err(|e| anyhow!("Failed to build blob sidecar: {}", e))?;
+
+        let blob_count = sidecar.blobs.len();
+
+        // Blob gas (max_fee_per_blob_gas) and blob versioned hashes are
+        // filled in automatically by the provider's BlobGasFiller
+        let tx = TransactionRequest::default()
+            .to(self.target_address)
+            .with_blob_sidecar(sidecar);
+
+        let pending_tx = self.provider.send_transaction(tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to send blob transaction: {}", e);
+                anyhow!("Blob transaction send failed: {}", e)
+            })?;
+
+        let tx_hash = format!("{:?}", pending_tx.tx_hash());
+
+        info!(
+            "Batch {} submitted as blob: tx={}, {} blob(s), {}KB (not waiting for confirmation)",
+            batch.sequence,
+            tx_hash,
+            blob_count,
+            encoded.len() / 1024
+        );
+
+        Ok(tx_hash)
+    }
+
+    /// Start submission loop (PIPELINED MODE): pins a starting nonce and
+    /// assigns each batch the next one in arrival order (nonce == sequence
+    /// mapping preserves frame order), keeping up to `DEFAULT_PIPELINE_WINDOW`
+    /// transactions in flight concurrently instead of sleeping between
+    /// submissions.
     pub async fn start_submission_loop(
+        self,
+        rx: mpsc::Receiver<FrameBatch>,
+    ) -> Result<()> {
+        self.start_submission_loop_pipelined(rx, DEFAULT_PIPELINE_WINDOW).await
+    }
+
+    /// Same as `start_submission_loop` with an explicit pipeline window (how
+    /// many transactions may be in flight at once). A transaction that
+    /// stalls past `STALL_TIMEOUT`, or is rejected as underpriced on
+    /// resubmission, is resubmitted under the *same* nonce with its priority
+    /// fee scaled by `FEE_BUMP_FACTOR` rather than skipped.
+    pub async fn start_submission_loop_pipelined(
         self,
         mut rx: mpsc::Receiver<FrameBatch>,
+        pipeline_window: usize,
     ) -> Result<()> {
-        info!("Starting rate-limited submission loop");
+        info!("Starting nonce-pipelined submission loop (window: {})", pipeline_window);
         info!("Receivers should monitor transactions FROM: {}", self.sender_address);
-        info!("Transactions will be submitted with 1.5s delay between each (max ~0.67 tx/sec)");
 
-        let mut submitted_count = 0u64;
-        let mut total_bytes = 0usize;
+        let mut next_nonce = self.provider
+            .get_transaction_count(self.sender_address)
+            .pending()
+            .await
+            .map_err(|e| anyhow!("Failed to fetch starting nonce: {}", e))?;
+        info!("Starting nonce: {}", next_nonce);
+
+        let provider = Arc::new(self.provider);
+        let target_address = self.target_address;
+        let submission_mode = self.submission_mode;
+        let semaphore = Arc::new(Semaphore::new(pipeline_window.max(1)));
+
+        let submitted_count = Arc::new(AtomicU64::new(0));
+        let total_bytes = Arc::new(AtomicUsize::new(0));
         let start_time = std::time::Instant::now();
-        let mut last_submit_time = std::time::Instant::now();
 
-        // Rate limiting: minimum time between transactions
-        // Increased to 1.5 seconds to give RPC node time to update nonces
-        let min_interval = std::time::Duration::from_millis(1500); // 1.5s between txs
+        let mut in_flight = tokio::task::JoinSet::new();
 
         while let Some(batch) = rx.recv().await {
-            // Wait if we're submitting too fast
-            let elapsed_since_last = last_submit_time.elapsed();
-            if elapsed_since_last < min_interval {
-                let wait_time = min_interval - elapsed_since_last;
-                debug!("Rate limiting: waiting {:?} before next submission", wait_time);
-                tokio::time::sleep(wait_time).await;
-            }
+            let permit = semaphore.clone().acquire_owned().await
+                .map_err(|e| anyhow!("Submission semaphore closed: {}", e))?;
 
-            match self.submit_batch_fast(&batch).await {
-                Ok(tx_hash) => {
-                    submitted_count += 1;
-                    total_bytes += batch.size_bytes();
-                    last_submit_time = std::time::Instant::now();
+            let nonce = next_nonce;
+            next_nonce += 1;
 
-                    if submitted_count % 10 == 0 {
-                        let elapsed = start_time.elapsed().as_secs_f32();
-                        let rate = submitted_count as f32 / elapsed;
-                        let avg_bytes = total_bytes / submitted_count as usize;
-                        info!(
-                            "Submitted {} batches in {:.1}s ({:.1} tx/sec), avg size: {}KB",
-                            submitted_count, elapsed, rate, avg_bytes / 1024
-                        );
-                    }
+            let provider = provider.clone();
+            let submitted_count = submitted_count.clone();
+            let total_bytes = total_bytes.clone();
 
-                    debug!("Batch {} submitted: {}", batch.sequence, tx_hash);
-                }
-                Err(e) => {
-                    error!("Failed to submit batch {}: {}", batch.sequence, e);
-
-                    // If it's a nonce error, wait longer for mempool to clear
-                    if e.to_string().contains("higher priority") || e.to_string().contains("nonce") {
-                        warn!("Nonce collision detected - waiting 3 seconds for mempool to clear...");
-                        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
-                    } else {
-                        warn!("Retrying after 2 second delay...");
-                        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-                    }
+            in_flight.spawn(async move {
+                let _permit = permit; // held until this submission settles
+                let batch_bytes = batch.size_bytes();
+                let sequence = batch.sequence;
 
-                    // Try one more time
-                    match self.submit_batch_fast(&batch).await {
-                        Ok(tx_hash) => {
-                            submitted_count += 1;
-                            total_bytes += batch.size_bytes();
-                            last_submit_time = std::time::Instant::now();
-                            info!("✓ Retry successful for batch {}: {}", batch.sequence, tx_hash);
-                        }
-                        Err(e2) => {
-                            error!("✗ Retry failed for batch {}: {}", batch.sequence, e2);
-                            warn!("Skipping batch {} and continuing...", batch.sequence);
+                match Self::submit_with_fee_escalation(
+                    &provider,
+                    target_address,
+                    submission_mode,
+                    &batch,
+                    nonce,
+                ).await {
+                    Ok(tx_hash) => {
+                        let submitted = submitted_count.fetch_add(1, Ordering::Relaxed) + 1;
+                        total_bytes.fetch_add(batch_bytes, Ordering::Relaxed);
+                        debug!("Batch {} (nonce {}) submitted: {}", sequence, nonce, tx_hash);
+
+                        if submitted % 10 == 0 {
+                            info!("Submitted {} batches so far (nonce {})", submitted, nonce);
                         }
                     }
+                    Err(e) => {
+                        error!("Batch {} (nonce {}) failed permanently: {}", sequence, nonce, e);
+                    }
                 }
-            }
+            });
         }
 
+        // Drain whatever is still in flight before reporting final totals
+        while in_flight.join_next().await.is_some() {}
+
         let elapsed = start_time.elapsed().as_secs_f32();
-        let rate = submitted_count as f32 / elapsed;
+        let submitted = submitted_count.load(Ordering::Relaxed);
+        let rate = submitted as f32 / elapsed;
 
         info!("Submission loop stopped gracefully");
         info!(
             "Total: {} batches in {:.1}s ({:.1} tx/sec), {}KB total data",
-            submitted_count, elapsed, rate, total_bytes / 1024
+            submitted, elapsed, rate, total_bytes.load(Ordering::Relaxed) / 1024
         );
 
         Ok(())
     }
+
+    /// Submit a batch under an explicit nonce, escalating the priority fee
+    /// and resubmitting under the same nonce if the transaction stalls past
+    /// `STALL_TIMEOUT` or the node rejects a resubmission as underpriced.
+    /// Returns once the transaction lands on-chain.
+    async fn submit_with_fee_escalation(
+        provider: &FilledProvider,
+        target_address: Address,
+        submission_mode: SubmissionMode,
+        batch: &FrameBatch,
+        nonce: u64,
+    ) -> Result<String> {
+        let mut encoded = bytes::BytesMut::new();
+        FrameBatchCodec::new().encode(batch.clone(), &mut encoded)?;
+        let encoded = encoded.freeze();
+
+        let mut priority_fee = BASE_PRIORITY_FEE_WEI;
+        let mut attempt = 0u32;
+
+        loop {
+            let max_fee = priority_fee.saturating_mul(2).saturating_add(priority_fee);
+
+            let tx = match submission_mode {
+                SubmissionMode::Calldata => TransactionRequest::default()
+                    .to(target_address)
+                    .with_input(Bytes::from(encoded.clone()))
+                    .nonce(nonce)
+                    .max_fee_per_gas(max_fee)
+                    .max_priority_fee_per_gas(priority_fee),
+                SubmissionMode::Blob => {
+                    let mut sidecar_builder = SidecarBuilder::<SimpleCoder>::new();
+                    sidecar_builder.ingest(&encoded);
+                    let sidecar = sidecar_builder
+                        .build()
+                        .map_err(|e| anyhow!("Failed to build blob sidecar: {}", e))?;
+
+                    TransactionRequest::default()
+                        .to(target_address)
+                        .with_blob_sidecar(sidecar)
+                        .nonce(nonce)
+                        .max_fee_per_gas(max_fee)
+                        .max_priority_fee_per_gas(priority_fee)
+                }
+            };
+
+            match provider.send_transaction(tx).await {
+                Ok(pending_tx) => {
+                    let tx_hash = format!("{:?}", pending_tx.tx_hash());
+
+                    match tokio::time::timeout(STALL_TIMEOUT, pending_tx.get_receipt()).await {
+                        Ok(Ok(_receipt)) => return Ok(tx_hash),
+                        Ok(Err(e)) => {
+                            return Err(anyhow!("Confirmation failed for nonce {}: {}", nonce, e))
+                        }
+                        Err(_) => {
+                            warn!(
+                                "Batch {} (nonce {}) stalled past {:?}, bumping priority fee and resubmitting",
+                                batch.sequence, nonce, STALL_TIMEOUT
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    let msg = e.to_string();
+
+                    // A transaction for this nonce already mined - either
+                    // this batch's own earlier, slower-to-confirm
+                    // submission or a prior stalled resubmission landed,
+                    // so the batch is already on-chain. Treat this as
+                    // success rather than looping it through the fee bump,
+                    // which would just hit "nonce too low" again on every
+                    // attempt until retries are exhausted.
+                    if msg.contains("nonce too low") {
+                        info!(
+                            "Batch {} (nonce {}) already mined under a prior submission",
+                            batch.sequence, nonce
+                        );
+                        return Ok(format!("nonce {} already mined", nonce));
+                    }
+
+                    let underpriced =
+                        msg.contains("replacement underpriced") || msg.contains("higher priority");
+
+                    if !underpriced {
+                        return Err(anyhow!("Transaction send failed for nonce {}: {}", nonce, e));
+                    }
+
+                    warn!(
+                        "Nonce {} rejected as underpriced ({}), bumping fee and resubmitting",
+                        nonce, msg
+                    );
+                }
+            }
+
+            attempt += 1;
+            if attempt > MAX_FEE_BUMP_ATTEMPTS {
+                return Err(anyhow!(
+                    "Batch {} (nonce {}) exhausted fee-bump retries",
+                    batch.sequence,
+                    nonce
+                ));
+            }
+
+            priority_fee = ((priority_fee.max(1) as f64) * FEE_BUMP_FACTOR) as u128;
+            priority_fee = priority_fee.min(MAX_PRIORITY_FEE_WEI);
+        }
+    }
 }
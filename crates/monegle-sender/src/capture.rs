@@ -1,11 +1,29 @@
 use anyhow::{anyhow, Result};
 use image::DynamicImage;
+use monegle_core::CameraControlsConfig;
 use nokhwa::pixel_format::RgbFormat;
-use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType, Resolution};
+use nokhwa::utils::{
+    CameraControl, CameraIndex, ControlValueSetter, KnownCameraControl, RequestedFormat,
+    RequestedFormatType, Resolution,
+};
 use nokhwa::Camera;
 use std::time::Duration;
-use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
+use xcap::{Monitor, Window};
+
+use crate::frame_slot::FrameSlotSender;
+
+/// A source of raw video frames. Implemented by both the physical-camera
+/// backend (`VideoCapture`) and the desktop/window backend
+/// (`ScreenCapture`) so the rest of the pipeline doesn't care which one is
+/// feeding it - `main` picks one based on `--source` and hands the rest of
+/// the pipeline a `Box<dyn CaptureSource>`.
+pub trait CaptureSource: Send {
+    /// Start capturing frames and push them into a latest-frame-wins slot.
+    /// Runs in a blocking context since capture backends are not
+    /// `Send`-friendly across an await point.
+    fn start_capture_loop_blocking(self: Box<Self>, tx: FrameSlotSender) -> Result<()>;
+}
 
 /// Video capture component
 pub struct VideoCapture {
@@ -14,9 +32,27 @@ pub struct VideoCapture {
 }
 
 impl VideoCapture {
-    /// Initialize a new video capture
+    /// Initialize a new video capture with the camera's default (usually
+    /// auto) controls
     /// Note: width/height are target ASCII dimensions, camera opens at native resolution
-    pub fn new(device_index: u32, fps: u32, _width: u32, _height: u32) -> Result<Self> {
+    pub fn new(device_index: u32, fps: u32, width: u32, height: u32) -> Result<Self> {
+        Self::new_with_controls(device_index, fps, width, height, &CameraControlsConfig::default())
+    }
+
+    /// Initialize a new video capture, pinning any controls set in
+    /// `controls` right after `open_stream()` (they are not applied before
+    /// the stream starts, so its first frames use the camera's own
+    /// settings). This is the only way to get a
+    /// usable dynamic range out of `rgb_to_brightness` in dim rooms, since
+    /// auto-exposure otherwise crushes the frame before it ever reaches
+    /// the ASCII mapping.
+    pub fn new_with_controls(
+        device_index: u32,
+        fps: u32,
+        _width: u32,
+        _height: u32,
+        controls: &CameraControlsConfig,
+    ) -> Result<Self> {
         info!(
             "Initializing camera {} at {} FPS (camera opens at native resolution)",
             device_index, fps
@@ -116,10 +152,55 @@ impl VideoCapture {
         info!("✓ Camera initialized successfully!");
         info!("Camera: {}", info.human_name());
 
-        Ok(Self {
-            camera,
-            fps,
-        })
+        let mut capture = Self { camera, fps };
+        capture.apply_controls(controls)?;
+
+        Ok(capture)
+    }
+
+    /// List the controls (exposure, gain, white balance, brightness, ...)
+    /// this camera exposes, along with their current value and valid range
+    pub fn list_controls(&self) -> Result<Vec<CameraControl>> {
+        self.camera
+            .camera_controls()
+            .map_err(|e| anyhow!("Failed to list camera controls: {}", e))
+    }
+
+    /// Set a single camera control, e.g. `KnownCameraControl::Exposure`
+    /// with `ControlValueSetter::Integer(value)`, or
+    /// `ControlValueSetter::Boolean(true)` to request auto mode on a
+    /// control that supports it
+    pub fn set_control(&mut self, control: KnownCameraControl, value: ControlValueSetter) -> Result<()> {
+        self.camera
+            .set_camera_control(control, value)
+            .map_err(|e| anyhow!("Failed to set camera control {:?}: {}", control, e))
+    }
+
+    /// Apply every control pinned in `controls`, leaving any `None` field
+    /// on the camera's own default. Auto-mode toggles are applied before
+    /// their paired manual value so a manual exposure/white-balance isn't
+    /// immediately overridden by the camera re-enabling auto mode.
+    fn apply_controls(&mut self, controls: &CameraControlsConfig) -> Result<()> {
+        if let Some(auto) = controls.auto_exposure {
+            self.set_control(KnownCameraControl::Exposure, ControlValueSetter::Boolean(auto))?;
+        }
+        if let Some(exposure) = controls.exposure {
+            self.set_control(KnownCameraControl::Exposure, ControlValueSetter::Integer(exposure))?;
+        }
+        if let Some(auto) = controls.auto_white_balance {
+            self.set_control(KnownCameraControl::WhiteBalance, ControlValueSetter::Boolean(auto))?;
+        }
+        if let Some(white_balance) = controls.white_balance {
+            self.set_control(KnownCameraControl::WhiteBalance, ControlValueSetter::Integer(white_balance))?;
+        }
+        if let Some(gain) = controls.gain {
+            self.set_control(KnownCameraControl::Gain, ControlValueSetter::Integer(gain))?;
+        }
+        if let Some(brightness) = controls.brightness {
+            self.set_control(KnownCameraControl::Brightness, ControlValueSetter::Integer(brightness))?;
+        }
+
+        Ok(())
     }
 
     /// Capture a single frame
@@ -139,11 +220,11 @@ impl VideoCapture {
         Ok(dynamic)
     }
 
-    /// Start capturing frames and send them through a channel
+    /// Start capturing frames and push them into a latest-frame-wins slot
     /// This runs in a blocking context since Camera is not Send
     pub fn start_capture_loop_blocking(
         mut self,
-        tx: mpsc::Sender<DynamicImage>,
+        tx: FrameSlotSender,
     ) -> Result<()> {
         info!("Starting capture loop at {} FPS", self.fps);
 
@@ -167,11 +248,181 @@ impl VideoCapture {
                     error_count = 0;
 
                     if frame_count % (self.fps as u64 * 10) == 0 {
-                        info!("Captured {} frames", frame_count);
+                        info!(
+                            "Captured {} frames ({} dropped)",
+                            frame_count,
+                            tx.dropped_frames()
+                        );
+                    }
+
+                    if !tx.push(image) {
+                        warn!("Capture slot closed, stopping capture loop");
+                        break;
+                    }
+                }
+                Err(e) => {
+                    error_count += 1;
+                    warn!("Frame capture error ({}): {}", error_count, e);
+
+                    if error_count > 10 {
+                        return Err(anyhow!("Too many consecutive capture errors"));
+                    }
+
+                    // Brief pause before retrying
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+            }
+        }
+
+        info!(
+            "Capture loop stopped after {} frames ({} dropped)",
+            frame_count,
+            tx.dropped_frames()
+        );
+        Ok(())
+    }
+}
+
+impl CaptureSource for VideoCapture {
+    fn start_capture_loop_blocking(self: Box<Self>, tx: FrameSlotSender) -> Result<()> {
+        (*self).start_capture_loop_blocking(tx)
+    }
+}
+
+/// Which screen region a `ScreenCapture` grabs each frame
+#[derive(Debug, Clone)]
+pub enum ScreenTarget {
+    /// The Nth display, by `xcap::Monitor::all()` index
+    Display(usize),
+    /// The first window whose title contains this substring
+    /// (case-insensitive)
+    Window(String),
+}
+
+/// A pixel rectangle to crop out of the captured image before it's handed
+/// to the converter, e.g. to stream just a terminal pane inside a larger
+/// display
+#[derive(Debug, Clone, Copy)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Desktop/window capture component, a `CaptureSource` sibling to
+/// `VideoCapture` for streaming the screen instead of a webcam
+pub struct ScreenCapture {
+    target: ScreenTarget,
+    crop: Option<CropRect>,
+    fps: u32,
+}
+
+impl ScreenCapture {
+    /// Initialize a new screen capture, failing fast if `target` doesn't
+    /// resolve to an existing display/window (mirrors `VideoCapture::new`
+    /// failing fast if the camera can't be opened)
+    pub fn new(target: ScreenTarget, crop: Option<CropRect>, fps: u32) -> Result<Self> {
+        match &target {
+            ScreenTarget::Display(index) => {
+                let monitors = Monitor::all().map_err(|e| anyhow!("Failed to enumerate displays: {}", e))?;
+                let monitor = monitors.get(*index).ok_or_else(|| {
+                    anyhow!("Display index {} out of range ({} displays found)", index, monitors.len())
+                })?;
+                info!("✓ Display {} selected: {}", index, monitor.name());
+            }
+            ScreenTarget::Window(name_substr) => {
+                let windows = Window::all().map_err(|e| anyhow!("Failed to enumerate windows: {}", e))?;
+                let matched = windows
+                    .iter()
+                    .find(|w| w.title().to_lowercase().contains(&name_substr.to_lowercase()))
+                    .ok_or_else(|| anyhow!("No window titled like '{}' found", name_substr))?;
+                info!("✓ Window selected: {}", matched.title());
+            }
+        }
+
+        Ok(Self { target, crop, fps })
+    }
+
+    /// Capture a single frame. The target is re-resolved from
+    /// `Monitor::all()`/`Window::all()` on every call rather than cached,
+    /// since a moved/closed window or a reconfigured display isn't
+    /// guaranteed to keep the same handle between frames
+    fn capture_frame(&self) -> Result<DynamicImage> {
+        let captured = match &self.target {
+            ScreenTarget::Display(index) => {
+                let monitors = Monitor::all().map_err(|e| anyhow!("Failed to enumerate displays: {}", e))?;
+                let monitor = monitors
+                    .get(*index)
+                    .ok_or_else(|| anyhow!("Display {} no longer available", index))?;
+                monitor
+                    .capture_image()
+                    .map_err(|e| anyhow!("Failed to capture display {}: {}", index, e))?
+            }
+            ScreenTarget::Window(name_substr) => {
+                let windows = Window::all().map_err(|e| anyhow!("Failed to enumerate windows: {}", e))?;
+                let window = windows
+                    .iter()
+                    .find(|w| w.title().to_lowercase().contains(&name_substr.to_lowercase()))
+                    .ok_or_else(|| anyhow!("Window matching '{}' no longer available", name_substr))?;
+                window
+                    .capture_image()
+                    .map_err(|e| anyhow!("Failed to capture window '{}': {}", name_substr, e))?
+            }
+        };
+
+        let dynamic = DynamicImage::ImageRgba8(captured);
+
+        let cropped = match self.crop {
+            // Clamp to the actual captured bounds so a crop rect from
+            // before a resolution change doesn't go out of bounds
+            Some(rect) => {
+                let x = rect.x.min(dynamic.width().saturating_sub(1));
+                let y = rect.y.min(dynamic.height().saturating_sub(1));
+                let width = rect.width.min(dynamic.width() - x);
+                let height = rect.height.min(dynamic.height() - y);
+                image::DynamicImage::crop_imm(&dynamic, x, y, width, height)
+            }
+            None => dynamic,
+        };
+
+        debug!("Captured frame: {}x{}", cropped.width(), cropped.height());
+
+        Ok(cropped)
+    }
+
+    /// Start capturing frames and push them into a latest-frame-wins slot
+    pub fn start_capture_loop_blocking(self, tx: FrameSlotSender) -> Result<()> {
+        info!("Starting screen capture loop at {} FPS", self.fps);
+
+        let frame_interval = Duration::from_secs_f32(1.0 / self.fps as f32);
+        let mut next_frame_time = std::time::Instant::now();
+
+        let mut frame_count = 0u64;
+        let mut error_count = 0u32;
+
+        loop {
+            let now = std::time::Instant::now();
+            if now < next_frame_time {
+                std::thread::sleep(next_frame_time - now);
+            }
+            next_frame_time += frame_interval;
+
+            match self.capture_frame() {
+                Ok(image) => {
+                    frame_count += 1;
+                    error_count = 0;
+
+                    if frame_count % (self.fps as u64 * 10) == 0 {
+                        info!(
+                            "Captured {} frames ({} dropped)",
+                            frame_count,
+                            tx.dropped_frames()
+                        );
                     }
 
-                    if tx.blocking_send(image).is_err() {
-                        warn!("Capture channel closed, stopping capture loop");
+                    if !tx.push(image) {
+                        warn!("Capture slot closed, stopping capture loop");
                         break;
                     }
                 }
@@ -189,7 +440,200 @@ impl VideoCapture {
             }
         }
 
-        info!("Capture loop stopped after {} frames", frame_count);
+        info!(
+            "Capture loop stopped after {} frames ({} dropped)",
+            frame_count,
+            tx.dropped_frames()
+        );
         Ok(())
     }
 }
+
+impl CaptureSource for ScreenCapture {
+    fn start_capture_loop_blocking(self: Box<Self>, tx: FrameSlotSender) -> Result<()> {
+        (*self).start_capture_loop_blocking(tx)
+    }
+}
+
+/// RTSP transport, for cameras/encoders that only speak one of the two
+#[derive(Debug, Clone, Copy, clap::ValueEnum, PartialEq, Eq)]
+pub enum RtspTransport {
+    Tcp,
+    Udp,
+}
+
+impl RtspTransport {
+    fn as_retina(self) -> retina::client::Transport {
+        match self {
+            RtspTransport::Tcp => retina::client::Transport::Tcp(Default::default()),
+            RtspTransport::Udp => retina::client::Transport::Udp(Default::default()),
+        }
+    }
+}
+
+/// Resolve a capture source string the way e.g. OpenCV's `VideoCapture`
+/// does: a plain integer is a local device index, an `rtsp://` URL is a
+/// network camera/encoder. This is the one place that decides whether
+/// `--camera` ends up opening `VideoCapture` or `RtspCapture`.
+pub fn open_capture_source(
+    source: &str,
+    transport: RtspTransport,
+    fps: u32,
+    camera_controls: &CameraControlsConfig,
+) -> Result<Box<dyn CaptureSource>> {
+    if source.starts_with("rtsp://") {
+        return Ok(Box::new(RtspCapture::new(source, transport, fps)?));
+    }
+
+    let device_index: u32 = source.parse().map_err(|_| {
+        anyhow!("Capture source '{}' is neither a camera index nor an rtsp:// URL", source)
+    })?;
+
+    VideoCapture::new_with_controls(device_index, fps, 640, 480, camera_controls)
+        .map(|c| Box::new(c) as Box<dyn CaptureSource>)
+}
+
+/// Network camera/encoder capture over RTSP, a `CaptureSource` sibling to
+/// `VideoCapture` for relaying existing surveillance/encoder feeds instead
+/// of a local device. Pulls video access units with `retina` and decodes
+/// them to `DynamicImage` with `openh264`, which handles H.264 only (an
+/// H.265 stream is not decoded and its session fails); reconnects with
+/// exponential backoff on stream drop since network cameras routinely
+/// bounce.
+pub struct RtspCapture {
+    url: String,
+    transport: RtspTransport,
+    fps: u32,
+}
+
+impl RtspCapture {
+    pub fn new(url: &str, transport: RtspTransport, fps: u32) -> Result<Self> {
+        info!("Initializing RTSP capture from {} ({:?})", url, transport);
+
+        Ok(Self {
+            url: url.to_string(),
+            transport,
+            fps,
+        })
+    }
+
+    /// Connect, decode, and forward frames until the session ends, then
+    /// reconnect with backoff. Returns only if `tx` is closed.
+    pub fn start_capture_loop_blocking(self, tx: FrameSlotSender) -> Result<()> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| anyhow!("Failed to start RTSP runtime: {}", e))?;
+
+        let mut backoff = Duration::from_secs(1);
+        const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+        loop {
+            info!("Connecting to RTSP source {}", self.url);
+
+            match runtime.block_on(self.run_session(&tx)) {
+                Ok(()) => {
+                    info!("RTSP session ended (capture slot closed), stopping capture loop");
+                    return Ok(());
+                }
+                Err(e) => {
+                    warn!(
+                        "RTSP session for {} dropped ({}), reconnecting in {:.0}s",
+                        self.url, e, backoff.as_secs_f32()
+                    );
+                    std::thread::sleep(backoff);
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Run a single RTSP session: describe, set up the video track, decode
+    /// each access unit, and forward it. Returns `Ok(())` only when `tx` is
+    /// closed (a clean shutdown); any stream-side error bubbles up so the
+    /// caller can reconnect.
+    async fn run_session(&self, tx: &FrameSlotSender) -> Result<()> {
+        let url = retina::client::Url::parse(&self.url)
+            .map_err(|e| anyhow!("Invalid RTSP URL {}: {}", self.url, e))?;
+
+        let mut session = retina::client::Session::describe(
+            url,
+            retina::client::SessionOptions::default().transport(self.transport.as_retina()),
+        )
+        .await
+        .map_err(|e| anyhow!("RTSP DESCRIBE failed: {}", e))?;
+
+        let video_stream_index = session
+            .streams()
+            .iter()
+            .position(|s| s.media() == "video")
+            .ok_or_else(|| anyhow!("No video track advertised by {}", self.url))?;
+
+        session
+            .setup(video_stream_index, retina::client::SetupOptions::default())
+            .await
+            .map_err(|e| anyhow!("RTSP SETUP failed: {}", e))?;
+
+        let mut decoder = openh264::decoder::Decoder::new()
+            .map_err(|e| anyhow!("Failed to initialize H.264 decoder: {}", e))?;
+
+        let mut demuxed = session
+            .play(retina::client::PlayOptions::default())
+            .await
+            .map_err(|e| anyhow!("RTSP PLAY failed: {}", e))?
+            .demuxed()
+            .map_err(|e| anyhow!("Failed to demux RTSP session: {}", e))?;
+
+        let frame_interval = Duration::from_secs_f32(1.0 / self.fps as f32);
+        let mut next_frame_time = tokio::time::Instant::now();
+
+        while let Some(item) = futures::StreamExt::next(&mut demuxed).await {
+            let item = item.map_err(|e| anyhow!("RTSP stream error: {}", e))?;
+
+            let retina::codec::CodecItem::VideoFrame(frame) = item else {
+                continue;
+            };
+
+            // Throttle to the configured FPS rather than forwarding every
+            // access unit (an encoder's native rate may run much higher)
+            if tokio::time::Instant::now() < next_frame_time {
+                continue;
+            }
+            next_frame_time += frame_interval;
+
+            let Some(decoded) = decoder
+                .decode(frame.data())
+                .map_err(|e| anyhow!("H.264 decode error: {}", e))?
+            else {
+                continue;
+            };
+
+            let image = decoded_frame_to_image(&decoded)?;
+
+            if !tx.push(image) {
+                return Ok(());
+            }
+        }
+
+        Err(anyhow!("RTSP stream ended unexpectedly"))
+    }
+}
+
+impl CaptureSource for RtspCapture {
+    fn start_capture_loop_blocking(self: Box<Self>, tx: FrameSlotSender) -> Result<()> {
+        (*self).start_capture_loop_blocking(tx)
+    }
+}
+
+/// Convert a decoded I420 (YUV 4:2:0) frame from `openh264` into an RGB
+/// `DynamicImage` for the rest of the pipeline
+fn decoded_frame_to_image(decoded: &openh264::decoder::DecodedYUV) -> Result<DynamicImage> {
+    let (width, height) = decoded.dimensions();
+    let mut rgb = vec![0u8; width * height * 3];
+    decoded.write_rgb8(&mut rgb);
+
+    let buffer = image::RgbImage::from_raw(width as u32, height as u32, rgb)
+        .ok_or_else(|| anyhow!("Decoded frame dimensions did not match buffer size"))?;
+
+    Ok(DynamicImage::ImageRgb8(buffer))
+}
@@ -1,22 +1,138 @@
 use anyhow::Result;
 use image::{DynamicImage, GenericImageView, imageops};
-use monegle_core::{CharacterSet, ColorMode, brightness_to_ascii, brightness_to_ascii_colored, aspect_ratio_correction};
+use monegle_core::{
+    ascii_from_index, aspect_ratio_correction, brightness_to_ascii, dither_brightness_indices,
+    median_cut_quantize, CharacterSet, ColorMode,
+};
+use std::sync::OnceLock;
 use tokio::sync::mpsc;
 use tracing::{debug, info};
 
+use crate::frame_slot::FrameSlotReceiver;
+
+/// Format a median-cut bucket's averaged RGB as a compact ANSI escape:
+/// 256-color maps it to the nearest fixed ANSI-256 palette entry, 16-color
+/// to the nearest of the 8 base foreground colors plus the bold attribute
+/// to reach the bright half of the ramp.
+fn ansi_palette_escape(mode: ColorMode, rgb: (u8, u8, u8)) -> String {
+    match mode {
+        ColorMode::Ansi256 => format!("\x1b[38;5;{}m", nearest_ansi256_code(rgb)),
+        ColorMode::Ansi16 => {
+            let code = nearest_ansi16_code(rgb);
+            let n = code % 8;
+            if code >= 8 {
+                format!("\x1b[3{};1m", n)
+            } else {
+                format!("\x1b[3{}m", n)
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+/// The xterm-standard ANSI-256 palette: 16 base colors, the 6x6x6 color
+/// cube (steps 0, 95, 135, 175, 215, 255 per channel), then a 24-step
+/// grayscale ramp. Built once and cached - `nearest_ansi256_code` is
+/// called once per cell every frame, so rebuilding this 256-entry table
+/// on every call would redo the cube and ramp construction per character.
+fn ansi256_palette() -> &'static [(u8, u8, u8); 256] {
+    static PALETTE: OnceLock<[(u8, u8, u8); 256]> = OnceLock::new();
+    PALETTE.get_or_init(|| {
+        const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        let mut palette = [(0u8, 0u8, 0u8); 256];
+        palette[..16].copy_from_slice(&ansi16_palette());
+
+        let mut i = 16;
+        for r in CUBE_STEPS {
+            for g in CUBE_STEPS {
+                for b in CUBE_STEPS {
+                    palette[i] = (r, g, b);
+                    i += 1;
+                }
+            }
+        }
+
+        for j in 0u8..24 {
+            let v = 8 + j * 10;
+            palette[i] = (v, v, v);
+            i += 1;
+        }
+
+        palette
+    })
+}
+
+/// The 16 basic ANSI colors (xterm defaults): normal, then bright
+const fn ansi16_palette() -> [(u8, u8, u8); 16] {
+    [
+        (0, 0, 0),
+        (128, 0, 0),
+        (0, 128, 0),
+        (128, 128, 0),
+        (0, 0, 128),
+        (128, 0, 128),
+        (0, 128, 128),
+        (192, 192, 192),
+        (128, 128, 128),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (0, 0, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ]
+}
+
+/// Nearest palette entry to `rgb` by squared Euclidean distance
+fn nearest_palette_index(palette: &[(u8, u8, u8)], rgb: (u8, u8, u8)) -> u8 {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &(r, g, b))| {
+            let dr = r as i32 - rgb.0 as i32;
+            let dg = g as i32 - rgb.1 as i32;
+            let db = b as i32 - rgb.2 as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(i, _)| i as u8)
+        .unwrap_or(0)
+}
+
+fn nearest_ansi256_code(rgb: (u8, u8, u8)) -> u8 {
+    nearest_palette_index(ansi256_palette().as_slice(), rgb)
+}
+
+fn nearest_ansi16_code(rgb: (u8, u8, u8)) -> u8 {
+    nearest_palette_index(&ansi16_palette(), rgb)
+}
+
 /// ASCII conversion component
 pub struct AsciiConverter {
     target_width: u32,
     target_height: u32,
     charset: CharacterSet,
     color_mode: ColorMode,
+
+    /// Floyd-Steinberg error-diffusion dithering over brightness before
+    /// glyph selection (see `dither_brightness_indices`), trading
+    /// per-pixel independence for smoother gradients at small character
+    /// grids. Only affects which glyph is picked - `ColorMode`s that carry
+    /// true color still colorize with the pixel's own RGB regardless.
+    dither: bool,
 }
 
 impl AsciiConverter {
-    pub fn new(target_width: u32, target_height: u32, charset: CharacterSet, color_mode: ColorMode) -> Self {
+    pub fn new(
+        target_width: u32,
+        target_height: u32,
+        charset: CharacterSet,
+        color_mode: ColorMode,
+        dither: bool,
+    ) -> Self {
         info!(
-            "Initializing ASCII converter: {}x{} chars, charset: {:?}, color: {:?}",
-            target_width, target_height, charset, color_mode
+            "Initializing ASCII converter: {}x{} chars, charset: {:?}, color: {:?}, dither: {}",
+            target_width, target_height, charset, color_mode, dither
         );
 
         Self {
@@ -24,6 +140,7 @@ impl AsciiConverter {
             target_height,
             charset,
             color_mode,
+            dither,
         }
     }
 
@@ -39,8 +156,13 @@ impl AsciiConverter {
             imageops::FilterType::Lanczos3,
         );
 
-        // For RGB mode, keep colors; otherwise convert to grayscale
-        let (source_image, use_rgb) = if self.color_mode == ColorMode::Rgb {
+        // RGB and the quantized ANSI modes all need the real pixel colors;
+        // only the gradient/monochrome modes work off grayscale
+        let needs_color = matches!(
+            self.color_mode,
+            ColorMode::Rgb | ColorMode::Ansi256 | ColorMode::Ansi16
+        );
+        let (source_image, use_rgb) = if needs_color {
             (DynamicImage::ImageRgba8(resized), true)
         } else {
             (DynamicImage::ImageRgba8(resized).grayscale(), false)
@@ -53,31 +175,70 @@ impl AsciiConverter {
         };
         let mut result = String::with_capacity(capacity);
 
+        // Gather per-cell color (for true-color modes) and brightness (for
+        // glyph selection) up front so dithering can diffuse error across
+        // the whole grid before any glyph is picked
+        let cell_count = (self.target_width * self.target_height) as usize;
+        let mut colors: Vec<(u8, u8, u8)> = Vec::with_capacity(cell_count);
+        let mut brightness_buffer: Vec<u8> = Vec::with_capacity(cell_count);
+
         for y in 0..self.target_height {
-            // Map back to the resized image coordinates
             let img_y = (y as f32 * corrected_height as f32 / self.target_height as f32) as u32;
 
             for x in 0..self.target_width {
                 let pixel = source_image.get_pixel(x, img_y);
+                let r = pixel[0];
+                let g = pixel[1];
+                let b = pixel[2];
+                let brightness = if use_rgb {
+                    ((0.299 * r as f32) + (0.587 * g as f32) + (0.114 * b as f32)) as u8
+                } else {
+                    pixel[0] // Grayscale, so R=G=B
+                };
 
-                if use_rgb {
-                    // Use actual RGB colors from the pixel
-                    let r = pixel[0];
-                    let g = pixel[1];
-                    let b = pixel[2];
-                    let brightness = ((0.299 * r as f32) + (0.587 * g as f32) + (0.114 * b as f32)) as u8;
-                    let ch = brightness_to_ascii(brightness, self.charset);
+                colors.push((r, g, b));
+                brightness_buffer.push(brightness);
+            }
+        }
+
+        let palette_len = self.charset.palette().chars().count();
+        let dithered_indices = self.dither.then(|| {
+            dither_brightness_indices(&brightness_buffer, self.target_width, self.target_height, palette_len)
+        });
+
+        // Ansi256/Ansi16 trade true color for a small frame-local palette,
+        // the same median-cut reduction EfficientRgbFrame uses for storage
+        let quantized_palette = match self.color_mode {
+            ColorMode::Ansi256 => Some(median_cut_quantize(&colors, 256)),
+            ColorMode::Ansi16 => Some(median_cut_quantize(&colors, 16)),
+            _ => None,
+        };
+
+        for y in 0..self.target_height {
+            for x in 0..self.target_width {
+                let idx = (y * self.target_width + x) as usize;
+                let (r, g, b) = colors[idx];
+                let brightness = brightness_buffer[idx];
+
+                let ch = match &dithered_indices {
+                    Some(indices) => ascii_from_index(indices[idx], self.charset),
+                    None => brightness_to_ascii(brightness, self.charset),
+                };
+
+                if let Some((palette, pixel_indices)) = &quantized_palette {
+                    let bucket_rgb = palette[pixel_indices[idx] as usize];
+                    result.push_str(&ansi_palette_escape(self.color_mode, bucket_rgb));
+                    result.push(ch);
+                    result.push_str("\x1b[0m");
+                } else if use_rgb {
+                    // Use actual RGB colors from the pixel; dithering only
+                    // ever changes which glyph was picked, not the color
                     result.push_str(&format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, ch));
                 } else if self.color_mode == ColorMode::None {
-                    // Monochrome
-                    let brightness = pixel[0]; // Grayscale, so R=G=B
-                    let ch = brightness_to_ascii(brightness, self.charset);
                     result.push(ch);
                 } else {
                     // Gradient color modes (Purple, Blue, Green)
-                    let brightness = pixel[0];
-                    let colored = brightness_to_ascii_colored(brightness, self.charset, self.color_mode);
-                    result.push_str(&colored);
+                    result.push_str(&self.color_mode.colorize(ch, brightness));
                 }
             }
 
@@ -94,7 +255,7 @@ impl AsciiConverter {
     /// Start conversion loop
     pub async fn start_conversion_loop(
         self,
-        mut rx: mpsc::Receiver<DynamicImage>,
+        mut rx: FrameSlotReceiver,
         tx: mpsc::Sender<String>,
     ) -> Result<()> {
         info!("Starting conversion loop");
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use monegle_core::dashboard::{Dashboard, DashboardCounters, DashboardTick};
 use monegle_core::{ColorMode, FrameBatch, StreamMetadata};
 use rand::Rng;
 use tokio::sync::mpsc;
@@ -75,16 +76,29 @@ pub fn generate_counter_frame(width: u32, height: u32, counter: u64, color_mode:
     result
 }
 
-/// Run counter mode with dry-run (terminal display only)
+/// Run counter mode with dry-run (terminal display only). When `dashboard`
+/// is set, frames are generated silently and only the TUI telemetry
+/// (FPS/counters) is rendered, replacing the manual `\x1B[2J\x1B[H`
+/// clearing; a 'q'/Esc keypress on the dashboard stops the loop. When
+/// `max_frames` is set, the loop stops on its own once that many frames
+/// have been generated instead of running until interrupted.
 pub async fn run_counter_dry_run_mode(
     width: u32,
     height: u32,
     fps: u32,
     color_mode: ColorMode,
+    dashboard: bool,
+    max_frames: Option<u64>,
 ) -> Result<()> {
     info!("Starting counter test mode (dry-run)");
     info!("Resolution: {}x{}, FPS: {}, Color: {:?}", width, height, fps, color_mode);
 
+    let mut dashboard = if dashboard {
+        Some(Dashboard::enter("Counter test mode (dry-run)")?)
+    } else {
+        None
+    };
+
     let frame_interval = tokio::time::Duration::from_secs_f32(1.0 / fps as f32);
     let mut interval = tokio::time::interval(frame_interval);
 
@@ -92,6 +106,13 @@ pub async fn run_counter_dry_run_mode(
     let mut frame_count = 0u64;
 
     loop {
+        if let Some(dash) = dashboard.as_ref() {
+            if dash.should_quit()? {
+                info!("Dashboard quit requested, stopping counter test mode");
+                break;
+            }
+        }
+
         interval.tick().await;
 
         // Counter increments every second
@@ -100,21 +121,49 @@ pub async fn run_counter_dry_run_mode(
         // Generate test frame
         let frame = generate_counter_frame(width, height, counter, color_mode);
 
-        // Display in terminal
-        print!("\x1B[2J\x1B[H"); // Clear screen
-        println!("╔════════════════════════════════════════════════════════╗");
-        println!("║  Monegle Counter Test Mode - Press Ctrl+C to stop    ║");
-        println!("╠════════════════════════════════════════════════════════╣");
-        println!("║  Frame: {}  Counter: {}  Time: {:.1}s              ║",
-            frame_count, counter, start_time.elapsed().as_secs_f32());
-        println!("╚════════════════════════════════════════════════════════╝");
-        println!("{}", frame);
+        if let Some(dash) = dashboard.as_mut() {
+            let elapsed = start_time.elapsed().as_secs_f32();
+            let current_fps = if elapsed > 0.0 { frame_count as f32 / elapsed } else { 0.0 };
+
+            dash.render(&DashboardTick {
+                depth: 0,
+                current_fps,
+                target_fps: fps as f32,
+                adaptive_fps: fps as f32,
+                sequence_range: None,
+                counters: DashboardCounters::default(),
+            })?;
+        } else {
+            // Display in terminal
+            print!("\x1B[2J\x1B[H"); // Clear screen
+            println!("╔════════════════════════════════════════════════════════╗");
+            println!("║  Monegle Counter Test Mode - Press Ctrl+C to stop    ║");
+            println!("╠════════════════════════════════════════════════════════╣");
+            println!("║  Frame: {}  Counter: {}  Time: {:.1}s              ║",
+                frame_count, counter, start_time.elapsed().as_secs_f32());
+            println!("╚════════════════════════════════════════════════════════╝");
+            println!("{}", frame);
+        }
 
         frame_count += 1;
+
+        if max_frames.is_some_and(|max| frame_count >= max) {
+            info!("Reached max_frames ({}), stopping counter test mode", frame_count);
+            break;
+        }
+    }
+
+    if let Some(dash) = dashboard {
+        dash.leave()?;
     }
+
+    Ok(())
 }
 
-/// Run counter mode with blockchain submission
+/// Run counter mode with blockchain submission. When `max_frames` is set,
+/// frame generation stops on its own once that many frames have been sent,
+/// dropping `convert_tx` so the batcher flushes its final partial batch and
+/// the blockchain sender drains cleanly instead of being aborted mid-flush.
 pub async fn run_counter_blockchain_mode(
     width: u32,
     height: u32,
@@ -125,6 +174,7 @@ pub async fn run_counter_blockchain_mode(
     max_batch_size: usize,
     keyframe_interval: u64,
     blockchain_sender: BlockchainSender,
+    max_frames: Option<u64>,
 ) -> Result<()> {
     info!("Starting counter test mode (blockchain)");
     info!("Resolution: {}x{}, FPS: {}, Color: {:?}", width, height, fps, color_mode);
@@ -183,6 +233,14 @@ pub async fn run_counter_blockchain_mode(
                 frame_count, counter, frame_count, generation_fps, elapsed);
             last_log_time = std::time::Instant::now();
         }
+
+        if max_frames.is_some_and(|max| frame_count >= max) {
+            info!("Reached max_frames ({}), ending stream", frame_count);
+            drop(convert_tx);
+            let _ = batcher_handle.await;
+            let _ = blockchain_handle.await;
+            return Ok(());
+        }
     }
 
     batcher_handle.abort();
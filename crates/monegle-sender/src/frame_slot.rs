@@ -0,0 +1,130 @@
+use image::DynamicImage;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tracing::warn;
+
+/// Shared state behind a [`FrameSlotSender`]/[`FrameSlotReceiver`] pair
+struct FrameSlotState {
+    queue: VecDeque<DynamicImage>,
+    sender_alive: bool,
+    receiver_alive: bool,
+}
+
+struct FrameSlotInner {
+    depth: usize,
+    state: Mutex<FrameSlotState>,
+    notify: Notify,
+    dropped: AtomicU64,
+}
+
+/// Sending half of a latest-frame-wins handoff between a capture backend
+/// (running on a blocking thread) and the async ASCII conversion stage.
+/// Unlike a plain bounded `mpsc::channel`, `push` never blocks: once the
+/// queue reaches `depth` it evicts the oldest buffered frame instead, so a
+/// stalled downstream pipeline (e.g. blockchain submission backpressure)
+/// can never make capture latency grow unboundedly - the converter just
+/// ends up working on a staler frame, and the eviction is counted as a
+/// dropped frame.
+pub struct FrameSlotSender {
+    inner: Arc<FrameSlotInner>,
+}
+
+/// Receiving half of a [`channel`] pair. `recv` mirrors
+/// `mpsc::Receiver::recv` - `None` once the sender is gone and the queue
+/// has drained.
+pub struct FrameSlotReceiver {
+    inner: Arc<FrameSlotInner>,
+}
+
+/// Create a `FrameSlotSender`/`FrameSlotReceiver` pair with room for
+/// `depth` buffered frames before the oldest is evicted. `depth: 1` gives
+/// the lowest possible latency (the converter always sees the newest
+/// capture); a higher depth smooths over brief stalls at the cost of a
+/// few frames of extra latency - worth it for recording, not for a live
+/// low-latency stream.
+pub fn channel(depth: usize) -> (FrameSlotSender, FrameSlotReceiver) {
+    let depth = depth.max(1);
+
+    let inner = Arc::new(FrameSlotInner {
+        depth,
+        state: Mutex::new(FrameSlotState {
+            queue: VecDeque::with_capacity(depth),
+            sender_alive: true,
+            receiver_alive: true,
+        }),
+        notify: Notify::new(),
+        dropped: AtomicU64::new(0),
+    });
+
+    (FrameSlotSender { inner: inner.clone() }, FrameSlotReceiver { inner })
+}
+
+impl FrameSlotSender {
+    /// Push a frame, evicting the oldest buffered one if the slot is
+    /// already at `depth`. Returns `false` once the receiver has been
+    /// dropped, mirroring `mpsc::Sender::send().is_err()` as the signal
+    /// for the capture loop to stop.
+    pub fn push(&self, frame: DynamicImage) -> bool {
+        let mut state = self.inner.state.lock().unwrap();
+
+        if !state.receiver_alive {
+            return false;
+        }
+
+        if state.queue.len() >= self.inner.depth {
+            state.queue.pop_front();
+            let dropped = self.inner.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+
+            if dropped % 100 == 0 {
+                warn!("Capture pipeline stalled, {} frames dropped so far", dropped);
+            }
+        }
+
+        state.queue.push_back(frame);
+        drop(state);
+        self.inner.notify.notify_one();
+
+        true
+    }
+
+    /// Total frames evicted before the converter could consume them
+    pub fn dropped_frames(&self) -> u64 {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for FrameSlotSender {
+    fn drop(&mut self) {
+        self.inner.state.lock().unwrap().sender_alive = false;
+        self.inner.notify.notify_one();
+    }
+}
+
+impl FrameSlotReceiver {
+    /// Wait for the next frame, or `None` once the sender has dropped and
+    /// the queue has drained
+    pub async fn recv(&mut self) -> Option<DynamicImage> {
+        loop {
+            {
+                let mut state = self.inner.state.lock().unwrap();
+                if let Some(frame) = state.queue.pop_front() {
+                    return Some(frame);
+                }
+                if !state.sender_alive {
+                    return None;
+                }
+            }
+
+            self.inner.notify.notified().await;
+        }
+    }
+}
+
+impl Drop for FrameSlotReceiver {
+    fn drop(&mut self) {
+        self.inner.state.lock().unwrap().receiver_alive = false;
+        self.inner.notify.notify_one();
+    }
+}
@@ -3,19 +3,27 @@ mod converter;
 mod batcher;
 mod blockchain;
 mod counter_mode;
+mod frame_slot;
 
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use monegle_core::Config;
+use monegle_core::{CameraControlsConfig, Config};
 use tokio::sync::mpsc;
 use tracing::{error, info};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-use capture::VideoCapture;
+use capture::{open_capture_source, CaptureSource, CropRect, RtspTransport, ScreenCapture, ScreenTarget};
 use converter::AsciiConverter;
 use batcher::FrameBatcher;
 use blockchain::BlockchainSender;
 
+/// Capture backend selected by `--source`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum CaptureSourceKind {
+    Camera,
+    Screen,
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "monegle-sender")]
 #[command(about = "Monegle ASCII Video Streaming Sender", long_about = None)]
@@ -28,9 +36,34 @@ struct Args {
     #[arg(short, long)]
     stream: Option<String>,
 
-    /// Camera device index (overrides config)
+    /// Camera source (overrides config): a local device index, or an
+    /// `rtsp://` URL to relay a network camera/encoder instead
     #[arg(long)]
-    camera: Option<u32>,
+    camera: Option<String>,
+
+    /// Capture backend: the physical camera (local device or RTSP, see
+    /// `--camera`), or the desktop/a window
+    #[arg(long, value_enum, default_value = "camera")]
+    source: CaptureSourceKind,
+
+    /// RTSP transport to use when `--camera` is an `rtsp://` URL
+    #[arg(long, value_enum, default_value = "tcp")]
+    transport: RtspTransport,
+
+    /// Display index to capture (`--source screen`, ignored if `--window`
+    /// is set)
+    #[arg(long)]
+    display: Option<u32>,
+
+    /// Capture the first window whose title contains this substring
+    /// instead of a full display (`--source screen` only)
+    #[arg(long)]
+    window: Option<String>,
+
+    /// Crop the captured image to "x,y,width,height" before conversion
+    /// (`--source screen` only)
+    #[arg(long)]
+    crop: Option<String>,
 
     /// Target address for frame transactions (overrides config)
     #[arg(long)]
@@ -43,6 +76,54 @@ struct Args {
     /// Test counter mode: send frames with incrementing counter instead of camera
     #[arg(long)]
     counter: bool,
+
+    /// Show a live TUI dashboard of FPS/counter telemetry instead of
+    /// printing frames (dry-run counter mode only)
+    #[arg(long)]
+    dashboard: bool,
+
+    /// Stop counter test mode after this many frames instead of running
+    /// until interrupted
+    #[arg(long)]
+    max_frames: Option<u64>,
+
+    /// Pin camera exposure (overrides config; `--source camera` only)
+    #[arg(long)]
+    exposure: Option<i64>,
+
+    /// Explicitly request auto-exposure, overriding a config `exposure`
+    #[arg(long)]
+    auto_exposure: bool,
+
+    /// Pin camera sensor gain (overrides config; `--source camera` only)
+    #[arg(long)]
+    gain: Option<i64>,
+
+    /// Pin camera white balance (overrides config; `--source camera` only)
+    #[arg(long)]
+    white_balance: Option<i64>,
+
+    /// Explicitly request auto white balance, overriding a config
+    /// `white_balance`
+    #[arg(long)]
+    auto_white_balance: bool,
+
+    /// Pin camera brightness (overrides config; `--source camera` only)
+    #[arg(long)]
+    brightness: Option<i64>,
+
+    /// How many frames the capture-to-encode handoff buffers before
+    /// overwriting the oldest (overrides config). 1 minimizes latency for
+    /// a live stream; a higher value smooths over brief encoder stalls at
+    /// the cost of staler frames, which suits recording better
+    #[arg(long)]
+    capture_slot_depth: Option<usize>,
+
+    /// Apply Floyd-Steinberg error-diffusion dithering to brightness
+    /// before glyph selection (overrides config; smoother gradients at
+    /// small character grids, at the cost of per-pixel independence)
+    #[arg(long)]
+    dither: bool,
 }
 
 #[tokio::main]
@@ -105,16 +186,21 @@ async fn main() -> Result<()> {
     };
 
     // Initialize components
-    let camera_device = args.camera.unwrap_or(sender_config.camera_device);
+    let camera_source = args.camera.clone().unwrap_or_else(|| sender_config.camera_device.to_string());
+    let camera_controls = merge_camera_controls(&args, &sender_config.camera_controls);
+    let capture_slot_depth = args.capture_slot_depth.unwrap_or(sender_config.capture_slot_depth);
     let fps = sender_config.fps as u32;
     let width = sender_config.resolution[0] as u32;
     let height = sender_config.resolution[1] as u32;
 
+    let dither = args.dither || sender_config.dither;
+
     let ascii_converter = AsciiConverter::new(
         width,
         height,
         sender_config.character_set,
         sender_config.color_mode,
+        dither,
     );
 
     info!("Components initialized, starting pipeline");
@@ -129,6 +215,8 @@ async fn main() -> Result<()> {
                 height,
                 fps,
                 sender_config.color_mode,
+                args.dashboard,
+                args.max_frames,
             ).await?;
         } else {
             // Counter test mode with blockchain submission
@@ -140,6 +228,7 @@ async fn main() -> Result<()> {
                 character_set: sender_config.character_set,
                 color_mode: sender_config.color_mode,
                 frames_per_batch: sender_config.frames_per_batch,
+                keyframe_interval: sender_config.keyframe_interval as u32,
             };
 
             let target_address = args.target
@@ -161,11 +250,13 @@ async fn main() -> Result<()> {
                 sender_config.max_batch_size,
                 sender_config.keyframe_interval,
                 blockchain_sender,
+                args.max_frames,
             ).await?;
         }
     } else if args.dry_run {
-        // DRY RUN MODE: Camera → ASCII → Terminal Display
-        run_dry_run_mode(camera_device, fps, ascii_converter).await?;
+        // DRY RUN MODE: Camera/Screen → ASCII → Terminal Display
+        let capture_source = build_capture_source(&args, &camera_source, fps, &camera_controls)?;
+        run_dry_run_mode(capture_source, fps, ascii_converter, capture_slot_depth).await?;
     } else {
         // NORMAL MODE: Camera → ASCII → Batch → Blockchain
 
@@ -178,6 +269,7 @@ async fn main() -> Result<()> {
             character_set: sender_config.character_set,
             color_mode: sender_config.color_mode,
             frames_per_batch: sender_config.frames_per_batch,
+            keyframe_interval: sender_config.keyframe_interval as u32,
         };
 
         let frame_batcher = FrameBatcher::new(
@@ -196,12 +288,14 @@ async fn main() -> Result<()> {
             &target_address,
         ).await?;
 
+        let capture_source = build_capture_source(&args, &camera_source, fps, &camera_controls)?;
+
         run_normal_mode(
-            camera_device,
-            fps,
+            capture_source,
             ascii_converter,
             frame_batcher,
             blockchain_sender,
+            capture_slot_depth,
         ).await?;
     }
 
@@ -210,27 +304,79 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Build the capture backend selected by `--source`: the physical camera
+/// (local device or RTSP, dispatched by `open_capture_source`), or the
+/// desktop/a window via `ScreenCapture`
+fn build_capture_source(
+    args: &Args,
+    camera_source: &str,
+    fps: u32,
+    camera_controls: &CameraControlsConfig,
+) -> Result<Box<dyn CaptureSource>> {
+    match args.source {
+        CaptureSourceKind::Camera => {
+            open_capture_source(camera_source, args.transport, fps, camera_controls)
+        }
+        CaptureSourceKind::Screen => {
+            let target = match &args.window {
+                Some(window) => ScreenTarget::Window(window.clone()),
+                None => ScreenTarget::Display(args.display.unwrap_or(0) as usize),
+            };
+            let crop = args.crop.as_deref().map(parse_crop_rect).transpose()?;
+
+            Ok(Box::new(ScreenCapture::new(target, crop, fps)?))
+        }
+    }
+}
+
+/// Merge `--exposure`/`--gain`/`--white-balance`/`--brightness` and their
+/// `--auto-*` counterparts over the config file's `camera_controls`, with
+/// CLI flags taking priority field-by-field; an `--auto-*` flag also drops
+/// the config's manual value for that control
+fn merge_camera_controls(args: &Args, config_controls: &CameraControlsConfig) -> CameraControlsConfig {
+    CameraControlsConfig {
+        exposure: args.exposure.or(config_controls.exposure.filter(|_| !args.auto_exposure)),
+        auto_exposure: Some(args.auto_exposure).filter(|b| *b).or(config_controls.auto_exposure),
+        gain: args.gain.or(config_controls.gain),
+        white_balance: args
+            .white_balance
+            .or(config_controls.white_balance.filter(|_| !args.auto_white_balance)),
+        auto_white_balance: Some(args.auto_white_balance)
+            .filter(|b| *b)
+            .or(config_controls.auto_white_balance),
+        brightness: args.brightness.or(config_controls.brightness),
+    }
+}
+
+/// Parse a `"x,y,width,height"` crop rectangle as passed to `--crop`
+fn parse_crop_rect(s: &str) -> Result<CropRect> {
+    let parts: Vec<&str> = s.split(',').collect();
+    let [x, y, width, height] = parts.as_slice() else {
+        return Err(anyhow!("Invalid --crop value '{}', expected \"x,y,width,height\"", s));
+    };
+
+    Ok(CropRect {
+        x: x.trim().parse().map_err(|e| anyhow!("Invalid --crop x: {}", e))?,
+        y: y.trim().parse().map_err(|e| anyhow!("Invalid --crop y: {}", e))?,
+        width: width.trim().parse().map_err(|e| anyhow!("Invalid --crop width: {}", e))?,
+        height: height.trim().parse().map_err(|e| anyhow!("Invalid --crop height: {}", e))?,
+    })
+}
+
 /// Run in dry-run mode: display ASCII video in terminal
 async fn run_dry_run_mode(
-    camera_device: u32,
+    capture_source: Box<dyn CaptureSource>,
     fps: u32,
     ascii_converter: AsciiConverter,
+    capture_slot_depth: usize,
 ) -> Result<()> {
-    let (capture_tx, capture_rx) = mpsc::channel(10);
+    let (capture_tx, capture_rx) = frame_slot::channel(capture_slot_depth);
     let (convert_tx, mut convert_rx) = mpsc::channel::<String>(10);
 
-    // Spawn camera capture
+    // Spawn capture
     let mut capture_handle = tokio::task::spawn_blocking(move || {
-        match VideoCapture::new(camera_device, fps, 640, 480) {
-            Ok(video_capture) => {
-                info!("Camera opened successfully!");
-                if let Err(e) = video_capture.start_capture_loop_blocking(capture_tx) {
-                    error!("Capture loop error: {}", e);
-                }
-            }
-            Err(e) => {
-                error!("Failed to initialize camera: {}", e);
-            }
+        if let Err(e) = capture_source.start_capture_loop_blocking(capture_tx) {
+            error!("Capture loop error: {}", e);
         }
     });
 
@@ -308,27 +454,20 @@ async fn run_dry_run_mode(
 
 /// Run in normal mode: send to blockchain
 async fn run_normal_mode(
-    camera_device: u32,
-    fps: u32,
+    capture_source: Box<dyn CaptureSource>,
     ascii_converter: AsciiConverter,
     frame_batcher: FrameBatcher,
     blockchain_sender: BlockchainSender,
+    capture_slot_depth: usize,
 ) -> Result<()> {
-    let (capture_tx, capture_rx) = mpsc::channel(10);
+    let (capture_tx, capture_rx) = frame_slot::channel(capture_slot_depth);
     let (convert_tx, convert_rx) = mpsc::channel(10);
     let (batch_tx, batch_rx) = mpsc::channel(5);
 
-    // Spawn camera capture
+    // Spawn capture
     let capture_handle = tokio::task::spawn_blocking(move || {
-        match VideoCapture::new(camera_device, fps, 640, 480) {
-            Ok(video_capture) => {
-                if let Err(e) = video_capture.start_capture_loop_blocking(capture_tx) {
-                    error!("Capture loop error: {}", e);
-                }
-            }
-            Err(e) => {
-                error!("Failed to initialize camera: {}", e);
-            }
+        if let Err(e) = capture_source.start_capture_loop_blocking(capture_tx) {
+            error!("Capture loop error: {}", e);
         }
     });
 